@@ -0,0 +1,60 @@
+use bytes::Bytes;
+use revm::{
+    db::{CacheDB, EmptyDB},
+    primitives::{AccountInfo, Bytecode, TransactTo, B160, U256},
+};
+use std::time::{Duration, Instant};
+extern crate alloc;
+
+/// Number of EIP-1167 clones that are copied in one transaction.
+const CLONES: u64 = 100;
+/// Address of the first clone.
+const FIRST_CLONE: u64 = 0x1000;
+
+fn main() {
+    // EIP-1167 minimal proxy pointing to 0xbebebebebebebebebebebebebebebebebebebebe.
+    let clone: Bytes = hex::decode(
+        "363d3d373d3d3d363d73bebebebebebebebebebebebebebebebebebebebe5af43d82803e903d91602b57fd5bf3",
+    )
+    .unwrap()
+    .into();
+
+    // for address in 0x1000..0x1064 { extcodecopy(address, 0, 0, 45) }
+    let contract: Bytes = hex::decode("6110005b602d60006000833c600101806110641160035700")
+        .unwrap()
+        .into();
+
+    let mut db = CacheDB::new(EmptyDB::default());
+    db.insert_account_info(
+        B160::zero(),
+        AccountInfo::new(U256::ZERO, 1, Bytecode::new_raw(contract)),
+    );
+    for i in 0..CLONES {
+        db.insert_account_info(
+            B160::from_low_u64_be(FIRST_CLONE + i),
+            AccountInfo::new(U256::ZERO, 1, Bytecode::new_raw(clone.clone())),
+        );
+    }
+
+    let mut evm = revm::new();
+    evm.database(db);
+
+    evm.env.tx.caller = "0x0000000000000000000000000000000000000001"
+        .parse()
+        .unwrap();
+    evm.env.tx.transact_to = TransactTo::Call(B160::zero());
+
+    // Microbenchmark
+    let bench_options = microbench::Options::default().time(Duration::from_secs(1));
+
+    microbench::bench(&bench_options, "EXTCODECOPY of EIP-1167 clones", || {
+        let _ = evm.transact().unwrap();
+    });
+
+    let time = Instant::now();
+    for _ in 0..10000 {
+        let _ = evm.transact().unwrap();
+    }
+    let elapsed = time.elapsed();
+    println!("10k runs in {:?}", elapsed.as_nanos() / 10_000);
+}
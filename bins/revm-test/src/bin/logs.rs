@@ -0,0 +1,70 @@
+use bytes::Bytes;
+use revm::{
+    db::BenchmarkDB,
+    primitives::{Bytecode, TransactTo, B160, B256},
+    Database, EVMData, Inspector,
+};
+use std::time::{Duration, Instant};
+extern crate alloc;
+
+/// Counts logs and their data without copying it.
+#[derive(Default)]
+struct LogCounter {
+    logs: usize,
+    data_len: usize,
+}
+
+impl<DB: Database> Inspector<DB> for LogCounter {
+    fn log(
+        &mut self,
+        _evm_data: &mut EVMData<'_, DB>,
+        _address: &B160,
+        _topics: &[B256],
+        data: &[u8],
+    ) {
+        self.logs += 1;
+        self.data_len += data.len();
+    }
+}
+
+fn main() {
+    // Airdrop that emits 100 ERC-20 `Transfer(caller, i, 1)` events.
+    let contract: Bytes = hex::decode(
+        "60645b600160005280337fddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef60206000a3600190038060025700",
+    )
+    .unwrap()
+    .into();
+
+    // BenchmarkDB is dummy state that implements Database trait.
+    let mut evm = revm::new();
+    evm.database(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract)));
+
+    evm.env.tx.caller = "0x0000000000000000000000000000000000000001"
+        .parse()
+        .unwrap();
+    evm.env.tx.transact_to = TransactTo::Call(B160::zero());
+
+    // Microbenchmark
+    let bench_options = microbench::Options::default().time(Duration::from_secs(1));
+
+    microbench::bench(&bench_options, "Airdrop logs", || {
+        let _ = evm.transact().unwrap();
+    });
+
+    microbench::bench(&bench_options, "Airdrop logs with inspector", || {
+        let _ = evm.inspect(LogCounter::default()).unwrap();
+    });
+
+    let time = Instant::now();
+    let mut counter = LogCounter::default();
+    for _ in 0..10000 {
+        let _ = evm.inspect(&mut counter).unwrap();
+    }
+    let elapsed = time.elapsed();
+    println!(
+        "10k inspected runs in {:?}, {} logs with {} bytes of data",
+        elapsed.as_nanos() / 10_000,
+        counter.logs,
+        counter.data_len
+    );
+}
@@ -0,0 +1,201 @@
+use bytes::Bytes;
+use revm::{
+    db::BenchmarkDB,
+    interpreter::{analysis::to_analysed, InstructionResult, Interpreter},
+    primitives::{Bytecode, ExecutionResult, TransactTo, B160},
+    Database, EVMData, Inspector, EVM,
+};
+use std::{
+    fmt,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+use structopt::StructOpt;
+use thiserror::Error as ThisError;
+
+/// Contract from https://github.com/karalabe/snailtracer, called with `Benchmark()`.
+const SNAILTRACER: &str = include_str!("snailtracer.hex");
+
+/// Hand written contract that does 100 token transfers. Every transfer decrements the balance
+/// of the caller, increments the balance of a new receiver and emits the `Transfer` event.
+/// Balances are stored in the `mapping(address => uint256)` at slot 0.
+const ERC20: &str = "60645b8015610064573360005260006020526040600020805460019003905580600052604060\
+    002080546001019055600160005280337fddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a\
+    4df523b3ef60206000a360019003610002565b00";
+
+/// Hand written contract that fills memory with 64 words in descending order, sorts them with
+/// bubble sort and returns the sorted words.
+const BUBBLESORT: &str = "60005b8060401461001b57806040038160051b52600101610002565b5060405b60018111\
+    1561006d5760005b6001810182811015610062578160051b518160051b5181811015610058578360051b528160\
+    051b5261005b565b50505b905061002b565b50506001900361001f565b6108006000f3";
+
+/// Hand written contract that hashes the first memory word with KECCAK256 10000 times, storing
+/// the hash back to the memory, and returns the last hash.
+const KECCAK_LOOP: &str = "6127105b801561001a57602060002060005260019003610003565b60206000f3";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Workload {
+    Snailtracer,
+    Erc20,
+    Bubblesort,
+    KeccakLoop,
+}
+
+impl Workload {
+    /// Bytecode of the contract and the input it is called with.
+    fn contract(&self) -> (Bytes, Bytes) {
+        let (code, input) = match self {
+            Self::Snailtracer => (SNAILTRACER.trim(), "30627b7c"),
+            Self::Erc20 => (ERC20, ""),
+            Self::Bubblesort => (BUBBLESORT, ""),
+            Self::KeccakLoop => (KECCAK_LOOP, ""),
+        };
+        (
+            hex::decode(code).unwrap().into(),
+            hex::decode(input).unwrap().into(),
+        )
+    }
+}
+
+impl FromStr for Workload {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "snailtracer" => Ok(Self::Snailtracer),
+            "erc20" => Ok(Self::Erc20),
+            "bubblesort" => Ok(Self::Bubblesort),
+            "keccak-loop" => Ok(Self::KeccakLoop),
+            _ => Err(format!(
+                "unknown workload {s}, expected snailtracer, erc20, bubblesort or keccak-loop"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Workload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Snailtracer => "snailtracer",
+            Self::Erc20 => "erc20",
+            Self::Bubblesort => "bubblesort",
+            Self::KeccakLoop => "keccak-loop",
+        })
+    }
+}
+
+#[derive(Debug, ThisError)]
+pub enum Error {
+    #[error("Workload {workload} did not succeed: {result:?}")]
+    Failed {
+        workload: Workload,
+        result: ExecutionResult,
+    },
+}
+
+/// Runs one of the bundled benchmark contracts and reports instructions and gas per second.
+#[derive(StructOpt, Debug)]
+pub struct Cmd {
+    /// snailtracer, erc20, bubblesort or keccak-loop
+    workload: Workload,
+    /// Number of times the contract is executed.
+    #[structopt(short = "i", long, default_value = "100")]
+    iterations: u32,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let (code, input) = self.workload.contract();
+
+        // BenchmarkDB has the contract at the zero address.
+        let mut evm = revm::new();
+        evm.database(BenchmarkDB::new_bytecode(to_analysed(Bytecode::new_raw(
+            code,
+        ))));
+        evm.env.tx.caller = B160::from(1);
+        evm.env.tx.transact_to = TransactTo::Call(B160::zero());
+        evm.env.tx.data = input;
+
+        // instructions are counted once, so counting does not slow down the measured runs.
+        let mut counter = InstructionCounter::default();
+        let result = evm.inspect(&mut counter).unwrap().result;
+        if !result.is_success() {
+            return Err(Error::Failed {
+                workload: self.workload,
+                result,
+            });
+        }
+        let gas_used = result.gas_used();
+
+        let elapsed = measure(&mut evm, self.iterations);
+        println!("Workload: {}", self.workload);
+        println!("Iterations: {}", self.iterations);
+        println!("Time per iteration: {:?}", elapsed / self.iterations.max(1));
+        println!("Instructions per iteration: {}", counter.instructions);
+        println!("Gas per iteration: {gas_used}");
+        println!(
+            "Instructions/sec: {}",
+            format_rate(per_sec(counter.instructions, self.iterations, elapsed))
+        );
+        println!(
+            "Gas/sec: {}",
+            format_rate(per_sec(gas_used, self.iterations, elapsed))
+        );
+        Ok(())
+    }
+}
+
+/// Rate of `amount` per iteration over all iterations, `None` if nothing was measured.
+fn per_sec(amount: u64, iterations: u32, elapsed: Duration) -> Option<f64> {
+    let secs = elapsed.as_secs_f64();
+    if iterations == 0 || secs == 0.0 {
+        return None;
+    }
+    Some(amount as f64 * iterations as f64 / secs)
+}
+
+fn format_rate(rate: Option<f64>) -> String {
+    match rate {
+        Some(rate) => format!("{rate:.0}"),
+        None => "n/a".to_string(),
+    }
+}
+
+fn measure(evm: &mut EVM<BenchmarkDB>, iterations: u32) -> Duration {
+    let time = Instant::now();
+    for _ in 0..iterations {
+        let _ = evm.transact().unwrap();
+    }
+    time.elapsed()
+}
+
+/// Counts executed instructions of all frames.
+#[derive(Default)]
+struct InstructionCounter {
+    instructions: u64,
+}
+
+impl<DB: Database> Inspector<DB> for InstructionCounter {
+    fn step(
+        &mut self,
+        _interp: &mut Interpreter,
+        _data: &mut EVMData<'_, DB>,
+    ) -> InstructionResult {
+        self.instructions += 1;
+        InstructionResult::Continue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_without_iterations() {
+        assert_eq!(per_sec(100, 0, Duration::from_secs(1)), None);
+        assert_eq!(per_sec(100, 10, Duration::ZERO), None);
+        assert_eq!(per_sec(100, 10, Duration::from_secs(2)), Some(500.0));
+        assert_eq!(format_rate(None), "n/a");
+        assert_eq!(format_rate(Some(500.4)), "500");
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{runner, statetest};
+use crate::{bench, runner, statetest};
 use structopt::{clap::AppSettings, StructOpt};
 
 #[derive(StructOpt, Debug)]
@@ -7,6 +7,7 @@ use structopt::{clap::AppSettings, StructOpt};
 pub enum MainCmd {
     Statetest(statetest::Cmd),
     Run(runner::Cmd),
+    Bench(bench::Cmd),
 }
 
 use thiserror::Error as ThisError;
@@ -15,6 +16,8 @@ use thiserror::Error as ThisError;
 pub enum Error {
     #[error("Statetest: {0}")]
     Statetest(statetest::Error),
+    #[error("Bench: {0}")]
+    Bench(bench::Error),
     #[error("Generic system error")]
     SystemError,
 }
@@ -23,6 +26,7 @@ impl MainCmd {
     pub fn run(&self) -> Result<(), Error> {
         match self {
             Self::Statetest(cmd) => cmd.run().map_err(Error::Statetest),
+            Self::Bench(cmd) => cmd.run().map_err(Error::Bench),
             _ => Ok(()),
         }
     }
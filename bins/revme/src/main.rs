@@ -1,3 +1,4 @@
+mod bench;
 mod cmd;
 mod exec;
 mod runner;
@@ -68,6 +68,7 @@ pub struct Env {
     pub current_timestamp: U256,
     pub current_base_fee: Option<U256>,
     pub previous_hash: B256,
+    pub current_excess_blob_gas: Option<U256>,
 }
 
 #[derive(Debug, PartialEq, Eq, Deserialize)]
@@ -177,6 +177,10 @@ pub fn execute_test_suit(
         env.block.timestamp = unit.env.current_timestamp;
         env.block.gas_limit = unit.env.current_gas_limit;
         env.block.basefee = unit.env.current_base_fee.unwrap_or_default();
+        if let Some(current_excess_blob_gas) = unit.env.current_excess_blob_gas {
+            env.block
+                .set_blob_excess_gas_and_price(current_excess_blob_gas.to());
+        }
         env.block.difficulty = unit.env.current_difficulty;
         // after the Merge prevrandao replaces mix_hash field in block and replaced difficulty opcode in EVM.
         env.block.prevrandao = Some(unit.env.current_difficulty.to_be_bytes().into());
@@ -305,12 +309,16 @@ pub fn execute_test_suit(
                         }) => {
                             println!("Failed reason: {reason:?} {path:?} UNIT_TEST:{name}\n gas:{gas_used:?} ({gas_refunded:?} refunded)");
                         }
-                        Ok(ExecutionResult::Revert { gas_used, output }) => {
+                        Ok(ExecutionResult::Revert {
+                            gas_used, output, ..
+                        }) => {
                             println!(
                                 "Reverted: {output:?} {path:?} UNIT_TEST:{name}\n gas:{gas_used:?}"
                             );
                         }
-                        Ok(ExecutionResult::Halt { reason, gas_used }) => {
+                        Ok(ExecutionResult::Halt {
+                            reason, gas_used, ..
+                        }) => {
                             println!(
                                 "Halted: {reason:?} {path:?} UNIT_TEST:{name}\n gas:{gas_used:?}"
                             );
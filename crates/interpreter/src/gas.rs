@@ -1,8 +1,10 @@
 pub mod calc;
 pub mod constants;
+pub mod schedule;
 
 pub use calc::*;
 pub use constants::*;
+pub use schedule::*;
 
 #[derive(Clone, Copy, Debug)]
 pub struct Gas {
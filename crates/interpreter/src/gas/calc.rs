@@ -5,17 +5,28 @@ use crate::{
     primitives::Spec,
     primitives::{SpecId::*, U256},
 };
-use revm_primitives::{Bytes, B160};
+use revm_primitives::{Bytes, Env, InvalidTransaction, B160};
 
-#[allow(clippy::collapsible_else_if)]
 pub fn sstore_refund<SPEC: Spec>(original: U256, current: U256, new: U256) -> i64 {
+    sstore_refund_with_eip3529::<SPEC>(original, current, new, SPEC::enabled(LONDON))
+}
+
+/// Same as [sstore_refund] but EIP-3529 reduction in refunds is selected by `eip3529`
+/// instead of the spec.
+#[allow(clippy::collapsible_else_if)]
+pub fn sstore_refund_with_eip3529<SPEC: Spec>(
+    original: U256,
+    current: U256,
+    new: U256,
+    eip3529: bool,
+) -> i64 {
+    // EIP-3529: Reduction in refunds
+    let sstore_clears_schedule = if eip3529 {
+        (SSTORE_RESET - COLD_SLOAD_COST + ACCESS_LIST_STORAGE_KEY) as i64
+    } else {
+        REFUND_SSTORE_CLEARS
+    };
     if SPEC::enabled(ISTANBUL) {
-        // EIP-3529: Reduction in refunds
-        let sstore_clears_schedule = if SPEC::enabled(LONDON) {
-            (SSTORE_RESET - COLD_SLOAD_COST + ACCESS_LIST_STORAGE_KEY) as i64
-        } else {
-            REFUND_SSTORE_CLEARS
-        };
         if current == new {
             0
         } else {
@@ -50,7 +61,7 @@ pub fn sstore_refund<SPEC: Spec>(original: U256, current: U256, new: U256) -> i6
         }
     } else {
         if current != U256::ZERO && new == U256::ZERO {
-            REFUND_SSTORE_CLEARS
+            sstore_clears_schedule
         } else {
             0
         }
@@ -71,12 +82,11 @@ pub fn create2_cost(len: usize) -> Option<u64> {
 fn log2floor(value: U256) -> u64 {
     assert!(value != U256::ZERO);
     let mut l: u64 = 256;
-    for i in 0..4 {
-        let i = 3 - i;
-        if value.as_limbs()[i] == 0u64 {
+    for limb in value.as_limbs().iter().rev() {
+        if *limb == 0u64 {
             l -= 64;
         } else {
-            l -= value.as_limbs()[i].leading_zeros() as u64;
+            l -= limb.leading_zeros() as u64;
             if l == 0 {
                 return l;
             } else {
@@ -373,3 +383,18 @@ pub fn initial_tx_gas<SPEC: Spec>(
 
     initial_gas
 }
+
+/// Checks that the gas limit of the transaction covers its [initial_tx_gas].
+///
+/// Returns the initial gas.
+pub fn validate_initial_tx_gas<SPEC: Spec>(env: &Env) -> Result<u64, InvalidTransaction> {
+    let initial_gas = initial_tx_gas::<SPEC>(
+        &env.tx.data,
+        env.tx.transact_to.is_create(),
+        &env.tx.access_list,
+    );
+    if env.tx.gas_limit < initial_gas {
+        return Err(InvalidTransaction::CallGasCostMoreThanGasLimit);
+    }
+    Ok(initial_gas)
+}
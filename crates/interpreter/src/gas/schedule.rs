@@ -0,0 +1,239 @@
+use super::constants::*;
+use crate::opcode::{self, OPCODE_JUMPMAP};
+use crate::primitives::SpecId;
+use alloc::vec::Vec;
+
+/// Formula of the gas that is charged on top of the constant gas of the opcode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum DynamicGas {
+    /// Memory expansion.
+    Memory,
+    /// Per byte of the exponent, see [super::exp_cost].
+    Exp,
+    /// Per word of hashed data and memory expansion, see [super::keccak256_cost].
+    Keccak256,
+    /// Per word of copied data and memory expansion, see [super::verylowcopy_cost].
+    Copy,
+    /// Cold account access, see [super::account_access_gas].
+    AccountAccess,
+    /// Cold account access, per word of copied code and memory expansion,
+    /// see [super::extcodecopy_cost].
+    ExtCodeCopy,
+    /// Cold storage slot access, see [super::sload_cost].
+    Sload,
+    /// Storage slot change and cold storage slot access, see [super::sstore_cost].
+    Sstore,
+    /// Per byte of log data and memory expansion, see [super::log_cost].
+    Log,
+    /// Initcode words (EIP-3860) and memory expansion, see [super::initcode_cost].
+    Create,
+    /// Per word of hashed initcode, initcode words (EIP-3860) and memory expansion,
+    /// see [super::create2_cost].
+    Create2,
+    /// Cold account access, value transfer, new account creation and memory expansion,
+    /// see [super::call_cost].
+    Call,
+    /// Cold account access and new account creation, see [super::selfdestruct_cost].
+    Selfdestruct,
+}
+
+/// Gas schedule of the opcode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct OpcodeGas {
+    pub opcode: u8,
+    pub name: &'static str,
+    /// Gas that is charged every time opcode is executed.
+    pub constant: u64,
+    /// Formula of the gas that depends on the operands and state, if any.
+    pub dynamic: Option<DynamicGas>,
+}
+
+/// Gas schedule of all opcodes that are valid in the given spec, sorted by opcode.
+pub fn opcode_gas_table(spec: SpecId) -> Vec<OpcodeGas> {
+    (0..=u8::MAX)
+        .filter_map(|opcode| opcode_gas(opcode, spec))
+        .collect()
+}
+
+/// Gas schedule of the opcode, `None` if opcode is not valid in the given spec.
+pub fn opcode_gas(opcode: u8, spec: SpecId) -> Option<OpcodeGas> {
+    let enabled = |id| SpecId::enabled(spec, id);
+    let berlin = enabled(SpecId::BERLIN);
+    let tangerine = enabled(SpecId::TANGERINE);
+    let introduced = match opcode {
+        opcode::DELEGATECALL => SpecId::HOMESTEAD,
+        opcode::RETURNDATASIZE | opcode::RETURNDATACOPY | opcode::STATICCALL | opcode::REVERT => {
+            SpecId::BYZANTIUM
+        }
+        opcode::SHL | opcode::SHR | opcode::SAR | opcode::EXTCODEHASH => SpecId::CONSTANTINOPLE,
+        opcode::CREATE2 => SpecId::PETERSBURG,
+        opcode::CHAINID | opcode::SELFBALANCE => SpecId::ISTANBUL,
+        opcode::BASEFEE => SpecId::LONDON,
+        opcode::PUSH0 => SpecId::SHANGHAI,
+        opcode::TLOAD | opcode::TSTORE | opcode::MCOPY | opcode::BLOBBASEFEE => SpecId::CANCUN,
+        _ => SpecId::FRONTIER,
+    };
+    if !enabled(introduced) || opcode == opcode::INVALID {
+        return None;
+    }
+    let name = OPCODE_JUMPMAP.get(opcode as usize).copied().flatten()?;
+
+    // access cost of account before Berlin (EIP-2929).
+    let access = |istanbul: u64, tangerine_cost: u64, frontier: u64| {
+        if berlin {
+            WARM_STORAGE_READ_COST
+        } else if enabled(SpecId::ISTANBUL) {
+            istanbul
+        } else if tangerine {
+            tangerine_cost
+        } else {
+            frontier
+        }
+    };
+    let account_access = berlin.then_some(DynamicGas::AccountAccess);
+
+    // constant gas of the opcodes without dynamic gas is taken from the analysis table.
+    let static_gas = opcode::spec_opcode_gas(spec)
+        .get(opcode as usize)
+        .map_or(ZERO, |info| info.get_gas() as u64);
+    let (constant, dynamic) = match opcode {
+        opcode::RETURN | opcode::REVERT => (ZERO, Some(DynamicGas::Memory)),
+        // charged by the instruction, analysis only marks the block start.
+        opcode::JUMPDEST => (JUMPDEST, None),
+        opcode::EXP => (EXP, Some(DynamicGas::Exp)),
+        opcode::KECCAK256 => (KECCAK256, Some(DynamicGas::Keccak256)),
+        opcode::MLOAD | opcode::MSTORE | opcode::MSTORE8 => (static_gas, Some(DynamicGas::Memory)),
+        opcode::CALLDATACOPY | opcode::CODECOPY | opcode::RETURNDATACOPY | opcode::MCOPY => {
+            (VERYLOW, Some(DynamicGas::Copy))
+        }
+        opcode::BALANCE => (access(700, 400, 20), account_access),
+        opcode::EXTCODESIZE | opcode::EXTCODEHASH => (static_gas, account_access),
+        opcode::EXTCODECOPY => (static_gas, Some(DynamicGas::ExtCodeCopy)),
+        opcode::SLOAD => (access(800, 200, 50), berlin.then_some(DynamicGas::Sload)),
+        // minimal cost is the cost of no-op store.
+        opcode::SSTORE => (
+            if enabled(SpecId::ISTANBUL) {
+                access(800, 800, 800)
+            } else {
+                SSTORE_RESET
+            },
+            Some(DynamicGas::Sstore),
+        ),
+        opcode::LOG0..=opcode::LOG4 => (
+            LOG + LOGTOPIC * (opcode - opcode::LOG0) as u64,
+            Some(DynamicGas::Log),
+        ),
+        opcode::CREATE => (CREATE, Some(DynamicGas::Create)),
+        opcode::CREATE2 => (CREATE, Some(DynamicGas::Create2)),
+        opcode::CALL | opcode::CALLCODE | opcode::DELEGATECALL | opcode::STATICCALL => {
+            (access(700, 700, 40), Some(DynamicGas::Call))
+        }
+        opcode::SELFDESTRUCT => (
+            if tangerine { 5000 } else { ZERO },
+            Some(DynamicGas::Selfdestruct),
+        ),
+        _ => (static_gas, None),
+    };
+    Some(OpcodeGas {
+        opcode,
+        name,
+        constant,
+        dynamic,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{
+        BerlinSpec, Bytecode, Bytes, CancunSpec, Env, FrontierSpec, IstanbulSpec, Spec,
+        TangerineSpec, B160, U256,
+    };
+    use crate::{Contract, DummyHost, InstructionResult, Interpreter};
+
+    /// Opcodes that can't be executed with [DummyHost].
+    const UNSUPPORTED: [u8; 7] = [
+        opcode::CREATE,
+        opcode::CREATE2,
+        opcode::CALL,
+        opcode::CALLCODE,
+        opcode::DELEGATECALL,
+        opcode::STATICCALL,
+        opcode::SELFDESTRUCT,
+    ];
+
+    /// Executes every opcode with zeroed operands and compares spent gas with the table.
+    fn check_spec<SPEC: Spec>() {
+        let table = opcode_gas_table(SPEC::SPEC_ID);
+        for opcode in 0..=u8::MAX {
+            if UNSUPPORTED.contains(&opcode) {
+                continue;
+            }
+            let contract = Contract::new(
+                Bytes::new(),
+                Bytecode::new_raw(Bytes::from(vec![opcode, opcode::STOP])),
+                B160::zero(),
+                B160::zero(),
+                U256::ZERO,
+            );
+            let mut interpreter = Interpreter::new(Box::new(contract), 1_000_000, false);
+            // enough operands for SWAP16.
+            for _ in 0..17 {
+                interpreter.stack.push(U256::ZERO).unwrap();
+            }
+            let mut host = DummyHost::new(Env::default());
+            interpreter.step::<DummyHost, SPEC>(&mut host);
+            let spent = interpreter.gas.spend();
+
+            let Some(gas) = table.iter().find(|gas| gas.opcode == opcode) else {
+                assert!(
+                    matches!(
+                        interpreter.instruction_result,
+                        InstructionResult::OpcodeNotFound
+                            | InstructionResult::NotActivated
+                            | InstructionResult::InvalidFEOpcode
+                    ),
+                    "{opcode:#x} is missing in {:?}",
+                    SPEC::SPEC_ID
+                );
+                continue;
+            };
+            if gas.dynamic.is_none() {
+                assert_eq!(spent, gas.constant, "{} in {:?}", gas.name, SPEC::SPEC_ID);
+            } else {
+                assert!(spent >= gas.constant, "{} in {:?}", gas.name, SPEC::SPEC_ID);
+            }
+        }
+    }
+
+    #[test]
+    fn table_matches_interpreter() {
+        check_spec::<FrontierSpec>();
+        check_spec::<TangerineSpec>();
+        check_spec::<IstanbulSpec>();
+        check_spec::<BerlinSpec>();
+        check_spec::<CancunSpec>();
+    }
+
+    #[test]
+    fn table_contents() {
+        let berlin = opcode_gas_table(SpecId::BERLIN);
+        assert!(berlin.iter().all(|gas| gas.opcode != opcode::PUSH0));
+        let balance = berlin
+            .iter()
+            .find(|gas| gas.opcode == opcode::BALANCE)
+            .unwrap();
+        assert_eq!(balance.constant, WARM_STORAGE_READ_COST);
+        assert_eq!(balance.dynamic, Some(DynamicGas::AccountAccess));
+
+        let shanghai = opcode_gas_table(SpecId::SHANGHAI);
+        assert_eq!(shanghai.len(), berlin.len() + 2);
+        assert_eq!(
+            opcode_gas(opcode::LOG2, SpecId::FRONTIER).unwrap().constant,
+            1125
+        );
+    }
+}
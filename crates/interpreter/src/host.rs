@@ -1,6 +1,8 @@
 mod dummy_host;
 
 use crate::primitives::Bytecode;
+#[cfg(feature = "gas_by_category")]
+use crate::primitives::GasByCategory;
 use crate::{
     primitives::{Bytes, Env, B160, B256, U256},
     CallInputs, CreateInputs, Gas, InstructionResult, Interpreter, SelfDestructResult,
@@ -54,4 +56,7 @@ pub trait Host {
     ) -> (InstructionResult, Option<B160>, Gas, Bytes);
     /// Invoke a call operation.
     fn call(&mut self, input: &mut CallInputs) -> (InstructionResult, Gas, Bytes);
+    /// Gas used by all frames of the transaction, grouped by category.
+    #[cfg(feature = "gas_by_category")]
+    fn gas_by_category(&mut self) -> &mut GasByCategory;
 }
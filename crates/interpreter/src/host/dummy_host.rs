@@ -10,6 +10,8 @@ pub struct DummyHost {
     pub storage: HashMap<U256, U256>,
     pub transient_storage: HashMap<U256, U256>,
     pub log: Vec<Log>,
+    #[cfg(feature = "gas_by_category")]
+    pub gas_by_category: crate::primitives::GasByCategory,
 }
 
 impl DummyHost {
@@ -19,6 +21,8 @@ impl DummyHost {
             storage: HashMap::new(),
             transient_storage: Default::default(),
             log: Vec::new(),
+            #[cfg(feature = "gas_by_category")]
+            gas_by_category: Default::default(),
         }
     }
     pub fn clear(&mut self) {
@@ -110,18 +114,35 @@ impl Host for DummyHost {
         })
     }
 
+    /// Selfdestruct is not supported, it fails with [InstructionResult::FatalExternalError].
     fn selfdestruct(&mut self, _address: B160, _target: B160) -> Option<SelfDestructResult> {
-        panic!("Selfdestruct is not supported for this host")
+        None
     }
 
+    /// Creates are not supported, they fail with [InstructionResult::FatalExternalError].
     fn create(
         &mut self,
         _inputs: &mut CreateInputs,
     ) -> (InstructionResult, Option<B160>, Gas, Bytes) {
-        panic!("Create is not supported for this host")
+        (
+            InstructionResult::FatalExternalError,
+            None,
+            Gas::new(0),
+            Bytes::new(),
+        )
     }
 
+    /// Calls are not supported, they fail with [InstructionResult::FatalExternalError].
     fn call(&mut self, _input: &mut CallInputs) -> (InstructionResult, Gas, Bytes) {
-        panic!("Call is not supported for this host")
+        (
+            InstructionResult::FatalExternalError,
+            Gas::new(0),
+            Bytes::new(),
+        )
+    }
+
+    #[cfg(feature = "gas_by_category")]
+    fn gas_by_category(&mut self) -> &mut crate::primitives::GasByCategory {
+        &mut self.gas_by_category
     }
 }
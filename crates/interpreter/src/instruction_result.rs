@@ -1,4 +1,9 @@
-use revm_primitives::{Eval, Halt};
+use crate::OpCode;
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use revm_primitives::{Eval, Halt, OutOfGasError};
 
 #[repr(u8)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -40,6 +45,10 @@ pub enum InstructionResult {
     CreateContractStartingWithEF,
     /// EIP-3860: Limit and meter initcode. Initcode size limit exceeded.
     CreateInitcodeSizeLimit,
+    /// Address is denied by the address policy of the inspector.
+    AddressDenied,
+    /// Precompile is excluded from the build by its cargo feature.
+    PrecompileDisabled,
 
     // Fatal external error. Returned by database.
     FatalExternalError,
@@ -70,9 +79,103 @@ impl InstructionResult {
                 | Self::CreateContractSizeLimit
                 | Self::CreateContractStartingWithEF
                 | Self::CreateInitcodeSizeLimit
+                | Self::AddressDenied
+                | Self::PrecompileDisabled
                 | Self::FatalExternalError
         )
     }
+
+    /// How the call or create frame that ended with this result terminated.
+    ///
+    /// Returns `None` for [InstructionResult::Continue] as it is not a terminal result.
+    pub fn frame_exit(&self) -> Option<FrameExit> {
+        match SuccessOrHalt::from(*self) {
+            SuccessOrHalt::Success(Eval::Stop) => Some(FrameExit::Stop),
+            SuccessOrHalt::Success(Eval::Return) => Some(FrameExit::Return),
+            SuccessOrHalt::Success(Eval::SelfDestruct) => Some(FrameExit::SelfDestruct),
+            SuccessOrHalt::Revert => Some(FrameExit::Revert),
+            SuccessOrHalt::Halt(halt) => Some(FrameExit::Halt(halt)),
+            SuccessOrHalt::FatalExternalError => Some(FrameExit::FatalExternalError),
+            SuccessOrHalt::InternalContinue => None,
+        }
+    }
+}
+
+/// How a call or create frame terminated, see [InstructionResult::frame_exit].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FrameExit {
+    /// STOP opcode or end of the bytecode.
+    Stop,
+    /// RETURN opcode, output is the returned data.
+    Return,
+    /// SELFDESTRUCT opcode.
+    SelfDestruct,
+    /// REVERT opcode, output is the revert data.
+    Revert,
+    /// Exceptional halt. Frames that were not executed because of the call depth limit
+    /// or insufficient balance are reported as halts too.
+    Halt(Halt),
+    /// Database error, execution is aborted.
+    FatalExternalError,
+}
+
+impl FrameExit {
+    /// Returns true if frame ended with STOP, RETURN or SELFDESTRUCT.
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Stop | Self::Return | Self::SelfDestruct)
+    }
+
+    /// Returns true if frame ended with REVERT.
+    pub fn is_revert(&self) -> bool {
+        matches!(self, Self::Revert)
+    }
+
+    /// Returns true if frame ended with an exceptional halt.
+    pub fn is_halt(&self) -> bool {
+        matches!(self, Self::Halt(_))
+    }
+
+    /// Error of the frame with the same text as the errors of the geth EVM, `None` on success.
+    ///
+    /// `opcode` is the last executed opcode, it is named in the invalid opcode errors.
+    /// Halts that geth doesn't have use their [Halt] description.
+    pub fn error(&self, opcode: u8) -> Option<String> {
+        let invalid_opcode = || match OpCode::try_from_u8(opcode) {
+            Some(opcode) => format!("invalid opcode: {}", opcode.as_str()),
+            None => format!("invalid opcode: opcode {opcode:#x} not defined"),
+        };
+        let error = match self {
+            Self::Stop | Self::Return | Self::SelfDestruct => return None,
+            Self::Revert => "execution reverted".to_string(),
+            Self::FatalExternalError => "fatal external error".to_string(),
+            Self::Halt(halt) => match halt {
+                Halt::OutOfGas(OutOfGasError::InvalidOperand) => "gas uint64 overflow".to_string(),
+                Halt::OutOfGas(_) => "out of gas".to_string(),
+                Halt::OpcodeNotFound | Halt::InvalidFEOpcode | Halt::NotActivated => {
+                    invalid_opcode()
+                }
+                Halt::InvalidJump => "invalid jump destination".to_string(),
+                Halt::StackUnderflow => "stack underflow".to_string(),
+                Halt::StackOverflow => "stack limit reached".to_string(),
+                Halt::OutOfOffset => "return data out of bounds".to_string(),
+                Halt::CreateCollision => "contract address collision".to_string(),
+                Halt::NonceOverflow => "nonce uint64 overflow".to_string(),
+                Halt::CreateContractSizeLimit => "max code size exceeded".to_string(),
+                Halt::CreateContractStartingWithEF => {
+                    "invalid code: must not begin with 0xef".to_string()
+                }
+                Halt::CreateInitcodeSizeLimit => "max initcode size exceeded".to_string(),
+                Halt::StateChangeDuringStaticCall | Halt::CallNotAllowedInsideStatic => {
+                    "write protection".to_string()
+                }
+                Halt::OutOfFund => "insufficient balance for transfer".to_string(),
+                Halt::CallTooDeep => "max call depth exceeded".to_string(),
+                halt => halt.to_string(),
+            },
+        };
+        Some(error)
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -162,9 +265,11 @@ impl From<InstructionResult> for SuccessOrHalt {
             InstructionResult::NonceOverflow => Self::Halt(Halt::NonceOverflow),
             InstructionResult::CreateContractSizeLimit => Self::Halt(Halt::CreateContractSizeLimit),
             InstructionResult::CreateContractStartingWithEF => {
-                Self::Halt(Halt::CreateContractSizeLimit)
+                Self::Halt(Halt::CreateContractStartingWithEF)
             }
             InstructionResult::CreateInitcodeSizeLimit => Self::Halt(Halt::CreateInitcodeSizeLimit),
+            InstructionResult::AddressDenied => Self::Halt(Halt::AddressDenied),
+            InstructionResult::PrecompileDisabled => Self::Halt(Halt::PrecompileDisabled),
             InstructionResult::FatalExternalError => Self::FatalExternalError,
         }
     }
@@ -186,3 +291,24 @@ macro_rules! return_revert {
         InstructionResult::Revert | InstructionResult::CallTooDeep | InstructionResult::OutOfFund
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_exit() {
+        assert_eq!(InstructionResult::Continue.frame_exit(), None);
+        assert_eq!(InstructionResult::Stop.frame_exit(), Some(FrameExit::Stop));
+        let revert = InstructionResult::Revert.frame_exit().unwrap();
+        assert!(revert.is_revert());
+        assert_eq!(revert.error(0xfd).as_deref(), Some("execution reverted"));
+        let halt = InstructionResult::InvalidFEOpcode.frame_exit().unwrap();
+        assert_eq!(halt, FrameExit::Halt(Halt::InvalidFEOpcode));
+        assert_eq!(halt.error(0xfe).as_deref(), Some("invalid opcode: INVALID"));
+        assert_eq!(
+            InstructionResult::Return.frame_exit().unwrap().error(0xf3),
+            None
+        );
+    }
+}
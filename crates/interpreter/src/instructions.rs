@@ -28,6 +28,13 @@ pub fn return_not_found(interpreter: &mut Interpreter, _host: &mut dyn Host) {
 
 #[inline(always)]
 pub fn eval<H: Host, S: Spec>(opcode: u8, interp: &mut Interpreter, host: &mut H) {
+    // any opcode can be disabled. Opcodes activated by a hardfork are checked by their
+    // instructions, so they can be enabled before the activation too.
+    #[cfg(feature = "optional_opcode_overrides")]
+    if host.env().cfg.opcode_overrides.get(opcode) == Some(false) {
+        interp.instruction_result = InstructionResult::NotActivated;
+        return;
+    }
     match opcode {
         opcode::STOP => return_stop(interp, host),
         opcode::ADD => arithmetic::wrapped_add(interp, host),
@@ -144,6 +151,7 @@ pub fn eval<H: Host, S: Spec>(opcode: u8, interp: &mut Interpreter, host: &mut H
         opcode::REVERT => control::revert::<S>(interp, host),
         opcode::INVALID => return_invalid(interp, host),
         opcode::BASEFEE => host_env::basefee::<S>(interp, host),
+        opcode::BLOBBASEFEE => host_env::blob_basefee::<S>(interp, host),
         opcode::ORIGIN => host_env::origin(interp, host),
         opcode::CALLER => system::caller(interp, host),
         opcode::CALLVALUE => system::callvalue(interp, host),
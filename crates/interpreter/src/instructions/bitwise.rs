@@ -103,25 +103,25 @@ pub fn byte(interpreter: &mut Interpreter, _host: &mut dyn Host) {
     *op2 = ret;
 }
 
-pub fn shl<SPEC: Spec>(interpreter: &mut Interpreter, _host: &mut dyn Host) {
+pub fn shl<SPEC: Spec>(interpreter: &mut Interpreter, host: &mut dyn Host) {
     // EIP-145: Bitwise shifting instructions in EVM
-    check!(interpreter, SPEC::enabled(CONSTANTINOPLE));
+    check_opcode!(interpreter, host, SHL, SPEC::enabled(CONSTANTINOPLE));
     gas!(interpreter, gas::VERYLOW);
     pop_top!(interpreter, op1, op2);
     *op2 <<= as_usize_saturated!(op1);
 }
 
-pub fn shr<SPEC: Spec>(interpreter: &mut Interpreter, _host: &mut dyn Host) {
+pub fn shr<SPEC: Spec>(interpreter: &mut Interpreter, host: &mut dyn Host) {
     // EIP-145: Bitwise shifting instructions in EVM
-    check!(interpreter, SPEC::enabled(CONSTANTINOPLE));
+    check_opcode!(interpreter, host, SHR, SPEC::enabled(CONSTANTINOPLE));
     gas!(interpreter, gas::VERYLOW);
     pop_top!(interpreter, op1, op2);
     *op2 >>= as_usize_saturated!(op1);
 }
 
-pub fn sar<SPEC: Spec>(interpreter: &mut Interpreter, _host: &mut dyn Host) {
+pub fn sar<SPEC: Spec>(interpreter: &mut Interpreter, host: &mut dyn Host) {
     // EIP-145: Bitwise shifting instructions in EVM
-    check!(interpreter, SPEC::enabled(CONSTANTINOPLE));
+    check_opcode!(interpreter, host, SAR, SPEC::enabled(CONSTANTINOPLE));
     gas!(interpreter, gas::VERYLOW);
     pop_top!(interpreter, op1, op2);
 
@@ -135,7 +135,7 @@ pub fn sar<SPEC: Spec>(interpreter: &mut Interpreter, _host: &mut dyn Host) {
             Sign::Minus => two_compl(U256::from(1)),
         }
     } else {
-        let shift = usize::try_from(op1).unwrap();
+        let shift = as_usize_saturated!(op1);
 
         match value_sign {
             Sign::Plus | Sign::Zero => *op2 >> shift,
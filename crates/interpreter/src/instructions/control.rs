@@ -56,10 +56,10 @@ pub fn ret(interpreter: &mut Interpreter, _host: &mut dyn Host) {
     interpreter.instruction_result = InstructionResult::Return;
 }
 
-pub fn revert<SPEC: Spec>(interpreter: &mut Interpreter, _host: &mut dyn Host) {
+pub fn revert<SPEC: Spec>(interpreter: &mut Interpreter, host: &mut dyn Host) {
     // zero gas cost gas!(interp,gas::ZERO);
     // EIP-140: REVERT instruction
-    check!(interpreter, SPEC::enabled(BYZANTIUM));
+    check_opcode!(interpreter, host, REVERT, SPEC::enabled(BYZANTIUM));
     pop!(interpreter, start, len);
     let len = as_usize_or_fail!(interpreter, len, InstructionResult::InvalidOperandOOG);
     if len == 0 {
@@ -12,12 +12,10 @@ use core::cmp::min;
 
 pub fn balance<SPEC: Spec>(interpreter: &mut Interpreter, host: &mut dyn Host) {
     pop_address!(interpreter, address);
-    let ret = host.balance(address);
-    if ret.is_none() {
+    let Some((balance, is_cold)) = host.balance(address) else {
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
-    }
-    let (balance, is_cold) = ret.unwrap();
+    };
     gas!(
         interpreter,
         if SPEC::enabled(ISTANBUL) {
@@ -34,25 +32,21 @@ pub fn balance<SPEC: Spec>(interpreter: &mut Interpreter, host: &mut dyn Host) {
 
 pub fn selfbalance<SPEC: Spec>(interpreter: &mut Interpreter, host: &mut dyn Host) {
     // EIP-1884: Repricing for trie-size-dependent opcodes
-    check!(interpreter, SPEC::enabled(ISTANBUL));
+    check_opcode!(interpreter, host, SELFBALANCE, SPEC::enabled(ISTANBUL));
     gas!(interpreter, gas::LOW);
-    let ret = host.balance(interpreter.contract.address);
-    if ret.is_none() {
+    let Some((balance, _)) = host.balance(interpreter.contract.address) else {
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
-    }
-    let (balance, _) = ret.unwrap();
+    };
     push!(interpreter, balance);
 }
 
 pub fn extcodesize<SPEC: Spec>(interpreter: &mut Interpreter, host: &mut dyn Host) {
     pop_address!(interpreter, address);
-    let ret = host.code(address);
-    if ret.is_none() {
+    let Some((code, is_cold)) = host.code(address) else {
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
-    }
-    let (code, is_cold) = ret.unwrap();
+    };
     if SPEC::enabled(BERLIN) {
         gas!(
             interpreter,
@@ -72,14 +66,18 @@ pub fn extcodesize<SPEC: Spec>(interpreter: &mut Interpreter, host: &mut dyn Hos
 }
 
 pub fn extcodehash<SPEC: Spec>(interpreter: &mut Interpreter, host: &mut dyn Host) {
-    check!(interpreter, SPEC::enabled(CONSTANTINOPLE)); // EIP-1052: EXTCODEHASH opcode
+    // EIP-1052: EXTCODEHASH opcode
+    check_opcode!(
+        interpreter,
+        host,
+        EXTCODEHASH,
+        SPEC::enabled(CONSTANTINOPLE)
+    );
     pop_address!(interpreter, address);
-    let ret = host.code_hash(address);
-    if ret.is_none() {
+    let Some((code_hash, is_cold)) = host.code_hash(address) else {
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
-    }
-    let (code_hash, is_cold) = ret.unwrap();
+    };
     if SPEC::enabled(BERLIN) {
         gas!(
             interpreter,
@@ -101,12 +99,10 @@ pub fn extcodecopy<SPEC: Spec>(interpreter: &mut Interpreter, host: &mut dyn Hos
     pop_address!(interpreter, address);
     pop!(interpreter, memory_offset, code_offset, len_u256);
 
-    let ret = host.code(address);
-    if ret.is_none() {
+    let Some((code, is_cold)) = host.code(address) else {
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
-    }
-    let (code, is_cold) = ret.unwrap();
+    };
 
     let len = as_usize_or_fail!(interpreter, len_u256, InstructionResult::InvalidOperandOOG);
     gas_or_fail!(
@@ -121,13 +117,16 @@ pub fn extcodecopy<SPEC: Spec>(interpreter: &mut Interpreter, host: &mut dyn Hos
         memory_offset,
         InstructionResult::InvalidOperandOOG
     );
-    let code_offset = min(as_usize_saturated!(code_offset), code.len());
+    let code_offset = as_usize_saturated!(code_offset);
     memory_resize!(interpreter, memory_offset, len);
 
     // Safety: set_data is unsafe function and memory_resize ensures us that it is safe to call it
-    interpreter
-        .memory
-        .set_data(memory_offset, code_offset, len, code.bytes());
+    interpreter.memory.set_data(
+        memory_offset,
+        code_offset,
+        len,
+        code.original_bytecode_slice(),
+    );
 }
 
 pub fn blockhash(interpreter: &mut Interpreter, host: &mut dyn Host) {
@@ -138,12 +137,11 @@ pub fn blockhash(interpreter: &mut Interpreter, host: &mut dyn Host) {
         let diff = as_usize_saturated!(diff);
         // blockhash should push zero if number is same as current block number.
         if diff <= 256 && diff != 0 {
-            let ret = host.block_hash(*number);
-            if ret.is_none() {
+            let Some(hash) = host.block_hash(*number) else {
                 interpreter.instruction_result = InstructionResult::FatalExternalError;
                 return;
-            }
-            *number = U256::from_be_bytes(*ret.unwrap());
+            };
+            *number = U256::from_be_bytes(*hash);
             return;
         }
     }
@@ -153,12 +151,10 @@ pub fn blockhash(interpreter: &mut Interpreter, host: &mut dyn Host) {
 pub fn sload<SPEC: Spec>(interpreter: &mut Interpreter, host: &mut dyn Host) {
     pop!(interpreter, index);
 
-    let ret = host.sload(interpreter.contract.address, index);
-    if ret.is_none() {
+    let Some((value, is_cold)) = host.sload(interpreter.contract.address, index) else {
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
-    }
-    let (value, is_cold) = ret.unwrap();
+    };
     gas!(interpreter, gas::sload_cost::<SPEC>(is_cold));
     push!(interpreter, value);
 }
@@ -167,23 +163,27 @@ pub fn sstore<SPEC: Spec>(interpreter: &mut Interpreter, host: &mut dyn Host) {
     check_staticcall!(interpreter);
 
     pop!(interpreter, index, value);
-    let ret = host.sstore(interpreter.contract.address, index, value);
-    if ret.is_none() {
+    let Some((original, old, new, is_cold)) =
+        host.sstore(interpreter.contract.address, index, value)
+    else {
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
-    }
-    let (original, old, new, is_cold) = ret.unwrap();
+    };
     gas_or_fail!(interpreter, {
         let remaining_gas = interpreter.gas.remaining();
         gas::sstore_cost::<SPEC>(original, old, new, remaining_gas, is_cold)
     });
-    refund!(interpreter, gas::sstore_refund::<SPEC>(original, old, new));
+    let eip3529 = host.env().cfg.is_eip3529_enabled(SPEC::enabled(LONDON));
+    refund!(
+        interpreter,
+        gas::sstore_refund_with_eip3529::<SPEC>(original, old, new, eip3529)
+    );
 }
 
 /// Store value to transient storage
 pub fn tstore<H: Host, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
     // EIP-1153: Transient storage opcodes
-    check!(interpreter, SPEC::enabled(CANCUN));
+    check_opcode!(interpreter, host, TSTORE, SPEC::enabled(CANCUN));
     check_staticcall!(interpreter);
     gas!(interpreter, gas::WARM_STORAGE_READ_COST);
 
@@ -195,7 +195,7 @@ pub fn tstore<H: Host, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H)
 /// Load value from transient storage
 pub fn tload<H: Host, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
     // EIP-1153: Transient storage opcodes
-    check!(interpreter, SPEC::enabled(CANCUN));
+    check_opcode!(interpreter, host, TLOAD, SPEC::enabled(CANCUN));
     gas!(interpreter, gas::WARM_STORAGE_READ_COST);
 
     pop_top!(interpreter, index);
@@ -237,15 +237,14 @@ pub fn selfdestruct<SPEC: Spec>(interpreter: &mut Interpreter, host: &mut dyn Ho
     check_staticcall!(interpreter);
     pop_address!(interpreter, target);
 
-    let res = host.selfdestruct(interpreter.contract.address, target);
-    if res.is_none() {
+    let Some(res) = host.selfdestruct(interpreter.contract.address, target) else {
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
-    }
-    let res = res.unwrap();
+    };
 
     // EIP-3529: Reduction in refunds
-    if !SPEC::enabled(LONDON) && !res.previously_destroyed {
+    let eip3529 = host.env().cfg.is_eip3529_enabled(SPEC::enabled(LONDON));
+    if !eip3529 && !res.previously_destroyed {
         refund!(interpreter, gas::SELFDESTRUCT)
     }
     gas!(interpreter, gas::selfdestruct_cost::<SPEC>(res));
@@ -255,12 +254,13 @@ pub fn selfdestruct<SPEC: Spec>(interpreter: &mut Interpreter, host: &mut dyn Ho
 
 pub fn prepare_create_inputs<const IS_CREATE2: bool, SPEC: Spec>(
     interpreter: &mut Interpreter,
+    host: &mut dyn Host,
     create_inputs: &mut Option<Box<CreateInputs>>,
 ) {
     check_staticcall!(interpreter);
     if IS_CREATE2 {
         // EIP-1014: Skinny CREATE2
-        check!(interpreter, SPEC::enabled(PETERSBURG));
+        check_opcode!(interpreter, host, CREATE2, SPEC::enabled(PETERSBURG));
     }
 
     interpreter.return_data_buffer = Bytes::new();
@@ -320,7 +320,7 @@ pub fn create<const IS_CREATE2: bool, SPEC: Spec>(
     host: &mut dyn Host,
 ) {
     let mut create_input: Option<Box<CreateInputs>> = None;
-    prepare_create_inputs::<IS_CREATE2, SPEC>(interpreter, &mut create_input);
+    prepare_create_inputs::<IS_CREATE2, SPEC>(interpreter, host, &mut create_input);
 
     let Some(mut create_input) = create_input else {
         return;
@@ -473,12 +473,10 @@ fn prepare_call_inputs<SPEC: Spec>(
     };
 
     // load account and calculate gas cost.
-    let res = host.load_account(to);
-    if res.is_none() {
+    let Some((is_cold, exist)) = host.load_account(to) else {
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
-    }
-    let (is_cold, exist) = res.unwrap();
+    };
     let is_new = !exist;
 
     gas!(
@@ -525,8 +523,14 @@ pub fn call_inner<SPEC: Spec>(
     host: &mut dyn Host,
 ) {
     match scheme {
-        CallScheme::DelegateCall => check!(interpreter, SPEC::enabled(HOMESTEAD)), // EIP-7: DELEGATECALL
-        CallScheme::StaticCall => check!(interpreter, SPEC::enabled(BYZANTIUM)), // EIP-214: New opcode STATICCALL
+        // EIP-7: DELEGATECALL
+        CallScheme::DelegateCall => {
+            check_opcode!(interpreter, host, DELEGATECALL, SPEC::enabled(HOMESTEAD));
+        }
+        // EIP-214: New opcode STATICCALL
+        CallScheme::StaticCall => {
+            check_opcode!(interpreter, host, STATICCALL, SPEC::enabled(BYZANTIUM));
+        }
         _ => (),
     }
     interpreter.return_data_buffer = Bytes::new();
@@ -561,18 +565,26 @@ pub fn call_inner<SPEC: Spec>(
                 interpreter.gas.erase_cost(gas.remaining());
                 interpreter.gas.record_refund(gas.refunded());
             }
-            interpreter
-                .memory
-                .set(out_offset, &interpreter.return_data_buffer[..target_len]);
+            interpreter.memory.set(
+                out_offset,
+                interpreter
+                    .return_data_buffer
+                    .get(..target_len)
+                    .unwrap_or_default(),
+            );
             push!(interpreter, U256::from(1));
         }
         return_revert!() => {
             if crate::USE_GAS {
                 interpreter.gas.erase_cost(gas.remaining());
             }
-            interpreter
-                .memory
-                .set(out_offset, &interpreter.return_data_buffer[..target_len]);
+            interpreter.memory.set(
+                out_offset,
+                interpreter
+                    .return_data_buffer
+                    .get(..target_len)
+                    .unwrap_or_default(),
+            );
             push!(interpreter, U256::ZERO);
         }
         InstructionResult::FatalExternalError => {
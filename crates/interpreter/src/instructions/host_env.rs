@@ -1,10 +1,11 @@
 use crate::{
-    gas, interpreter::Interpreter, primitives::Spec, primitives::SpecId::*, Host, InstructionResult,
+    gas, interpreter::Interpreter, primitives::Spec, primitives::SpecId::*, primitives::U256, Host,
+    InstructionResult,
 };
 
 pub fn chainid<SPEC: Spec>(interpreter: &mut Interpreter, host: &mut dyn Host) {
     // EIP-1344: ChainID opcode
-    check!(interpreter, SPEC::enabled(ISTANBUL));
+    check_opcode!(interpreter, host, CHAINID, SPEC::enabled(ISTANBUL));
     gas!(interpreter, gas::BASE);
     push!(interpreter, host.env().cfg.chain_id);
 }
@@ -27,7 +28,7 @@ pub fn number(interpreter: &mut Interpreter, host: &mut dyn Host) {
 pub fn difficulty<H: Host, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
     gas!(interpreter, gas::BASE);
     if SPEC::enabled(MERGE) {
-        push_b256!(interpreter, host.env().block.prevrandao.unwrap());
+        push_b256!(interpreter, host.env().block.prevrandao.unwrap_or_default());
     } else {
         push!(interpreter, host.env().block.difficulty);
     }
@@ -46,11 +47,61 @@ pub fn gasprice(interpreter: &mut Interpreter, host: &mut dyn Host) {
 pub fn basefee<SPEC: Spec>(interpreter: &mut Interpreter, host: &mut dyn Host) {
     gas!(interpreter, gas::BASE);
     // EIP-3198: BASEFEE opcode
-    check!(interpreter, SPEC::enabled(LONDON));
+    check_opcode!(interpreter, host, BASEFEE, SPEC::enabled(LONDON));
     push!(interpreter, host.env().block.basefee);
 }
 
+/// EIP-7516: BLOBBASEFEE opcode
+pub fn blob_basefee<SPEC: Spec>(interpreter: &mut Interpreter, host: &mut dyn Host) {
+    check_opcode!(interpreter, host, BLOBBASEFEE, SPEC::enabled(CANCUN));
+    gas!(interpreter, gas::BASE);
+    push!(
+        interpreter,
+        U256::from(host.env().block.get_blob_gasprice().unwrap_or_default())
+    );
+}
+
 pub fn origin(interpreter: &mut Interpreter, host: &mut dyn Host) {
     gas!(interpreter, gas::BASE);
     push_b256!(interpreter, host.env().tx.caller.into());
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::primitives::{Bytecode, Bytes, CancunSpec, Env, ShanghaiSpec, B160, U256};
+    use crate::{opcode, Contract, DummyHost, InstructionResult, Interpreter};
+
+    fn blob_basefee_interpreter() -> Interpreter {
+        let contract = Contract::new(
+            Bytes::new(),
+            Bytecode::new_raw(Bytes::from_static(&[opcode::BLOBBASEFEE, opcode::STOP])),
+            B160::zero(),
+            B160::zero(),
+            U256::ZERO,
+        );
+        Interpreter::new(Box::new(contract), 100, false)
+    }
+
+    #[test]
+    fn blob_basefee_cancun() {
+        let mut env = Env::default();
+        env.block.set_blob_excess_gas_and_price(10 * 1024 * 1024);
+        let mut host = DummyHost::new(env);
+        let mut interpreter = blob_basefee_interpreter();
+
+        let result = interpreter.run::<DummyHost, CancunSpec>(&mut host);
+        assert_eq!(result, InstructionResult::Stop);
+        assert_eq!(interpreter.stack.peek(0), Ok(U256::from(23)));
+        assert_eq!(interpreter.gas.spend(), crate::gas::BASE);
+    }
+
+    #[test]
+    fn blob_basefee_not_activated_before_cancun() {
+        let mut host = DummyHost::new(Env::default());
+        let mut interpreter = blob_basefee_interpreter();
+
+        let result = interpreter.run::<DummyHost, ShanghaiSpec>(&mut host);
+        assert_eq!(result, InstructionResult::NotActivated);
+        assert!(interpreter.stack.is_empty());
+    }
+}
@@ -18,6 +18,24 @@ macro_rules! check {
     };
 }
 
+/// Same as `check!` but lets the opcode overrides set in [`crate::primitives::CfgEnv`]
+/// take precedence over the spec activation of the opcode.
+macro_rules! check_opcode {
+    ($interp:expr, $host:expr, $opcode:ident, $expresion:expr) => {
+        #[cfg(feature = "optional_opcode_overrides")]
+        let enabled = $host
+            .env()
+            .cfg
+            .is_opcode_enabled(crate::opcode::$opcode, $expresion);
+        #[cfg(not(feature = "optional_opcode_overrides"))]
+        let enabled = {
+            let _ = &$host;
+            $expresion
+        };
+        check!($interp, enabled);
+    };
+}
+
 macro_rules! gas {
     ($interp:expr, $gas:expr) => {
         if crate::USE_GAS {
@@ -53,29 +71,8 @@ macro_rules! gas_or_fail {
 
 macro_rules! memory_resize {
     ($interp:expr, $offset:expr, $len:expr) => {{
-        let len: usize = $len;
-        let offset: usize = $offset;
-        if let Some(new_size) =
-            crate::interpreter::memory::next_multiple_of_32(offset.saturating_add(len))
-        {
-            #[cfg(feature = "memory_limit")]
-            if new_size > ($interp.memory_limit as usize) {
-                $interp.instruction_result = InstructionResult::MemoryLimitOOG;
-                return;
-            }
-
-            if new_size > $interp.memory.len() {
-                if crate::USE_GAS {
-                    let num_bytes = new_size / 32;
-                    if !$interp.gas.record_memory(crate::gas::memory_gas(num_bytes)) {
-                        $interp.instruction_result = InstructionResult::MemoryLimitOOG;
-                        return;
-                    }
-                }
-                $interp.memory.resize(new_size);
-            }
-        } else {
-            $interp.instruction_result = InstructionResult::MemoryOOG;
+        if let Err(result) = $interp.resize_memory($offset, $len) {
+            $interp.instruction_result = result;
             return;
         }
     }};
@@ -88,29 +85,24 @@ macro_rules! pop_address {
             return;
         }
         // Safety: Length is checked above.
-        let $x1: B160 = B160(
-            unsafe { $interp.stack.pop_unsafe() }.to_be_bytes::<{ U256::BYTES }>()[12..]
-                .try_into()
-                .unwrap(),
-        );
+        let $x1: B160 = $crate::primitives::B256(
+            unsafe { $interp.stack.pop_unsafe() }.to_be_bytes::<{ U256::BYTES }>(),
+        )
+        .into();
     };
     ( $interp:expr, $x1:ident, $x2:ident) => {
         if $interp.stack.len() < 2 {
             $interp.instruction_result = InstructionResult::StackUnderflow;
             return;
         }
-        let mut temp = H256::zero();
-
-        let $x1: B160 = B160(
-            unsafe { $interp.stack.pop_unsafe() }.to_be_bytes::<{ U256::BYTES }>()[12..]
-                .try_into()
-                .unwrap(),
-        );
-        let $x2: B160 = B160(
-            unsafe { $interp.stack.pop_unsafe() }.to_be_bytes::<{ U256::BYTES }>()[12..]
-                .try_into()
-                .unwrap(),
-        );
+        let $x1: B160 = $crate::primitives::B256(
+            unsafe { $interp.stack.pop_unsafe() }.to_be_bytes::<{ U256::BYTES }>(),
+        )
+        .into();
+        let $x2: B160 = $crate::primitives::B256(
+            unsafe { $interp.stack.pop_unsafe() }.to_be_bytes::<{ U256::BYTES }>(),
+        )
+        .into();
     };
 }
 
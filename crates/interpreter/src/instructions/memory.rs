@@ -14,12 +14,7 @@ pub fn mload(interpreter: &mut Interpreter, _host: &mut dyn Host) {
     pop!(interpreter, index);
     let index = as_usize_or_fail!(interpreter, index, InstructionResult::InvalidOperandOOG);
     memory_resize!(interpreter, index, 32);
-    push!(
-        interpreter,
-        U256::from_be_bytes::<{ U256::BYTES }>(
-            interpreter.memory.get_slice(index, 32).try_into().unwrap()
-        )
-    );
+    push!(interpreter, interpreter.memory.get_u256(index));
 }
 
 pub fn mstore(interpreter: &mut Interpreter, _host: &mut dyn Host) {
@@ -35,7 +30,7 @@ pub fn mstore8(interpreter: &mut Interpreter, _host: &mut dyn Host) {
     pop!(interpreter, index, value);
     let index = as_usize_or_fail!(interpreter, index, InstructionResult::InvalidOperandOOG);
     memory_resize!(interpreter, index, 1);
-    let value = value.as_le_bytes()[0];
+    let value = value.as_limbs()[0] as u8;
     // Safety: we resized our memory two lines above.
     unsafe { interpreter.memory.set_byte(index, value) }
 }
@@ -46,10 +41,10 @@ pub fn msize(interpreter: &mut Interpreter, _host: &mut dyn Host) {
 }
 
 // From EIP-5656 MCOPY
-pub fn mcopy<SPEC: Spec>(interpreter: &mut Interpreter, _host: &mut dyn Host) {
+pub fn mcopy<SPEC: Spec>(interpreter: &mut Interpreter, host: &mut dyn Host) {
     // Opcode enabled in Cancun.
     // EIP-5656: MCOPY - Memory copying instruction
-    check!(interpreter, SPEC::enabled(CANCUN));
+    check_opcode!(interpreter, host, MCOPY, SPEC::enabled(CANCUN));
     // get src and dest and length from stack
     pop!(interpreter, dest, src, len);
 
@@ -1,5 +1,5 @@
 use crate::gas;
-use crate::primitives::SpecId;
+use crate::primitives::{GasCategory, SpecId};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub struct OpCode(u8);
@@ -124,6 +124,7 @@ pub const INVALID: u8 = 0xfe;
 pub const ADDRESS: u8 = 0x30;
 pub const BALANCE: u8 = 0x31;
 pub const BASEFEE: u8 = 0x48;
+pub const BLOBBASEFEE: u8 = 0x4a;
 pub const ORIGIN: u8 = 0x32;
 pub const CALLER: u8 = 0x33;
 pub const CALLVALUE: u8 = 0x34;
@@ -161,9 +162,15 @@ pub const CHAINID: u8 = 0x46;
 
 impl OpCode {
     pub fn try_from_u8(opcode: u8) -> Option<OpCode> {
-        OPCODE_JUMPMAP[opcode as usize].map(|_| OpCode(opcode))
+        OPCODE_JUMPMAP
+            .get(opcode as usize)
+            .copied()
+            .flatten()
+            .map(|_| OpCode(opcode))
     }
 
+    // `u8` always indexes into the 256 entries of the jump map.
+    #[allow(clippy::indexing_slicing)]
     pub const fn as_str(&self) -> &'static str {
         if let Some(str) = OPCODE_JUMPMAP[self.0 as usize] {
             str
@@ -178,9 +185,23 @@ impl OpCode {
     }
 }
 
+/// Returns the [GasCategory] that gas used by the opcode is recorded in.
+pub const fn gas_category(opcode: u8) -> GasCategory {
+    match opcode {
+        MLOAD | MSTORE | MSTORE8 | MSIZE | MCOPY | CALLDATACOPY | CODECOPY | RETURNDATACOPY => {
+            GasCategory::Memory
+        }
+        SLOAD => GasCategory::StorageRead,
+        SSTORE => GasCategory::StorageWrite,
+        CALL | CALLCODE | DELEGATECALL | STATICCALL => GasCategory::Call,
+        CREATE | CREATE2 => GasCategory::Create,
+        _ => GasCategory::Compute,
+    }
+}
+
 impl core::fmt::Display for OpCode {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        if let Some(val) = OPCODE_JUMPMAP[self.0 as usize] {
+        if let Some(Some(val)) = OPCODE_JUMPMAP.get(self.0 as usize) {
             f.write_str(val)
         } else {
             write!(f, "UNKNOWN(0x{:02x})", self.0)
@@ -263,7 +284,7 @@ pub const OPCODE_JUMPMAP: [Option<&'static str>; 256] = [
     /* 0x47 */ Some("SELFBALANCE"),
     /* 0x48 */ Some("BASEFEE"),
     /* 0x49 */ None,
-    /* 0x4a */ None,
+    /* 0x4a */ Some("BLOBBASEFEE"),
     /* 0x4b */ None,
     /* 0x4c */ None,
     /* 0x4d */ None,
@@ -643,7 +664,12 @@ macro_rules! gas_opcodee {
                 0
             }),
             /* 0x49 */ OpInfo::none(),
-            /* 0x4a */ OpInfo::none(),
+            /* 0x4a  BLOBBASEFEE */
+            OpInfo::gas(if SpecId::enabled($spec_id, SpecId::CANCUN) {
+                gas::BASE
+            } else {
+                0
+            }),
             /* 0x4b */ OpInfo::none(),
             /* 0x4c */ OpInfo::none(),
             /* 0x4d */ OpInfo::none(),
@@ -13,9 +13,9 @@ pub fn pop(interpreter: &mut Interpreter, _host: &mut dyn Host) {
 
 /// EIP-3855: PUSH0 instruction  
 /// Introduce a new instruction which pushes the constant value 0 onto the stack
-pub fn push0<SPEC: Spec>(interpreter: &mut Interpreter, _host: &mut dyn Host) {
+pub fn push0<SPEC: Spec>(interpreter: &mut Interpreter, host: &mut dyn Host) {
     // EIP-3855: PUSH0 instruction
-    check!(interpreter, SPEC::enabled(SHANGHAI));
+    check_opcode!(interpreter, host, PUSH0, SPEC::enabled(SHANGHAI));
     gas!(interpreter, gas::BASE);
     if let Err(result) = interpreter.stack.push(U256::ZERO) {
         interpreter.instruction_result = result;
@@ -50,3 +50,65 @@ pub fn swap<const N: usize>(interpreter: &mut Interpreter, _host: &mut dyn Host)
         interpreter.instruction_result = ret;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::primitives::{Bytecode, Bytes, Env, MergeSpec, ShanghaiSpec, B160, U256};
+    use crate::{opcode, Contract, DummyHost, InstructionResult, Interpreter};
+
+    fn interpreter(code: &'static [u8]) -> Interpreter {
+        let contract = Contract::new(
+            Bytes::new(),
+            Bytecode::new_raw(Bytes::from_static(code)),
+            B160::zero(),
+            B160::zero(),
+            U256::ZERO,
+        );
+        Interpreter::new(Box::new(contract), 100, false)
+    }
+
+    #[test]
+    fn push0() {
+        let code = &[opcode::PUSH0, opcode::STOP];
+        let mut host = DummyHost::new(Env::default());
+
+        let mut interp = interpreter(code);
+        let result = interp.run::<DummyHost, ShanghaiSpec>(&mut host);
+        assert_eq!(result, InstructionResult::Stop);
+        assert_eq!(interp.stack.peek(0), Ok(U256::ZERO));
+
+        let mut interp = interpreter(code);
+        let result = interp.run::<DummyHost, MergeSpec>(&mut host);
+        assert_eq!(result, InstructionResult::NotActivated);
+        assert!(interp.stack.is_empty());
+    }
+
+    #[cfg(feature = "optional_opcode_overrides")]
+    #[test]
+    fn push0_overrides() {
+        let code = &[opcode::PUSH0, opcode::STOP];
+        let mut host = DummyHost::new(Env::default());
+
+        // enabled before Shanghai.
+        host.env.cfg.opcode_overrides.enable(opcode::PUSH0);
+        let mut interp = interpreter(code);
+        let result = interp.run::<DummyHost, MergeSpec>(&mut host);
+        assert_eq!(result, InstructionResult::Stop);
+        assert_eq!(interp.stack.peek(0), Ok(U256::ZERO));
+
+        // disabled after Shanghai.
+        host.env.cfg.opcode_overrides.disable(opcode::PUSH0);
+        let mut interp = interpreter(code);
+        let result = interp.run::<DummyHost, ShanghaiSpec>(&mut host);
+        assert_eq!(result, InstructionResult::NotActivated);
+        assert!(interp.stack.is_empty());
+
+        // opcodes that are not activated by a hardfork can be disabled too.
+        host.env.cfg.opcode_overrides.reset(opcode::PUSH0);
+        host.env.cfg.opcode_overrides.disable(opcode::ADDRESS);
+        let mut interp = interpreter(&[opcode::PUSH0, opcode::ADDRESS, opcode::STOP]);
+        let result = interp.run::<DummyHost, ShanghaiSpec>(&mut host);
+        assert_eq!(result, InstructionResult::NotActivated);
+        assert_eq!(interp.stack.len(), 1);
+    }
+}
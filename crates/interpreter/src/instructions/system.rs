@@ -4,7 +4,6 @@ use crate::{
     primitives::{keccak256, Spec, SpecId::*, B256, KECCAK_EMPTY, U256},
     Host, InstructionResult,
 };
-use core::cmp::min;
 
 pub fn calculate_keccak256(interpreter: &mut Interpreter, _host: &mut dyn Host) {
     pop!(interpreter, from, len);
@@ -65,14 +64,12 @@ pub fn calldataload(interpreter: &mut Interpreter, _host: &mut dyn Host) {
     pop!(interpreter, index);
     let index = as_usize_saturated!(index);
 
-    let load = if index < interpreter.contract.input.len() {
-        let have_bytes = min(interpreter.contract.input.len() - index, 32);
-        let mut bytes = [0u8; 32];
-        bytes[..have_bytes].copy_from_slice(&interpreter.contract.input[index..index + have_bytes]);
-        B256(bytes)
-    } else {
-        B256::zero()
-    };
+    let mut load = B256::zero();
+    if let Some(input) = interpreter.contract.input.get(index..) {
+        for (byte, input) in load.0.iter_mut().zip(input) {
+            *byte = *input;
+        }
+    }
 
     push_b256!(interpreter, load);
 }
@@ -108,19 +105,19 @@ pub fn calldatacopy(interpreter: &mut Interpreter, _host: &mut dyn Host) {
         .set_data(memory_offset, data_offset, len, &interpreter.contract.input);
 }
 
-pub fn returndatasize<SPEC: Spec>(interpreter: &mut Interpreter, _host: &mut dyn Host) {
+pub fn returndatasize<SPEC: Spec>(interpreter: &mut Interpreter, host: &mut dyn Host) {
     gas!(interpreter, gas::BASE);
     // EIP-211: New opcodes: RETURNDATASIZE and RETURNDATACOPY
-    check!(interpreter, SPEC::enabled(BYZANTIUM));
+    check_opcode!(interpreter, host, RETURNDATASIZE, SPEC::enabled(BYZANTIUM));
     push!(
         interpreter,
         U256::from(interpreter.return_data_buffer.len())
     );
 }
 
-pub fn returndatacopy<SPEC: Spec>(interpreter: &mut Interpreter, _host: &mut dyn Host) {
+pub fn returndatacopy<SPEC: Spec>(interpreter: &mut Interpreter, host: &mut dyn Host) {
     // EIP-211: New opcodes: RETURNDATASIZE and RETURNDATACOPY
-    check!(interpreter, SPEC::enabled(BYZANTIUM));
+    check_opcode!(interpreter, host, RETURNDATACOPY, SPEC::enabled(BYZANTIUM));
     pop!(interpreter, memory_offset, offset, len);
     let len = as_usize_or_fail!(interpreter, len, InstructionResult::InvalidOperandOOG);
     gas_or_fail!(interpreter, gas::verylowcopy_cost(len as u64));
@@ -139,7 +136,10 @@ pub fn returndatacopy<SPEC: Spec>(interpreter: &mut Interpreter, _host: &mut dyn
         memory_resize!(interpreter, memory_offset, len);
         interpreter.memory.set(
             memory_offset,
-            &interpreter.return_data_buffer[data_offset..data_end],
+            interpreter
+                .return_data_buffer
+                .get(data_offset..data_end)
+                .unwrap_or_default(),
         );
     }
 }
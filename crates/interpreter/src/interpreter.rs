@@ -5,10 +5,10 @@ mod stack;
 
 pub use analysis::BytecodeLocked;
 pub use contract::Contract;
-pub use memory::Memory;
+pub use memory::{Memory, MemoryPool};
 pub use stack::Stack;
 
-use crate::primitives::{Bytes, Spec};
+use crate::primitives::{Bytes, Spec, U256};
 use crate::{
     alloc::boxed::Box,
     instructions::{eval, InstructionResult},
@@ -57,48 +57,43 @@ impl Interpreter {
 
     /// Create new interpreter
     pub fn new(contract: Box<Contract>, gas_limit: u64, is_static: bool) -> Self {
-        #[cfg(not(feature = "memory_limit"))]
-        {
-            Self {
-                instruction_pointer: contract.bytecode.as_ptr(),
-                return_range: Range::default(),
-                memory: Memory::new(),
-                stack: Stack::new(),
-                return_data_buffer: Bytes::new(),
-                contract,
-                instruction_result: InstructionResult::Continue,
-                is_static,
-                gas: Gas::new(gas_limit),
-            }
-        }
-
-        #[cfg(feature = "memory_limit")]
-        {
-            Self::new_with_memory_limit(contract, gas_limit, is_static, u64::MAX)
-        }
+        Self::new_with_memory(contract, gas_limit, is_static, Memory::new())
     }
 
-    #[cfg(feature = "memory_limit")]
-    pub fn new_with_memory_limit(
+    /// Create new interpreter that uses given memory, for example one taken from [`MemoryPool`].
+    pub fn new_with_memory(
         contract: Box<Contract>,
         gas_limit: u64,
         is_static: bool,
-        memory_limit: u64,
+        memory: Memory,
     ) -> Self {
         Self {
             instruction_pointer: contract.bytecode.as_ptr(),
             return_range: Range::default(),
-            memory: Memory::new(),
+            memory,
             stack: Stack::new(),
             return_data_buffer: Bytes::new(),
             contract,
             instruction_result: InstructionResult::Continue,
             is_static,
             gas: Gas::new(gas_limit),
-            memory_limit,
+            #[cfg(feature = "memory_limit")]
+            memory_limit: u64::MAX,
         }
     }
 
+    #[cfg(feature = "memory_limit")]
+    pub fn new_with_memory_limit(
+        contract: Box<Contract>,
+        gas_limit: u64,
+        is_static: bool,
+        memory_limit: u64,
+    ) -> Self {
+        let mut interpreter = Self::new(contract, gas_limit, is_static);
+        interpreter.memory_limit = memory_limit;
+        interpreter
+    }
+
     pub fn contract(&self) -> &Contract {
         &self.contract
     }
@@ -117,6 +112,69 @@ impl Interpreter {
         &self.stack
     }
 
+    /// Resizes memory to fit `len` bytes at `offset` and charges memory expansion gas,
+    /// the same way as memory accessing instructions do.
+    ///
+    /// Zero `len` still expands memory to `offset`, callers that access nothing need to skip it.
+    #[inline(always)]
+    pub fn resize_memory(&mut self, offset: usize, len: usize) -> Result<(), InstructionResult> {
+        let Some(new_size) = memory::next_multiple_of_32(offset.saturating_add(len)) else {
+            return Err(InstructionResult::MemoryOOG);
+        };
+
+        #[cfg(feature = "memory_limit")]
+        if new_size > (self.memory_limit as usize) {
+            return Err(InstructionResult::MemoryLimitOOG);
+        }
+
+        if new_size > self.memory.len() {
+            if crate::USE_GAS {
+                let num_bytes = new_size / 32;
+                if !self.gas.record_memory(crate::gas::memory_gas(num_bytes)) {
+                    return Err(InstructionResult::MemoryLimitOOG);
+                }
+            }
+            self.memory.resize(new_size);
+        }
+        Ok(())
+    }
+
+    /// Reads 32 bytes at `offset` as big-endian word, resizing memory if needed.
+    pub fn memory_read_u256(&mut self, offset: usize) -> Result<U256, InstructionResult> {
+        self.resize_memory(offset, 32)?;
+        Ok(self.memory.get_u256(offset))
+    }
+
+    /// Writes `value` as big-endian word at `offset`, resizing memory if needed.
+    pub fn memory_write_u256(
+        &mut self,
+        offset: usize,
+        value: U256,
+    ) -> Result<(), InstructionResult> {
+        self.resize_memory(offset, 32)?;
+        self.memory.set_u256(offset, value);
+        Ok(())
+    }
+
+    /// Reads `len` bytes at `offset`, resizing memory if needed.
+    pub fn memory_read(&mut self, offset: usize, len: usize) -> Result<&[u8], InstructionResult> {
+        if len == 0 {
+            return Ok(&[]);
+        }
+        self.resize_memory(offset, len)?;
+        Ok(self.memory.get_slice(offset, len))
+    }
+
+    /// Writes `data` at `offset`, resizing memory if needed.
+    pub fn memory_write(&mut self, offset: usize, data: &[u8]) -> Result<(), InstructionResult> {
+        if data.is_empty() {
+            return Ok(());
+        }
+        self.resize_memory(offset, data.len())?;
+        self.memory.set(offset, data);
+        Ok(())
+    }
+
     /// Return a reference of the program counter.
     pub fn program_counter(&self) -> usize {
         // Safety: this is just subtraction of pointers, it is safe to do.
@@ -135,7 +193,17 @@ impl Interpreter {
         // byte instruction is STOP so we are safe to just increment program_counter bcs on last instruction
         // it will do noop and just stop execution of this contract
         self.instruction_pointer = unsafe { self.instruction_pointer.offset(1) };
+        #[cfg(feature = "gas_by_category")]
+        let (spent, nested) = (self.gas.spend(), host.gas_by_category().total());
         eval::<H, SPEC>(opcode, self, host);
+        #[cfg(feature = "gas_by_category")]
+        {
+            // gas used by the called frames is already recorded by them.
+            let nested = host.gas_by_category().total() - nested;
+            let used = self.gas.spend().saturating_sub(spent + nested);
+            let category = crate::opcode::gas_category(opcode);
+            host.gas_by_category().record(category, used);
+        }
     }
 
     /// loop steps until we are finished with execution
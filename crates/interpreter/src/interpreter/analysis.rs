@@ -15,22 +15,30 @@ use revm_primitives::{
 ///
 /// If the bytecode is already analyzed, it is returned as-is.
 pub fn to_analysed(bytecode: Bytecode) -> Bytecode {
+    to_locked(bytecode).unlock()
+}
+
+/// Perform bytecode analysis if it is not done already and lock the bytecode for execution.
+pub fn to_locked(bytecode: Bytecode) -> BytecodeLocked {
     let hash = bytecode.hash;
-    let (bytecode, len) = match bytecode.state {
+    let (bytecode, len, jump_map) = match bytecode.state {
         BytecodeState::Raw => {
             let len = bytecode.bytecode.len();
             let checked = bytecode.to_checked();
-            (checked.bytecode, len)
+            let jump_map = analyze(checked.bytecode.as_ref());
+            (checked.bytecode, len, jump_map)
         }
-        BytecodeState::Checked { len } => (bytecode.bytecode, len),
-        _ => return bytecode,
+        BytecodeState::Checked { len } => {
+            let jump_map = analyze(bytecode.bytecode.as_ref());
+            (bytecode.bytecode, len, jump_map)
+        }
+        BytecodeState::Analysed { len, jump_map } => (bytecode.bytecode, len, jump_map),
     };
-    let jump_map = analyze(bytecode.as_ref());
-
-    Bytecode {
+    BytecodeLocked {
         bytecode,
+        len,
         hash,
-        state: BytecodeState::Analysed { len, jump_map },
+        jump_map,
     }
 }
 
@@ -73,9 +81,7 @@ pub struct BytecodeLocked {
 
 impl Default for BytecodeLocked {
     fn default() -> Self {
-        Bytecode::default()
-            .try_into()
-            .expect("Bytecode default is analysed code")
+        to_locked(Bytecode::default())
     }
 }
 
@@ -127,7 +133,8 @@ impl BytecodeLocked {
     }
 
     pub fn original_bytecode_slice(&self) -> &[u8] {
-        &self.bytecode.as_ref()[..self.len]
+        let bytecode = self.bytecode.as_ref();
+        bytecode.get(..self.len).unwrap_or(bytecode)
     }
 
     pub fn jump_map(&self) -> &JumpMap {
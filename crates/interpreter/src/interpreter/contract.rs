@@ -1,4 +1,4 @@
-use super::analysis::{to_analysed, BytecodeLocked};
+use super::analysis::{to_locked, BytecodeLocked};
 use crate::primitives::{Bytecode, Bytes, B160, U256};
 use crate::CallContext;
 use revm_primitives::{Env, TransactTo};
@@ -20,7 +20,7 @@ pub struct Contract {
 
 impl Contract {
     pub fn new(input: Bytes, bytecode: Bytecode, address: B160, caller: B160, value: U256) -> Self {
-        let bytecode = to_analysed(bytecode).try_into().expect("it is analyzed");
+        let bytecode = to_locked(bytecode);
 
         Self {
             input,
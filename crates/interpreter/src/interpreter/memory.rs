@@ -1,4 +1,7 @@
-use crate::{alloc::vec::Vec, primitives::U256};
+use crate::{
+    alloc::vec::Vec,
+    primitives::{B256, U256},
+};
 use core::{
     cmp::min,
     ops::{BitAnd, Not},
@@ -21,8 +24,13 @@ impl Default for Memory {
 impl Memory {
     /// Create a new memory with the given limit.
     pub fn new() -> Self {
+        Self::with_capacity(4 * 1024) // took it from evmone
+    }
+
+    /// Create a new memory with preallocated `capacity` bytes.
+    pub fn with_capacity(capacity: usize) -> Self {
         Self {
-            data: Vec::with_capacity(4 * 1024), // took it from evmone
+            data: Vec::with_capacity(capacity),
         }
     }
 
@@ -61,10 +69,13 @@ impl Memory {
         self.data.resize(new_size, 0);
     }
 
-    /// Get memory region at given offset. Dont check offset and size
+    /// Get memory region at given offset. Memory is expected to be resized to contain it,
+    /// empty slice is returned otherwise.
     #[inline(always)]
     pub fn get_slice(&self, offset: usize, size: usize) -> &[u8] {
-        &self.data[offset..offset + size]
+        self.data
+            .get(offset..offset.saturating_add(size))
+            .unwrap_or_default()
     }
 
     /// Set memory region at given offset
@@ -73,19 +84,47 @@ impl Memory {
     /// The caller is responsible for checking the offset and value
     #[inline(always)]
     pub unsafe fn set_byte(&mut self, index: usize, byte: u8) {
-        *self.data.get_mut(index).unwrap() = byte;
+        if let Some(slot) = self.data.get_mut(index) {
+            *slot = byte;
+        }
+    }
+
+    /// Get 32 bytes at given offset. Memory is expected to be resized to contain them,
+    /// zero word is returned otherwise.
+    #[inline(always)]
+    pub fn get_word(&self, offset: usize) -> B256 {
+        self.data
+            .get(offset..offset.saturating_add(32))
+            .map(B256::from_slice)
+            .unwrap_or_default()
+    }
+
+    /// Get 32 bytes at given offset as big-endian [U256]. Dont check offset
+    #[inline(always)]
+    pub fn get_u256(&self, offset: usize) -> U256 {
+        U256::from_be_bytes::<{ U256::BYTES }>(self.get_word(offset).0)
+    }
+
+    /// Set 32 bytes at given offset. The offset is already checked
+    #[inline(always)]
+    pub fn set_word(&mut self, offset: usize, value: &B256) {
+        self.set(offset, value.as_bytes());
     }
 
     #[inline(always)]
     pub fn set_u256(&mut self, index: usize, value: U256) {
-        self.data[index..index + 32].copy_from_slice(&value.to_be_bytes::<{ U256::BYTES }>());
+        self.set(index, &value.to_be_bytes::<{ U256::BYTES }>());
     }
 
-    /// Set memory region at given offset. The offset and value are already checked
+    /// Set memory region at given offset. The offset and value are already checked,
+    /// regions outside of the memory are not written.
     #[inline(always)]
     pub fn set(&mut self, offset: usize, value: &[u8]) {
-        if !value.is_empty() {
-            self.data[offset..(value.len() + offset)].copy_from_slice(value);
+        if let Some(slot) = self
+            .data
+            .get_mut(offset..offset.saturating_add(value.len()))
+        {
+            slot.copy_from_slice(value);
         }
     }
 
@@ -93,22 +132,19 @@ impl Memory {
     /// are doing bound checks on data/data_offeset/len and zeroing parts that is not copied.
     #[inline(always)]
     pub fn set_data(&mut self, memory_offset: usize, data_offset: usize, len: usize, data: &[u8]) {
-        if data_offset >= data.len() {
-            // nulify all memory slots
-            for i in &mut self.data[memory_offset..memory_offset + len] {
-                *i = 0;
-            }
+        let Some(memory) = self
+            .data
+            .get_mut(memory_offset..memory_offset.saturating_add(len))
+        else {
             return;
-        }
-        let data_end = min(data_offset + len, data.len());
-        let memory_data_end = memory_offset + (data_end - data_offset);
-        self.data[memory_offset..memory_data_end].copy_from_slice(&data[data_offset..data_end]);
+        };
+        let data = data.get(data_offset..).unwrap_or_default();
+        let (data, _) = data.split_at(min(len, data.len()));
+        let (copied, rest) = memory.split_at_mut(data.len());
+        copied.copy_from_slice(data);
 
         // nulify rest of memory slots
-        // Safety: Memory is assumed to be valid. And it is commented where that assumption is made
-        for i in &mut self.data[memory_data_end..memory_offset + len] {
-            *i = 0;
-        }
+        rest.fill(0);
     }
 
     /// In memory copy given a src, dst, and length
@@ -117,7 +153,142 @@ impl Memory {
     /// The caller is responsible to check that we resized memory properly.
     #[inline(always)]
     pub fn copy(&mut self, dst: usize, src: usize, length: usize) {
-        self.data.copy_within(src..src + length, dst);
+        let len = self.data.len();
+        if src.saturating_add(length) <= len && dst.saturating_add(length) <= len {
+            self.data.copy_within(src..src + length, dst);
+        }
+    }
+}
+
+/// Pool of [`Memory`] buffers that can be reused between interpreters and transactions.
+///
+/// Released buffers are cleared but keep their allocation, so memory heavy workloads
+/// don't need to allocate again on every call. It also tracks the peak memory size
+/// that was used since the last [`MemoryPool::reset_peak`].
+///
+/// Total capacity of pooled buffers is limited by [`MemoryPool::max_pooled_bytes`],
+/// buffers released over the limit are freed.
+#[derive(Clone, Debug)]
+pub struct MemoryPool {
+    /// Cleared buffers ready to be reused.
+    buffers: Vec<Vec<u8>>,
+    /// Capacity of newly allocated buffers.
+    initial_capacity: usize,
+    /// Biggest memory length seen since last reset.
+    peak: usize,
+    /// Total capacity of pooled buffers.
+    pooled_bytes: usize,
+    /// Limit of `pooled_bytes`.
+    max_pooled_bytes: usize,
+}
+
+impl Default for MemoryPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryPool {
+    /// Create new pool with the default initial capacity of [`Memory::new`].
+    pub fn new() -> Self {
+        Self::with_initial_capacity(4 * 1024)
+    }
+
+    /// Create new pool where newly allocated buffers have `initial_capacity` bytes.
+    pub fn with_initial_capacity(initial_capacity: usize) -> Self {
+        Self {
+            buffers: Vec::new(),
+            initial_capacity,
+            peak: 0,
+            pooled_bytes: 0,
+            max_pooled_bytes: Self::DEFAULT_MAX_POOLED_BYTES,
+        }
+    }
+
+    /// Default limit of the total capacity of pooled buffers, 32 MiB.
+    pub const DEFAULT_MAX_POOLED_BYTES: usize = 32 * 1024 * 1024;
+
+    /// Limit of the total capacity of pooled buffers.
+    pub fn max_pooled_bytes(&self) -> usize {
+        self.max_pooled_bytes
+    }
+
+    /// Set limit of the total capacity of pooled buffers. Pooled buffers over the limit are freed.
+    pub fn set_max_pooled_bytes(&mut self, max_pooled_bytes: usize) {
+        self.max_pooled_bytes = max_pooled_bytes;
+        while self.pooled_bytes > max_pooled_bytes {
+            let Some(data) = self.buffers.pop() else {
+                break;
+            };
+            self.pooled_bytes -= data.capacity();
+        }
+    }
+
+    /// Capacity of newly allocated buffers.
+    pub fn initial_capacity(&self) -> usize {
+        self.initial_capacity
+    }
+
+    /// Set capacity of newly allocated buffers. Already pooled buffers are not affected.
+    pub fn set_initial_capacity(&mut self, initial_capacity: usize) {
+        self.initial_capacity = initial_capacity;
+    }
+
+    /// Take empty memory from the pool or allocate new one if pool is empty.
+    pub fn acquire(&mut self) -> Memory {
+        match self.buffers.pop() {
+            Some(data) => {
+                self.pooled_bytes -= data.capacity();
+                Memory { data }
+            }
+            None => Memory::with_capacity(self.initial_capacity),
+        }
+    }
+
+    /// Return memory to the pool and record its size.
+    ///
+    /// Memory is freed instead if pooling it would exceed [`MemoryPool::max_pooled_bytes`].
+    pub fn release(&mut self, memory: Memory) {
+        let mut data = memory.data;
+        self.peak = self.peak.max(data.len());
+        let pooled_bytes = self.pooled_bytes.saturating_add(data.capacity());
+        if pooled_bytes > self.max_pooled_bytes {
+            return;
+        }
+        data.clear();
+        self.pooled_bytes = pooled_bytes;
+        self.buffers.push(data);
+    }
+
+    /// Biggest memory size in bytes that was released to the pool since last reset.
+    pub fn peak(&self) -> usize {
+        self.peak
+    }
+
+    /// Reset peak memory size. It is called at the start of every transaction.
+    pub fn reset_peak(&mut self) {
+        self.peak = 0;
+    }
+
+    /// Number of buffers that are ready to be reused.
+    pub fn len(&self) -> usize {
+        self.buffers.len()
+    }
+
+    /// Returns true if there are no pooled buffers.
+    pub fn is_empty(&self) -> bool {
+        self.buffers.is_empty()
+    }
+
+    /// Total capacity in bytes of pooled buffers.
+    pub fn pooled_bytes(&self) -> usize {
+        self.pooled_bytes
+    }
+
+    /// Drop all pooled buffers and free their memory.
+    pub fn clear(&mut self) {
+        self.buffers = Vec::new();
+        self.pooled_bytes = 0;
     }
 }
 
@@ -130,10 +301,118 @@ pub(crate) fn next_multiple_of_32(x: usize) -> Option<usize> {
 
 #[cfg(test)]
 mod tests {
-    use crate::Memory;
+    use crate::primitives::{Bytecode, Bytes, B160, B256, U256};
+    use crate::{Contract, InstructionResult, Interpreter, Memory, MemoryPool};
 
     use super::next_multiple_of_32;
 
+    #[test]
+    fn test_memory_pool() {
+        let mut pool = MemoryPool::with_initial_capacity(64);
+        let mut memory = pool.acquire();
+        assert_eq!(memory.data().capacity(), 64);
+
+        memory.resize(128);
+        memory.set(0, &[1, 2, 3]);
+        pool.release(memory);
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool.peak(), 128);
+
+        // reused buffer is empty but keeps its allocation.
+        let memory = pool.acquire();
+        assert!(memory.is_empty());
+        assert!(memory.data().capacity() >= 128);
+        assert!(pool.is_empty());
+
+        pool.release(memory);
+        assert_eq!(pool.peak(), 128);
+        pool.reset_peak();
+        assert_eq!(pool.peak(), 0);
+    }
+
+    #[test]
+    fn test_memory_pool_limit() {
+        let mut pool = MemoryPool::with_initial_capacity(64);
+        pool.set_max_pooled_bytes(128);
+        let (first, second, third) = (pool.acquire(), pool.acquire(), pool.acquire());
+        pool.release(first);
+        pool.release(second);
+        assert_eq!(pool.pooled_bytes(), 128);
+        // buffer over the limit is freed.
+        pool.release(third);
+        assert_eq!(pool.len(), 2);
+
+        let mut memory = pool.acquire();
+        assert_eq!(pool.pooled_bytes(), 64);
+        memory.resize(256);
+        pool.release(memory);
+        assert_eq!((pool.len(), pool.peak()), (1, 256));
+
+        pool.set_max_pooled_bytes(0);
+        assert!(pool.is_empty());
+        assert_eq!(pool.pooled_bytes(), 0);
+    }
+
+    #[test]
+    fn test_set_data() {
+        let mut memory = Memory::new();
+        memory.resize(64);
+        memory.set(0, &[0xff; 64]);
+
+        // partially out of bounds data is zero padded.
+        memory.set_data(0, 2, 4, &[1, 2, 3, 4]);
+        assert_eq!(&memory.data()[..5], &[3, 4, 0, 0, 0xff]);
+
+        // fully out of bounds data zeroes memory.
+        memory.set_data(32, usize::MAX, 32, &[1, 2, 3, 4]);
+        assert_eq!(&memory.data()[32..], &[0; 32]);
+    }
+
+    #[test]
+    fn test_words() {
+        let mut memory = Memory::new();
+        memory.resize(64);
+        memory.set_u256(32, U256::from(0x1234));
+        assert_eq!(memory.get_u256(32), U256::from(0x1234));
+        assert_eq!(memory.get_word(32).as_bytes()[30..], [0x12, 0x34]);
+
+        memory.set_word(0, &B256::repeat_byte(0xff));
+        assert_eq!(memory.get_u256(0), U256::MAX);
+    }
+
+    #[test]
+    fn test_interpreter_helpers() {
+        let contract = Contract::new(
+            Bytes::new(),
+            Bytecode::new(),
+            B160::zero(),
+            B160::zero(),
+            U256::ZERO,
+        );
+        let mut interpreter = Interpreter::new(Box::new(contract), 1_000, false);
+
+        // reading zero bytes does not expand memory.
+        assert_eq!(interpreter.memory_read(100, 0), Ok(&[][..]));
+        assert!(interpreter.memory.is_empty());
+
+        interpreter.memory_write_u256(1, U256::from(7)).unwrap();
+        assert_eq!(interpreter.memory.len(), 64);
+        assert_eq!(interpreter.gas.memory(), 6);
+        assert_eq!(interpreter.memory_read_u256(1), Ok(U256::from(7)));
+        interpreter.memory_write(64, &[1, 2]).unwrap();
+        assert_eq!(interpreter.memory_read(64, 2), Ok(&[1, 2][..]));
+        assert_eq!(interpreter.memory.len(), 96);
+
+        assert_eq!(
+            interpreter.memory_read(usize::MAX, 1),
+            Err(InstructionResult::MemoryOOG)
+        );
+        assert_eq!(
+            interpreter.memory_write(0, &[0; 1024 * 1024]),
+            Err(InstructionResult::MemoryLimitOOG)
+        );
+    }
+
     #[test]
     fn test_copy() {
         // Create a sample memory instance
@@ -13,16 +13,16 @@ pub struct Stack {
 #[cfg(feature = "std")]
 impl std::fmt::Display for Stack {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
-        if self.data.is_empty() {
-            f.write_str("[]")?;
-        } else {
+        if let Some((last, rest)) = self.data.split_last() {
             f.write_str("[")?;
-            for i in self.data[..self.data.len() - 1].iter() {
+            for i in rest.iter() {
                 f.write_str(&i.to_string())?;
                 f.write_str(", ")?;
             }
-            f.write_str(&self.data.last().unwrap().to_string())?;
+            f.write_str(&last.to_string())?;
             f.write_str("]")?;
+        } else {
+            f.write_str("[]")?;
         }
         Ok(())
     }
@@ -88,8 +88,9 @@ impl Stack {
     pub unsafe fn pop_unsafe(&mut self) -> U256 {
         let mut len = self.data.len();
         len -= 1;
+        let pop = *self.data.get_unchecked(len);
         self.data.set_len(len);
-        *self.data.get_unchecked(len)
+        pop
     }
 
     #[inline(always)]
@@ -139,11 +140,12 @@ impl Stack {
     pub unsafe fn pop2_unsafe(&mut self) -> (U256, U256) {
         let mut len = self.data.len();
         len -= 2;
-        self.data.set_len(len);
-        (
+        let pop = (
             *self.data.get_unchecked(len + 1),
             *self.data.get_unchecked(len),
-        )
+        );
+        self.data.set_len(len);
+        pop
     }
 
     #[inline(always)]
@@ -154,12 +156,13 @@ impl Stack {
     pub unsafe fn pop3_unsafe(&mut self) -> (U256, U256, U256) {
         let mut len = self.data.len();
         len -= 3;
-        self.data.set_len(len);
-        (
+        let pop = (
             *self.data.get_unchecked(len + 2),
             *self.data.get_unchecked(len + 1),
             *self.data.get_unchecked(len),
-        )
+        );
+        self.data.set_len(len);
+        pop
     }
 
     #[inline(always)]
@@ -170,13 +173,14 @@ impl Stack {
     pub unsafe fn pop4_unsafe(&mut self) -> (U256, U256, U256, U256) {
         let mut len = self.data.len();
         len -= 4;
-        self.data.set_len(len);
-        (
+        let pop = (
             *self.data.get_unchecked(len + 3),
             *self.data.get_unchecked(len + 2),
             *self.data.get_unchecked(len + 1),
             *self.data.get_unchecked(len),
-        )
+        );
+        self.data.set_len(len);
+        pop
     }
 
     #[inline]
@@ -206,11 +210,12 @@ impl Stack {
     /// the stack is at index `0`. If the index is too large,
     /// `StackError::Underflow` is returned.
     pub fn peek(&self, no_from_top: usize) -> Result<U256, InstructionResult> {
-        if self.data.len() > no_from_top {
-            Ok(self.data[self.data.len() - no_from_top - 1])
-        } else {
-            Err(InstructionResult::StackUnderflow)
-        }
+        self.data
+            .iter()
+            .rev()
+            .nth(no_from_top)
+            .copied()
+            .ok_or(InstructionResult::StackUnderflow)
     }
 
     #[inline(always)]
@@ -222,10 +227,8 @@ impl Stack {
             Some(InstructionResult::StackOverflow)
         } else {
             // Safety: check for out of bounds is done above and it makes this safe to do.
-            unsafe {
-                *self.data.get_unchecked_mut(len) = *self.data.get_unchecked(len - N);
-                self.data.set_len(len + 1);
-            }
+            let value = unsafe { *self.data.get_unchecked(len - N) };
+            self.data.push(value);
             None
         }
     }
@@ -253,52 +256,9 @@ impl Stack {
             return Some(InstructionResult::StackOverflow);
         }
 
-        let slot;
-        // Safety: check above ensures us that we are okey in increment len.
-        unsafe {
-            self.data.set_len(new_len);
-            slot = self.data.get_unchecked_mut(new_len - 1);
-        }
-
-        unsafe {
-            *slot.as_limbs_mut() = [0u64; 4];
-            let mut dangling = [0u8; 8];
-            if N < 8 {
-                dangling[8 - N..].copy_from_slice(slice);
-                slot.as_limbs_mut()[0] = u64::from_be_bytes(dangling);
-            } else if N < 16 {
-                slot.as_limbs_mut()[0] =
-                    u64::from_be_bytes(slice[N - 8..N].try_into().expect("Infallible"));
-                if N != 8 {
-                    dangling[8 * 2 - N..].copy_from_slice(&slice[..N - 8]);
-                    slot.as_limbs_mut()[1] = u64::from_be_bytes(dangling);
-                }
-            } else if N < 24 {
-                slot.as_limbs_mut()[0] =
-                    u64::from_be_bytes(slice[N - 8..N].try_into().expect("Infallible"));
-                slot.as_limbs_mut()[1] =
-                    u64::from_be_bytes(slice[N - 16..N - 8].try_into().expect("Infallible"));
-                if N != 16 {
-                    dangling[8 * 3 - N..].copy_from_slice(&slice[..N - 16]);
-                    slot.as_limbs_mut()[2] = u64::from_be_bytes(dangling);
-                }
-            } else {
-                // M<32
-                slot.as_limbs_mut()[0] =
-                    u64::from_be_bytes(slice[N - 8..N].try_into().expect("Infallible"));
-                slot.as_limbs_mut()[1] =
-                    u64::from_be_bytes(slice[N - 16..N - 8].try_into().expect("Infallible"));
-                slot.as_limbs_mut()[2] =
-                    u64::from_be_bytes(slice[N - 24..N - 16].try_into().expect("Infallible"));
-                if N == 32 {
-                    slot.as_limbs_mut()[3] =
-                        u64::from_be_bytes(slice[..N - 24].try_into().expect("Infallible"));
-                } else if N != 24 {
-                    dangling[8 * 4 - N..].copy_from_slice(&slice[..N - 24]);
-                    slot.as_limbs_mut()[3] = u64::from_be_bytes(dangling);
-                }
-            }
-        }
+        // Bytecode is padded so the slice always has `N` bytes that fit into a word.
+        self.data
+            .push(U256::try_from_be_slice(slice).unwrap_or_default());
         None
     }
 
@@ -307,12 +267,13 @@ impl Stack {
     /// stack is at index `0`. If the index is too large,
     /// `StackError::Underflow` is returned.
     pub fn set(&mut self, no_from_top: usize, val: U256) -> Result<(), InstructionResult> {
-        if self.data.len() > no_from_top {
-            let len = self.data.len();
-            self.data[len - no_from_top - 1] = val;
-            Ok(())
-        } else {
-            Err(InstructionResult::StackUnderflow)
-        }
+        let slot = self
+            .data
+            .iter_mut()
+            .rev()
+            .nth(no_from_top)
+            .ok_or(InstructionResult::StackUnderflow)?;
+        *slot = val;
+        Ok(())
     }
 }
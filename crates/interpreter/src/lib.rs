@@ -1,4 +1,14 @@
 #![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(
+    all(feature = "no_panic", not(test)),
+    deny(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::indexing_slicing,
+        clippy::panic,
+        clippy::unreachable
+    )
+)]
 
 pub mod gas;
 mod host;
@@ -16,10 +26,10 @@ pub(crate) const USE_GAS: bool = !cfg!(feature = "no_gas_measuring");
 pub use gas::Gas;
 pub use host::{DummyHost, Host};
 pub use inner_models::*;
-pub use instruction_result::InstructionResult;
+pub use instruction_result::{FrameExit, InstructionResult};
 pub use instructions::opcode::{self, OpCode, OPCODE_JUMPMAP};
 pub use interpreter::*;
-pub use interpreter::{BytecodeLocked, Contract, Interpreter, Memory, Stack};
+pub use interpreter::{BytecodeLocked, Contract, Interpreter, Memory, MemoryPool, Stack};
 
 #[doc(inline)]
 pub use revm_primitives as primitives;
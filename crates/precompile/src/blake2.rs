@@ -1,9 +1,14 @@
-use crate::{Error, PrecompileAddress, StandardPrecompileFn};
+#[cfg(not(feature = "no_blake2"))]
+use crate::Error;
 use crate::{Precompile, PrecompileResult};
+use crate::{PrecompileAddress, StandardPrecompileFn};
+#[cfg(not(feature = "no_blake2"))]
 use core::convert::TryInto;
 
-const F_ROUND: u64 = 1;
-const INPUT_LENGTH: usize = 213;
+#[cfg(not(feature = "no_blake2"))]
+pub(crate) const F_ROUND: u64 = 1;
+#[cfg(not(feature = "no_blake2"))]
+pub(crate) const INPUT_LENGTH: usize = 213;
 
 pub const FUN: PrecompileAddress = PrecompileAddress(
     crate::u64_to_b160(9),
@@ -13,6 +18,7 @@ pub const FUN: PrecompileAddress = PrecompileAddress(
 /// reference: https://eips.ethereum.org/EIPS/eip-152
 /// input format:
 /// [4 bytes for rounds][64 bytes for h][128 bytes for m][8 bytes for t_0][8 bytes for t_1][1 byte for f]
+#[cfg(not(feature = "no_blake2"))]
 fn run(input: &[u8], gas_limit: u64) -> PrecompileResult {
     if input.len() != INPUT_LENGTH {
         return Err(Error::Blake2WrongLength);
@@ -55,6 +61,12 @@ fn run(input: &[u8], gas_limit: u64) -> PrecompileResult {
     Ok((gas_used, out.to_vec()))
 }
 
+#[cfg(feature = "no_blake2")]
+fn run(input: &[u8], gas_limit: u64) -> PrecompileResult {
+    crate::disabled(input, gas_limit)
+}
+
+#[cfg(not(feature = "no_blake2"))]
 mod algo {
     /// SIGMA from spec: https://datatracker.ietf.org/doc/html/rfc7693#section-2.7
     const SIGMA: [[usize; 16]; 10] = [
@@ -1,27 +1,31 @@
-use crate::{primitives::U256, Error, Precompile, PrecompileAddress, PrecompileResult, B160};
+#[cfg(not(feature = "no_bn128"))]
+use crate::primitives::U256;
+use crate::{Error, Precompile, PrecompileAddress, PrecompileResult, B160};
 use alloc::vec::Vec;
 
 pub mod add {
     use super::*;
     const ADDRESS: B160 = crate::u64_to_b160(6);
 
+    pub(crate) const ISTANBUL_ADD_GAS: u64 = 150;
     pub const ISTANBUL: PrecompileAddress = PrecompileAddress(
         ADDRESS,
         Precompile::Standard(|input: &[u8], target_gas: u64| -> PrecompileResult {
-            if 150 > target_gas {
+            if ISTANBUL_ADD_GAS > target_gas {
                 return Err(Error::OutOfGas);
             }
-            Ok((150, super::run_add(input)?))
+            Ok((ISTANBUL_ADD_GAS, super::run_add(input)?))
         }),
     );
 
+    pub(crate) const BYZANTIUM_ADD_GAS: u64 = 500;
     pub const BYZANTIUM: PrecompileAddress = PrecompileAddress(
         ADDRESS,
         Precompile::Standard(|input: &[u8], target_gas: u64| -> PrecompileResult {
-            if 500 > target_gas {
+            if BYZANTIUM_ADD_GAS > target_gas {
                 return Err(Error::OutOfGas);
             }
-            Ok((500, super::run_add(input)?))
+            Ok((BYZANTIUM_ADD_GAS, super::run_add(input)?))
         }),
     );
 }
@@ -29,23 +33,26 @@ pub mod add {
 pub mod mul {
     use super::*;
     const ADDRESS: B160 = crate::u64_to_b160(7);
+
+    pub(crate) const ISTANBUL_MUL_GAS: u64 = 6_000;
     pub const ISTANBUL: PrecompileAddress = PrecompileAddress(
         ADDRESS,
         Precompile::Standard(|input: &[u8], gas_limit: u64| -> PrecompileResult {
-            if 6_000 > gas_limit {
+            if ISTANBUL_MUL_GAS > gas_limit {
                 return Err(Error::OutOfGas);
             }
-            Ok((6_000, super::run_mul(input)?))
+            Ok((ISTANBUL_MUL_GAS, super::run_mul(input)?))
         }),
     );
 
+    pub(crate) const BYZANTIUM_MUL_GAS: u64 = 40_000;
     pub const BYZANTIUM: PrecompileAddress = PrecompileAddress(
         ADDRESS,
         Precompile::Standard(|input: &[u8], gas_limit: u64| -> PrecompileResult {
-            if 40_000 > gas_limit {
+            if BYZANTIUM_MUL_GAS > gas_limit {
                 return Err(Error::OutOfGas);
             }
-            Ok((40_000, super::run_mul(input)?))
+            Ok((BYZANTIUM_MUL_GAS, super::run_mul(input)?))
         }),
     );
 }
@@ -54,8 +61,8 @@ pub mod pair {
     use super::*;
     const ADDRESS: B160 = crate::u64_to_b160(8);
 
-    const ISTANBUL_PAIR_PER_POINT: u64 = 34_000;
-    const ISTANBUL_PAIR_BASE: u64 = 45_000;
+    pub(crate) const ISTANBUL_PAIR_PER_POINT: u64 = 34_000;
+    pub(crate) const ISTANBUL_PAIR_BASE: u64 = 45_000;
     pub const ISTANBUL: PrecompileAddress = PrecompileAddress(
         ADDRESS,
         Precompile::Standard(|input: &[u8], target_gas: u64| -> PrecompileResult {
@@ -68,8 +75,8 @@ pub mod pair {
         }),
     );
 
-    const BYZANTIUM_PAIR_PER_POINT: u64 = 80_000;
-    const BYZANTIUM_PAIR_BASE: u64 = 100_000;
+    pub(crate) const BYZANTIUM_PAIR_PER_POINT: u64 = 80_000;
+    pub(crate) const BYZANTIUM_PAIR_BASE: u64 = 100_000;
     pub const BYZANTIUM: PrecompileAddress = PrecompileAddress(
         ADDRESS,
         Precompile::Standard(|input: &[u8], target_gas: u64| -> PrecompileResult {
@@ -84,15 +91,19 @@ pub mod pair {
 }
 
 /// Input length for the add operation.
+#[cfg(not(feature = "no_bn128"))]
 const ADD_INPUT_LEN: usize = 128;
 
 /// Input length for the multiplication operation.
+#[cfg(not(feature = "no_bn128"))]
 const MUL_INPUT_LEN: usize = 128;
 
 /// Pair element length.
-const PAIR_ELEMENT_LEN: usize = 192;
+#[cfg(not(feature = "no_bn128"))]
+pub(crate) const PAIR_ELEMENT_LEN: usize = 192;
 
 /// Reads the `x` and `y` points from an input at a given position.
+#[cfg(not(feature = "no_bn128"))]
 fn read_point(input: &[u8], pos: usize) -> Result<bn::G1, Error> {
     use bn::{AffineG1, Fq, Group, G1};
 
@@ -113,6 +124,7 @@ fn read_point(input: &[u8], pos: usize) -> Result<bn::G1, Error> {
     }
 }
 
+#[cfg(not(feature = "no_bn128"))]
 fn run_add(input: &[u8]) -> Result<Vec<u8>, Error> {
     use bn::AffineG1;
 
@@ -137,6 +149,7 @@ fn run_add(input: &[u8]) -> Result<Vec<u8>, Error> {
     Ok(output.into())
 }
 
+#[cfg(not(feature = "no_bn128"))]
 fn run_mul(input: &[u8]) -> Result<Vec<u8>, Error> {
     use bn::AffineG1;
 
@@ -159,6 +172,7 @@ fn run_mul(input: &[u8]) -> Result<Vec<u8>, Error> {
     Ok(out.to_vec())
 }
 
+#[cfg(not(feature = "no_bn128"))]
 fn run_pair(
     input: &[u8],
     pair_per_point_cost: u64,
@@ -235,6 +249,21 @@ fn run_pair(
     Ok((gas_used, output.to_be_bytes_vec()))
 }
 
+#[cfg(feature = "no_bn128")]
+fn run_add(_input: &[u8]) -> Result<Vec<u8>, Error> {
+    Err(Error::Disabled)
+}
+
+#[cfg(feature = "no_bn128")]
+fn run_mul(_input: &[u8]) -> Result<Vec<u8>, Error> {
+    Err(Error::Disabled)
+}
+
+#[cfg(feature = "no_bn128")]
+fn run_pair(input: &[u8], _per_point: u64, _base: u64, gas_limit: u64) -> PrecompileResult {
+    crate::disabled(input, gas_limit)
+}
+
 /*
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,182 @@
+//! Gas cost estimation of precompile calls without executing them.
+#[cfg(not(feature = "no_blake2"))]
+use crate::blake2;
+#[cfg(not(feature = "no_bn128"))]
+use crate::bn128;
+#[cfg(not(feature = "no_modexp"))]
+use crate::modexp;
+use crate::{calc_linear_cost_u32, hash, identity, secp256k1, SpecId, B160};
+
+/// Estimated gas cost of the precompile call, see [precompile_gas].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrecompileGas {
+    /// Cost depends only on the input length and is exact.
+    Exact(u64),
+    /// Cost depends on the input content. `max` is `None` if cost is not bounded.
+    Range { min: u64, max: Option<u64> },
+    /// Input length is invalid, call is going to fail and consume all gas given to it.
+    Fail,
+}
+
+/// Estimates gas cost of calling precompile at `address` with input of `input_len` bytes.
+///
+/// Returns `None` if there is no precompile at `address` for the given spec.
+///
+/// Estimation is exact for all precompiles except:
+/// * modexp, cost depends on the exponent value. Minimal cost is 200 since Berlin (EIP-2565)
+///   and 0 before it, and it is not bounded.
+/// * blake2f, cost is the number of rounds from the input, that is between 0 and `u32::MAX`.
+///
+/// Precompiles that are excluded from the build by their `no_*` feature return [PrecompileGas::Fail],
+/// as calls to them fail with `PrecompileError::Disabled`.
+pub fn precompile_gas(address: &B160, input_len: usize, spec: SpecId) -> Option<PrecompileGas> {
+    if address[..19].iter().any(|b| *b != 0) {
+        return None;
+    }
+    let is_byzantium = spec >= SpecId::BYZANTIUM;
+    let is_istanbul = spec >= SpecId::ISTANBUL;
+    let gas = match address[19] {
+        1 => PrecompileGas::Exact(secp256k1::ECRECOVER_BASE),
+        2 => PrecompileGas::Exact(calc_linear_cost_u32(
+            input_len,
+            hash::SHA256_BASE,
+            hash::SHA256_PER_WORD,
+        )),
+        3 => PrecompileGas::Exact(calc_linear_cost_u32(
+            input_len,
+            hash::RIPEMD160_BASE,
+            hash::RIPEMD160_PER_WORD,
+        )),
+        4 => PrecompileGas::Exact(calc_linear_cost_u32(
+            input_len,
+            identity::IDENTITY_BASE,
+            identity::IDENTITY_PER_WORD,
+        )),
+        #[cfg(not(feature = "no_modexp"))]
+        5 if is_byzantium => PrecompileGas::Range {
+            min: if spec >= SpecId::BERLIN {
+                modexp::BERLIN_MIN_GAS
+            } else {
+                0
+            },
+            max: None,
+        },
+        #[cfg(not(feature = "no_bn128"))]
+        6 if is_istanbul => PrecompileGas::Exact(bn128::add::ISTANBUL_ADD_GAS),
+        #[cfg(not(feature = "no_bn128"))]
+        6 if is_byzantium => PrecompileGas::Exact(bn128::add::BYZANTIUM_ADD_GAS),
+        #[cfg(not(feature = "no_bn128"))]
+        7 if is_istanbul => PrecompileGas::Exact(bn128::mul::ISTANBUL_MUL_GAS),
+        #[cfg(not(feature = "no_bn128"))]
+        7 if is_byzantium => PrecompileGas::Exact(bn128::mul::BYZANTIUM_MUL_GAS),
+        #[cfg(not(feature = "no_bn128"))]
+        8 if is_byzantium => {
+            if input_len % bn128::PAIR_ELEMENT_LEN != 0 {
+                return Some(PrecompileGas::Fail);
+            }
+            let (per_point, base) = if is_istanbul {
+                (
+                    bn128::pair::ISTANBUL_PAIR_PER_POINT,
+                    bn128::pair::ISTANBUL_PAIR_BASE,
+                )
+            } else {
+                (
+                    bn128::pair::BYZANTIUM_PAIR_PER_POINT,
+                    bn128::pair::BYZANTIUM_PAIR_BASE,
+                )
+            };
+            PrecompileGas::Exact(
+                per_point * input_len as u64 / bn128::PAIR_ELEMENT_LEN as u64 + base,
+            )
+        }
+        #[cfg(not(feature = "no_blake2"))]
+        9 if is_istanbul => {
+            if input_len != blake2::INPUT_LENGTH {
+                return Some(PrecompileGas::Fail);
+            }
+            PrecompileGas::Range {
+                min: 0,
+                max: Some(u32::MAX as u64 * blake2::F_ROUND),
+            }
+        }
+        #[cfg(feature = "no_modexp")]
+        5 if is_byzantium => PrecompileGas::Fail,
+        #[cfg(feature = "no_bn128")]
+        6..=8 if is_byzantium => PrecompileGas::Fail,
+        #[cfg(feature = "no_blake2")]
+        9 if is_istanbul => PrecompileGas::Fail,
+        _ => return None,
+    };
+    Some(gas)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{u64_to_b160, Precompile, Precompiles};
+
+    #[test]
+    fn matches_precompiles() {
+        for spec in [
+            SpecId::HOMESTEAD,
+            SpecId::BYZANTIUM,
+            SpecId::ISTANBUL,
+            SpecId::BERLIN,
+        ] {
+            let precompiles = Precompiles::new(spec);
+            for i in 1..=10 {
+                let address = u64_to_b160(i);
+                let estimate = precompile_gas(&address, 192, spec);
+                assert_eq!(estimate.is_some(), precompiles.contains(&address));
+
+                // length based precompiles should return same cost as execution.
+                if let Some(PrecompileGas::Exact(gas)) = estimate {
+                    let Some(Precompile::Standard(fun)) = precompiles.get(&address) else {
+                        panic!("standard precompile expected");
+                    };
+                    // ecrecover and bn128 fail on zero input after charging gas.
+                    if let Ok((used, _)) = fun(&[0; 192], u64::MAX) {
+                        assert_eq!(used, gas, "precompile {i} spec {spec:?}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "no_bn128")]
+    #[test]
+    fn disabled() {
+        assert_eq!(
+            precompile_gas(&u64_to_b160(6), 128, SpecId::ISTANBUL),
+            Some(PrecompileGas::Fail)
+        );
+        assert_eq!(
+            precompile_gas(&u64_to_b160(6), 128, SpecId::HOMESTEAD),
+            None
+        );
+    }
+
+    #[cfg(all(
+        not(feature = "no_bn128"),
+        not(feature = "no_modexp"),
+        not(feature = "no_blake2")
+    ))]
+    #[test]
+    fn input_dependent() {
+        assert_eq!(
+            precompile_gas(&u64_to_b160(5), 96, SpecId::BERLIN),
+            Some(PrecompileGas::Range {
+                min: 200,
+                max: None
+            })
+        );
+        assert_eq!(
+            precompile_gas(&u64_to_b160(9), 100, SpecId::BERLIN),
+            Some(PrecompileGas::Fail)
+        );
+        assert_eq!(
+            precompile_gas(&u64_to_b160(8), 2 * 192, SpecId::ISTANBUL),
+            Some(PrecompileGas::Exact(113_000))
+        );
+    }
+}
@@ -11,11 +11,16 @@ pub const RIPEMD160: PrecompileAddress = PrecompileAddress(
     Precompile::Standard(ripemd160_run as StandardPrecompileFn),
 );
 
+pub(crate) const SHA256_BASE: u64 = 60;
+pub(crate) const SHA256_PER_WORD: u64 = 12;
+pub(crate) const RIPEMD160_BASE: u64 = 600;
+pub(crate) const RIPEMD160_PER_WORD: u64 = 120;
+
 /// See: https://ethereum.github.io/yellowpaper/paper.pdf
 /// See: https://docs.soliditylang.org/en/develop/units-and-global-variables.html#mathematical-and-cryptographic-functions
 /// See: https://etherscan.io/address/0000000000000000000000000000000000000002
 fn sha256_run(input: &[u8], gas_limit: u64) -> PrecompileResult {
-    let cost = calc_linear_cost_u32(input.len(), 60, 12);
+    let cost = calc_linear_cost_u32(input.len(), SHA256_BASE, SHA256_PER_WORD);
     if cost > gas_limit {
         Err(Error::OutOfGas)
     } else {
@@ -28,7 +33,7 @@ fn sha256_run(input: &[u8], gas_limit: u64) -> PrecompileResult {
 /// See: https://docs.soliditylang.org/en/develop/units-and-global-variables.html#mathematical-and-cryptographic-functions
 /// See: https://etherscan.io/address/0000000000000000000000000000000000000003
 fn ripemd160_run(input: &[u8], gas_limit: u64) -> PrecompileResult {
-    let gas_used = calc_linear_cost_u32(input.len(), 600, 120);
+    let gas_used = calc_linear_cost_u32(input.len(), RIPEMD160_BASE, RIPEMD160_PER_WORD);
     if gas_used > gas_limit {
         Err(Error::OutOfGas)
     } else {
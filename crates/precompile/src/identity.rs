@@ -7,9 +7,9 @@ pub const FUN: PrecompileAddress = PrecompileAddress(
 );
 
 /// The base cost of the operation.
-const IDENTITY_BASE: u64 = 15;
+pub(crate) const IDENTITY_BASE: u64 = 15;
 /// The cost per word.
-const IDENTITY_PER_WORD: u64 = 3;
+pub(crate) const IDENTITY_PER_WORD: u64 = 3;
 
 /// Takes the input bytes, copies them, and returns it as the output.
 ///
@@ -2,11 +2,13 @@
 
 mod blake2;
 mod bn128;
+mod gas;
 mod hash;
 mod identity;
 mod modexp;
 mod secp256k1;
 
+pub use gas::{precompile_gas, PrecompileGas};
 use once_cell::sync::OnceCell;
 pub use primitives::{
     precompile::{PrecompileError as Error, *},
@@ -28,6 +30,15 @@ pub fn calc_linear_cost_u32(len: usize, base: u64, word: u64) -> u64 {
     (len as u64 + 32 - 1) / 32 * word + base
 }
 
+/// Precompile that is excluded from the build by its `no_*` feature.
+///
+/// Calls fail with [Error::Disabled] instead of being executed as calls to an empty account,
+/// so a missing precompile can't silently change the result of the execution.
+#[cfg(any(feature = "no_bn128", feature = "no_modexp", feature = "no_blake2"))]
+fn disabled(_input: &[u8], _gas_limit: u64) -> PrecompileResult {
+    Err(Error::Disabled)
+}
+
 #[derive(Debug)]
 pub struct PrecompileOutput {
     pub cost: u64,
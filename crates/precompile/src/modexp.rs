@@ -1,11 +1,14 @@
-use crate::{
-    primitives::U256, Error, Precompile, PrecompileAddress, PrecompileResult, StandardPrecompileFn,
-};
+#[cfg(not(feature = "no_modexp"))]
+use crate::{primitives::U256, Error};
+use crate::{Precompile, PrecompileAddress, PrecompileResult, StandardPrecompileFn};
+#[cfg(not(feature = "no_modexp"))]
 use alloc::vec::Vec;
+#[cfg(not(feature = "no_modexp"))]
 use core::{
     cmp::{max, min, Ordering},
     mem::size_of,
 };
+#[cfg(not(feature = "no_modexp"))]
 use num::{BigUint, One, Zero};
 
 pub const BYZANTIUM: PrecompileAddress = PrecompileAddress(
@@ -20,18 +23,35 @@ pub const BERLIN: PrecompileAddress = PrecompileAddress(
 
 /// See: https://eips.ethereum.org/EIPS/eip-198
 /// See: https://etherscan.io/address/0000000000000000000000000000000000000005
+#[cfg(not(feature = "no_modexp"))]
 fn byzantium_run(input: &[u8], gas_limit: u64) -> PrecompileResult {
     run_inner(input, gas_limit, 0, |a, b, c, d| {
         byzantium_gas_calc(a, b, c, d)
     })
 }
 
+/// Minimal gas cost of the call after EIP-2565.
+#[cfg(not(feature = "no_modexp"))]
+pub(crate) const BERLIN_MIN_GAS: u64 = 200;
+
+#[cfg(not(feature = "no_modexp"))]
 pub fn berlin_run(input: &[u8], gas_limit: u64) -> PrecompileResult {
-    run_inner(input, gas_limit, 200, |a, b, c, d| {
+    run_inner(input, gas_limit, BERLIN_MIN_GAS, |a, b, c, d| {
         berlin_gas_calc(a, b, c, d)
     })
 }
 
+#[cfg(feature = "no_modexp")]
+fn byzantium_run(input: &[u8], gas_limit: u64) -> PrecompileResult {
+    crate::disabled(input, gas_limit)
+}
+
+#[cfg(feature = "no_modexp")]
+pub fn berlin_run(input: &[u8], gas_limit: u64) -> PrecompileResult {
+    crate::disabled(input, gas_limit)
+}
+
+#[cfg(not(feature = "no_modexp"))]
 fn calculate_iteration_count(exp_length: u64, exp_highp: &BigUint) -> u64 {
     let mut iteration_count: u64 = 0;
 
@@ -46,6 +66,7 @@ fn calculate_iteration_count(exp_length: u64, exp_highp: &BigUint) -> u64 {
     max(iteration_count, 1)
 }
 
+#[cfg(not(feature = "no_modexp"))]
 macro_rules! read_u64_with_overflow {
     ($input:expr,$from:expr,$to:expr, $overflow_limit:expr) => {{
         const SPLIT: usize = 32 - size_of::<u64>();
@@ -63,6 +84,7 @@ macro_rules! read_u64_with_overflow {
     }};
 }
 
+#[cfg(not(feature = "no_modexp"))]
 fn run_inner<F>(input: &[u8], gas_limit: u64, min_gas: u64, calc_gas: F) -> PrecompileResult
 where
     F: FnOnce(u64, u64, u64, &BigUint) -> u64,
@@ -143,6 +165,7 @@ where
     }
 }
 
+#[cfg(not(feature = "no_modexp"))]
 fn byzantium_gas_calc(base_len: u64, exp_len: u64, mod_len: u64, exp_highp: &BigUint) -> u64 {
     // ouput of this function is bounded by 2^128
     fn mul_complexity(x: u64) -> U256 {
@@ -172,6 +195,7 @@ fn byzantium_gas_calc(base_len: u64, exp_len: u64, mod_len: u64, exp_highp: &Big
 
 // Calculate gas cost according to EIP 2565:
 // https://eips.ethereum.org/EIPS/eip-2565
+#[cfg(not(feature = "no_modexp"))]
 fn berlin_gas_calc(base_length: u64, exp_length: u64, mod_length: u64, exp_highp: &BigUint) -> u64 {
     fn calculate_multiplication_complexity(base_length: u64, mod_length: u64) -> U256 {
         let max_length = max(base_length, mod_length);
@@ -190,11 +214,11 @@ fn berlin_gas_calc(base_length: u64, exp_length: u64, mod_length: u64, exp_highp
     if gas.as_limbs()[1] != 0 || gas.as_limbs()[2] != 0 || gas.as_limbs()[3] != 0 {
         u64::MAX
     } else {
-        max(200, gas.as_limbs()[0])
+        max(BERLIN_MIN_GAS, gas.as_limbs()[0])
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, not(feature = "no_modexp")))]
 mod tests {
 
     use super::*;
@@ -59,12 +59,12 @@ mod secp256k1 {
     }
 }
 
+pub(crate) const ECRECOVER_BASE: u64 = 3_000;
+
 fn ec_recover_run(i: &[u8], target_gas: u64) -> PrecompileResult {
     use alloc::vec::Vec;
     use core::cmp::min;
 
-    const ECRECOVER_BASE: u64 = 3_000;
-
     if ECRECOVER_BASE > target_gas {
         return Err(Error::OutOfGas);
     }
@@ -84,6 +84,34 @@ impl From<B256> for ruint::aliases::U256 {
 
 impl_fixed_hash_conversions!(B256, B160);
 
+#[cfg(feature = "alloy")]
+impl From<alloy_primitives::Address> for B160 {
+    fn from(fr: alloy_primitives::Address) -> Self {
+        B160(fr.0 .0)
+    }
+}
+
+#[cfg(feature = "alloy")]
+impl From<B160> for alloy_primitives::Address {
+    fn from(fr: B160) -> Self {
+        alloy_primitives::Address::from(fr.0)
+    }
+}
+
+#[cfg(feature = "alloy")]
+impl From<alloy_primitives::B256> for B256 {
+    fn from(fr: alloy_primitives::B256) -> Self {
+        B256(fr.0)
+    }
+}
+
+#[cfg(feature = "alloy")]
+impl From<B256> for alloy_primitives::B256 {
+    fn from(fr: B256) -> Self {
+        alloy_primitives::B256::from(fr.0)
+    }
+}
+
 #[cfg(feature = "serde")]
 impl serde::Serialize for B256 {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -375,4 +403,35 @@ mod tests {
         let new_b256: B256 = u256.into();
         assert_eq!(b256, new_b256)
     }
+
+    #[cfg(feature = "alloy")]
+    #[test]
+    fn should_convert_to_alloy_address() {
+        let b160 = B160::random();
+        let address: alloy_primitives::Address = b160.into();
+        assert_eq!(address.as_slice(), b160.as_bytes());
+        let new_b160: B160 = address.into();
+        assert_eq!(b160, new_b160)
+    }
+
+    #[cfg(feature = "alloy")]
+    #[test]
+    fn should_convert_to_alloy_b256() {
+        let b256 = B256::random();
+        let hash: alloy_primitives::B256 = b256.into();
+        assert_eq!(hash.as_slice(), b256.as_bytes());
+        let new_b256: B256 = hash.into();
+        assert_eq!(b256, new_b256)
+    }
+
+    #[cfg(feature = "alloy")]
+    #[test]
+    fn should_share_alloy_u256() {
+        // alloy-primitives is built on the same ruint crate, so no conversion is needed.
+        let value = crate::U256::MAX - crate::U256::from(7);
+        let alloy: alloy_primitives::U256 = value;
+        assert_eq!(alloy.to_be_bytes::<32>(), value.to_be_bytes::<32>());
+        let back: crate::U256 = alloy_primitives::U256::from_be_bytes(alloy.to_be_bytes::<32>());
+        assert_eq!(back, value);
+    }
 }
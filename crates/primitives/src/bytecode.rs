@@ -118,6 +118,11 @@ impl Bytecode {
         }
     }
 
+    /// Original bytecode without padding, it does not allocate.
+    pub fn original_bytecode_slice(&self) -> &[u8] {
+        &self.bytecode[..self.len()]
+    }
+
     pub fn hash(&self) -> B256 {
         self.hash
     }
@@ -0,0 +1,342 @@
+//! Deterministic compact binary encoding of [State] changesets.
+//!
+//! Encoding is optimized for size and is stable between runs, so it can be used as an input to
+//! zk provers or for transferring state between processes:
+//! * Integers are encoded as LEB128 varints and [U256] values with stripped leading zeros.
+//! * Accounts are sorted by address and storage slots by key.
+//! * Bytecodes are deduplicated by their hash and referenced by index.
+//!
+//! Bytecodes are encoded by their original bytes, decoded bytecodes are [`Bytecode::new_raw`].
+
+use crate::{
+    Account, AccountInfo, AccountStatus, Bytecode, State, StorageSlot, B160, B256, KECCAK_EMPTY,
+    U256,
+};
+use alloc::{collections::BTreeMap, vec::Vec};
+use bytes::Bytes;
+use core::fmt;
+
+/// Version of the encoding, it is the first byte of the encoded state.
+pub const COMPACT_STATE_VERSION: u8 = 1;
+
+/// Code index of account without code.
+const CODE_NONE: u64 = 0;
+/// Code index of account with empty code.
+const CODE_EMPTY: u64 = 1;
+/// First code index that points into the code table.
+const CODE_TABLE_OFFSET: u64 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompactDecodeError {
+    /// Input ended before the value was fully decoded.
+    UnexpectedEof,
+    /// Version byte is not [COMPACT_STATE_VERSION].
+    UnsupportedVersion(u8),
+    /// Varint does not fit into u64.
+    VarintOverflow,
+    /// [U256] is encoded with more than 32 bytes.
+    InvalidU256Length(u8),
+    /// Unknown [AccountStatus] bits.
+    InvalidStatus(u8),
+    /// Unknown code hash tag.
+    InvalidCodeHashTag(u8),
+    /// Code index does not point into the code table.
+    InvalidCodeIndex(u64),
+    /// There are bytes left after the state was decoded.
+    TrailingBytes,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CompactDecodeError {}
+
+impl fmt::Display for CompactDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedEof => f.write_str("Unexpected end of input"),
+            Self::UnsupportedVersion(v) => write!(f, "Unsupported version {v}"),
+            Self::VarintOverflow => f.write_str("Varint overflow"),
+            Self::InvalidU256Length(len) => write!(f, "Invalid U256 length {len}"),
+            Self::InvalidStatus(status) => write!(f, "Invalid account status {status:#x}"),
+            Self::InvalidCodeHashTag(tag) => write!(f, "Invalid code hash tag {tag}"),
+            Self::InvalidCodeIndex(index) => write!(f, "Invalid code index {index}"),
+            Self::TrailingBytes => f.write_str("Trailing bytes after state"),
+        }
+    }
+}
+
+/// Encode state in compact binary format.
+pub fn encode_state(state: &State) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.push(COMPACT_STATE_VERSION);
+
+    // deduplicated code table sorted by code hash.
+    let codes: BTreeMap<B256, Bytes> = state
+        .values()
+        .filter_map(|account| account.info.code.as_ref())
+        .filter(|code| !code.is_empty())
+        .map(|code| (code.hash(), code.original_bytes()))
+        .collect();
+    let code_index: BTreeMap<B256, u64> = codes
+        .keys()
+        .enumerate()
+        .map(|(i, hash)| (*hash, i as u64 + CODE_TABLE_OFFSET))
+        .collect();
+
+    write_varint(&mut out, codes.len() as u64);
+    for code in codes.values() {
+        write_varint(&mut out, code.len() as u64);
+        out.extend_from_slice(code);
+    }
+
+    let mut accounts: Vec<_> = state.iter().collect();
+    accounts.sort_unstable_by_key(|(address, _)| **address);
+
+    write_varint(&mut out, accounts.len() as u64);
+    for (address, account) in accounts {
+        out.extend_from_slice(address.as_bytes());
+        out.push(account.status.bits());
+        write_u256(&mut out, account.info.balance);
+        write_varint(&mut out, account.info.nonce);
+        if account.info.code_hash == KECCAK_EMPTY {
+            out.push(0);
+        } else {
+            out.push(1);
+            out.extend_from_slice(account.info.code_hash.as_bytes());
+        }
+        let index = match &account.info.code {
+            None => CODE_NONE,
+            Some(code) if code.is_empty() => CODE_EMPTY,
+            Some(code) => code_index[&code.hash()],
+        };
+        write_varint(&mut out, index);
+
+        let mut storage: Vec<_> = account.storage.iter().collect();
+        storage.sort_unstable_by_key(|(key, _)| **key);
+
+        write_varint(&mut out, storage.len() as u64);
+        for (key, slot) in storage {
+            write_u256(&mut out, *key);
+            write_u256(&mut out, slot.original_value);
+            write_u256(&mut out, slot.present_value);
+        }
+    }
+    out
+}
+
+/// Decode state encoded with [encode_state].
+pub fn decode_state(mut input: &[u8]) -> Result<State, CompactDecodeError> {
+    let buf = &mut input;
+    let version = read_u8(buf)?;
+    if version != COMPACT_STATE_VERSION {
+        return Err(CompactDecodeError::UnsupportedVersion(version));
+    }
+
+    let codes_len = read_varint(buf)?;
+    let mut codes = Vec::new();
+    for _ in 0..codes_len {
+        let len = read_varint(buf)?;
+        let code = read_bytes(buf, len)?;
+        codes.push(Bytecode::new_raw(Bytes::copy_from_slice(code)));
+    }
+
+    let accounts_len = read_varint(buf)?;
+    let mut state = State::new();
+    for _ in 0..accounts_len {
+        let address = B160::from_slice(read_bytes(buf, 20)?);
+        let status_bits = read_u8(buf)?;
+        let status = AccountStatus::from_bits(status_bits)
+            .ok_or(CompactDecodeError::InvalidStatus(status_bits))?;
+        let balance = read_u256(buf)?;
+        let nonce = read_varint(buf)?;
+        let code_hash = match read_u8(buf)? {
+            0 => KECCAK_EMPTY,
+            1 => B256::from_slice(read_bytes(buf, 32)?),
+            tag => return Err(CompactDecodeError::InvalidCodeHashTag(tag)),
+        };
+        let code = match read_varint(buf)? {
+            CODE_NONE => None,
+            CODE_EMPTY => Some(Bytecode::new()),
+            index => Some(
+                codes
+                    .get((index - CODE_TABLE_OFFSET) as usize)
+                    .ok_or(CompactDecodeError::InvalidCodeIndex(index))?
+                    .clone(),
+            ),
+        };
+
+        let storage_len = read_varint(buf)?;
+        let mut storage = crate::Storage::new();
+        for _ in 0..storage_len {
+            let key = read_u256(buf)?;
+            let original_value = read_u256(buf)?;
+            let present_value = read_u256(buf)?;
+            storage.insert(
+                key,
+                StorageSlot {
+                    original_value,
+                    present_value,
+                },
+            );
+        }
+
+        state.insert(
+            address,
+            Account {
+                info: AccountInfo {
+                    balance,
+                    nonce,
+                    code_hash,
+                    code,
+                },
+                storage,
+                status,
+            },
+        );
+    }
+
+    if !buf.is_empty() {
+        return Err(CompactDecodeError::TrailingBytes);
+    }
+    Ok(state)
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn write_u256(out: &mut Vec<u8>, value: U256) {
+    let bytes = value.to_be_bytes::<32>();
+    let skip = bytes.iter().take_while(|b| **b == 0).count();
+    out.push((32 - skip) as u8);
+    out.extend_from_slice(&bytes[skip..]);
+}
+
+fn read_u8(buf: &mut &[u8]) -> Result<u8, CompactDecodeError> {
+    let (first, rest) = buf.split_first().ok_or(CompactDecodeError::UnexpectedEof)?;
+    *buf = rest;
+    Ok(*first)
+}
+
+fn read_bytes<'a>(buf: &mut &'a [u8], len: u64) -> Result<&'a [u8], CompactDecodeError> {
+    if (buf.len() as u64) < len {
+        return Err(CompactDecodeError::UnexpectedEof);
+    }
+    let (bytes, rest) = buf.split_at(len as usize);
+    *buf = rest;
+    Ok(bytes)
+}
+
+fn read_varint(buf: &mut &[u8]) -> Result<u64, CompactDecodeError> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = read_u8(buf)?;
+        let bits = (byte & 0x7f) as u64;
+        if shift == 63 && bits > 1 {
+            return Err(CompactDecodeError::VarintOverflow);
+        }
+        value |= bits << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(CompactDecodeError::VarintOverflow)
+}
+
+fn read_u256(buf: &mut &[u8]) -> Result<U256, CompactDecodeError> {
+    let len = read_u8(buf)?;
+    if len > 32 {
+        return Err(CompactDecodeError::InvalidU256Length(len));
+    }
+    let bytes = read_bytes(buf, len as u64)?;
+    Ok(U256::try_from_be_slice(bytes).expect("Length is checked"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn golden_vector() {
+        let mut account = Account::from(AccountInfo {
+            balance: U256::from(1),
+            nonce: 1,
+            ..Default::default()
+        });
+        account.info.code = None;
+        account.mark_touch();
+        account.storage.insert(
+            U256::from(1),
+            StorageSlot {
+                original_value: U256::ZERO,
+                present_value: U256::from(2),
+            },
+        );
+        let mut state = State::new();
+        state.insert(B160::from_low_u64_be(1), account);
+
+        let encoded = encode_state(&state);
+        let expected = hex!(
+            "01" // version
+            "00" // no codes
+            "01" // one account
+            "0000000000000000000000000000000000000001" // address
+            "04" // touched
+            "0101" // balance
+            "01" // nonce
+            "00" // empty code hash
+            "00" // no code
+            "01" // one storage slot
+            "0101" // key
+            "00" // original value
+            "0102" // present value
+        );
+        assert_eq!(encoded, expected.to_vec());
+        assert_eq!(decode_state(&encoded), Ok(state));
+    }
+
+    #[test]
+    fn roundtrip_dedups_code() {
+        let code = Bytecode::new_raw(Bytes::from(hex!("6001600101").to_vec()));
+        let info = AccountInfo::new(U256::from(u64::MAX), 300, code.clone());
+
+        let mut state = State::new();
+        for i in 1..=3 {
+            let mut account = Account::from(info.clone());
+            account.mark_created();
+            account
+                .storage
+                .insert(U256::MAX, StorageSlot::new(U256::from(i)));
+            state.insert(B160::from_low_u64_be(i), account);
+        }
+        let mut empty = Account::from(AccountInfo::default());
+        empty.info.code = Some(Bytecode::new());
+        state.insert(B160::from_low_u64_be(4), empty);
+
+        let encoded = encode_state(&state);
+        // code is written only once.
+        assert_eq!(
+            encoded
+                .windows(code.len())
+                .filter(|w| *w == &code.bytes()[..])
+                .count(),
+            1
+        );
+        assert_eq!(decode_state(&encoded), Ok(state));
+
+        assert_eq!(
+            decode_state(&encoded[..encoded.len() - 1]),
+            Err(CompactDecodeError::UnexpectedEof)
+        );
+        let mut trailing = encoded;
+        trailing.push(0);
+        assert_eq!(
+            decode_state(&trailing),
+            Err(CompactDecodeError::TrailingBytes)
+        );
+    }
+}
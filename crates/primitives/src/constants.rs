@@ -11,3 +11,21 @@ pub const MAX_CODE_SIZE: usize = 0x6000;
 ///
 /// Limit of maximum initcode size is 2 * MAX_CODE_SIZE
 pub const MAX_INITCODE_SIZE: usize = 2 * MAX_CODE_SIZE;
+
+/// EIP-4844: Shard Blob Transactions
+pub const GAS_PER_BLOB: u64 = 1 << 17;
+
+/// Target number of the blob per block.
+pub const TARGET_BLOB_NUMBER_PER_BLOCK: u64 = 3;
+
+/// Max number of blobs per block
+pub const MAX_BLOB_NUMBER_PER_BLOCK: u64 = 2 * TARGET_BLOB_NUMBER_PER_BLOCK;
+
+/// Target consumable blob gas per block (for 1559-like pricing).
+pub const TARGET_BLOB_GAS_PER_BLOCK: u64 = TARGET_BLOB_NUMBER_PER_BLOCK * GAS_PER_BLOB;
+
+/// Minimum gas price for data blobs.
+pub const MIN_BLOB_GASPRICE: u64 = 1;
+
+/// Controls the maximum rate of change for blob gas price.
+pub const BLOB_GASPRICE_UPDATE_FRACTION: u64 = 3338477;
@@ -46,6 +46,59 @@ pub trait DatabaseRef {
     fn block_hash(&self, number: U256) -> Result<B256, Self::Error>;
 }
 
+/// Object safe combination of [Database] and [DatabaseCommit].
+///
+/// It is implemented for every type that implements both traits, so the backing database
+/// can be selected at runtime and used as `Box<dyn DatabaseDyn<E>>` (for example as `EVM` database).
+/// Databases with different error types can be unified with [DatabaseMapErr].
+pub trait DatabaseDyn<E>: Database<Error = E> + DatabaseCommit {}
+
+impl<E, T: Database<Error = E> + DatabaseCommit + ?Sized> DatabaseDyn<E> for T {}
+
+/// Wrapper that maps database errors with the given function.
+#[derive(Clone, Debug)]
+pub struct DatabaseMapErr<DB, F> {
+    pub db: DB,
+    pub map_err: F,
+}
+
+impl<DB, F> DatabaseMapErr<DB, F> {
+    pub fn new(db: DB, map_err: F) -> Self {
+        Self { db, map_err }
+    }
+
+    /// Return inner database.
+    pub fn into_inner(self) -> DB {
+        self.db
+    }
+}
+
+impl<DB: Database, E, F: Fn(DB::Error) -> E> Database for DatabaseMapErr<DB, F> {
+    type Error = E;
+
+    fn basic(&mut self, address: B160) -> Result<Option<AccountInfo>, Self::Error> {
+        self.db.basic(address).map_err(&self.map_err)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.db.code_by_hash(code_hash).map_err(&self.map_err)
+    }
+
+    fn storage(&mut self, address: B160, index: U256) -> Result<U256, Self::Error> {
+        self.db.storage(address, index).map_err(&self.map_err)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        self.db.block_hash(number).map_err(&self.map_err)
+    }
+}
+
+impl<DB: DatabaseCommit, F> DatabaseCommit for DatabaseMapErr<DB, F> {
+    fn commit(&mut self, changes: Map<B160, Account>) {
+        self.db.commit(changes)
+    }
+}
+
 pub struct RefDBWrapper<'a, Error> {
     pub db: &'a dyn DatabaseRef<Error = Error>,
 }
@@ -9,6 +9,7 @@ use crate::{
     db::{Database, DatabaseRef},
     AccountInfo, Bytecode, B160, B256, U256,
 };
+use core::fmt;
 
 #[derive(Debug)]
 pub struct DatabaseComponents<S, BH> {
@@ -22,6 +23,29 @@ pub enum DatabaseComponentError<SE, BHE> {
     BlockHash(BHE),
 }
 
+#[cfg(feature = "std")]
+impl<SE, BHE> std::error::Error for DatabaseComponentError<SE, BHE>
+where
+    SE: std::error::Error + 'static,
+    BHE: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::State(e) => Some(e),
+            Self::BlockHash(e) => Some(e),
+        }
+    }
+}
+
+impl<SE: fmt::Display, BHE: fmt::Display> fmt::Display for DatabaseComponentError<SE, BHE> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::State(e) => write!(f, "State error: {e}"),
+            Self::BlockHash(e) => write!(f, "Block hash error: {e}"),
+        }
+    }
+}
+
 impl<S: State, BH: BlockHash> Database for DatabaseComponents<S, BH> {
     type Error = DatabaseComponentError<S::Error, BH::Error>;
 
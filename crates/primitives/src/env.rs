@@ -1,12 +1,13 @@
 use crate::{
-    alloc::vec::Vec, Account, EVMError, InvalidTransaction, Spec, SpecId, B160, B256, KECCAK_EMPTY,
-    MAX_INITCODE_SIZE, U256,
+    alloc::vec::Vec, calc_blob_gasprice, Account, EVMError, HashMap, InvalidTransaction, Spec,
+    SpecId, B160, B256, KECCAK_EMPTY, MAX_INITCODE_SIZE, U256,
 };
 use bytes::Bytes;
 use core::cmp::{min, Ordering};
 
 #[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "camelCase"))]
 pub struct Env {
     pub cfg: CfgEnv,
     pub block: BlockEnv,
@@ -14,41 +15,236 @@ pub struct Env {
 }
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "camelCase"))]
 pub struct BlockEnv {
     pub number: U256,
     /// Coinbase or miner or address that created and signed the block.
     /// Address where we are going to send gas spend
+    #[cfg_attr(feature = "serde", serde(alias = "miner"))]
     pub coinbase: B160,
     pub timestamp: U256,
     /// Difficulty is removed and not used after Paris (aka TheMerge). Value is replaced with prevrandao.
     pub difficulty: U256,
     /// Prevrandao is used after Paris (aka TheMerge) instead of the difficulty value.
     /// NOTE: prevrandao can be found in block in place of mix_hash.
+    #[cfg_attr(feature = "serde", serde(alias = "mixHash"))]
     pub prevrandao: Option<B256>,
     /// basefee is added in EIP1559 London upgrade
+    #[cfg_attr(feature = "serde", serde(alias = "baseFeePerGas"))]
     pub basefee: U256,
     pub gas_limit: U256,
+    /// Excess blob gas and the blob gas price derived from it.
+    /// See also [`calc_blob_gasprice`].
+    ///
+    /// Incorporated as part of the Cancun upgrade via [EIP-4844].
+    ///
+    /// [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
+    pub blob_excess_gas_and_price: Option<BlobExcessGasAndPrice>,
+}
+
+impl BlockEnv {
+    /// Takes `blob_excess_gas` and saves it inside env
+    /// together with the calculated `blob_gasprice`.
+    pub fn set_blob_excess_gas_and_price(&mut self, excess_blob_gas: u64) {
+        self.blob_excess_gas_and_price = Some(BlobExcessGasAndPrice::new(excess_blob_gas));
+    }
+
+    /// See [EIP-4844] and [`calc_blob_gasprice`].
+    ///
+    /// Returns `None` if `Cancun` is not enabled. This is enforced in [`Env::validate_block_env`].
+    ///
+    /// [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
+    #[inline]
+    pub fn get_blob_gasprice(&self) -> Option<u64> {
+        self.blob_excess_gas_and_price
+            .as_ref()
+            .map(|a| a.blob_gasprice)
+    }
+
+    /// Return `blob_excess_gas` header field. See [EIP-4844].
+    ///
+    /// Returns `None` if `Cancun` is not enabled. This is enforced in [`Env::validate_block_env`].
+    ///
+    /// [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
+    #[inline]
+    pub fn get_blob_excess_gas(&self) -> Option<u64> {
+        self.blob_excess_gas_and_price
+            .as_ref()
+            .map(|a| a.excess_blob_gas)
+    }
+}
+
+/// Structure holding block blob excess gas and it calculates blob fee.
+///
+/// Incorporated as part of the Cancun upgrade via [EIP-4844].
+///
+/// [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct BlobExcessGasAndPrice {
+    /// The excess blob gas of the block.
+    #[cfg_attr(feature = "serde", serde(with = "crate::utilities::serde_hex_u64"))]
+    pub excess_blob_gas: u64,
+    /// The calculated blob gas price based on the `excess_blob_gas`, See [calc_blob_gasprice]
+    #[cfg_attr(feature = "serde", serde(with = "crate::utilities::serde_hex_u64"))]
+    pub blob_gasprice: u64,
+}
+
+impl BlobExcessGasAndPrice {
+    /// Creates a new instance by calculating the blob gas price with [`calc_blob_gasprice`].
+    pub fn new(excess_blob_gas: u64) -> Self {
+        let blob_gasprice = calc_blob_gasprice(excess_blob_gas);
+        Self {
+            excess_blob_gas,
+            blob_gasprice,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "camelCase"))]
 pub struct TxEnv {
     /// Caller or Author or tx signer
+    #[cfg_attr(feature = "serde", serde(alias = "from"))]
     pub caller: B160,
+    #[cfg_attr(
+        feature = "serde",
+        serde(alias = "gas", with = "crate::utilities::serde_hex_u64")
+    )]
     pub gas_limit: u64,
+    #[cfg_attr(feature = "serde", serde(alias = "maxFeePerGas"))]
     pub gas_price: U256,
+    #[cfg_attr(feature = "serde", serde(alias = "maxPriorityFeePerGas"))]
     pub gas_priority_fee: Option<U256>,
+    /// Serialized as JSON-RPC `to`, where a missing or `null` value is contract creation.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "to",
+            alias = "transactTo",
+            default = "TransactTo::create",
+            with = "crate::utilities::serde_transact_to"
+        )
+    )]
     pub transact_to: TransactTo,
     pub value: U256,
-    #[cfg_attr(feature = "serde", serde(with = "crate::utilities::serde_hex_bytes"))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(alias = "input", with = "crate::utilities::serde_hex_bytes")
+    )]
     pub data: Bytes,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "crate::utilities::serde_hex_u64_opt")
+    )]
     pub chain_id: Option<u64>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "crate::utilities::serde_hex_u64_opt")
+    )]
     pub nonce: Option<u64>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "crate::utilities::serde_access_list")
+    )]
     pub access_list: Vec<(B160, Vec<U256>)>,
 }
 
+impl TxEnv {
+    /// Resets all fields to their default values.
+    ///
+    /// Useful when the same [TxEnv] is reused for multiple transactions, as setting
+    /// only some fields leaves optional fields like access list from the previous transaction.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Clears access list.
+    #[inline]
+    pub fn clear_access_list(&mut self) {
+        self.access_list.clear();
+    }
+
+    /// Clears EIP-1559 priority fee, making the transaction a legacy one.
+    #[inline]
+    pub fn clear_gas_priority_fee(&mut self) {
+        self.gas_priority_fee = None;
+    }
+
+    /// Clears nonce and chain id, disabling their checks.
+    #[inline]
+    pub fn clear_nonce_and_chain_id(&mut self) {
+        self.nonce = None;
+        self.chain_id = None;
+    }
+
+    /// Sets access list from JSON-RPC formatted access list,
+    /// e.g. `[{"address": "0x..", "storageKeys": ["0x.."]}]`.
+    ///
+    /// Addresses need to be 20 bytes and storage keys 32 bytes long hex strings.
+    #[cfg(feature = "serde")]
+    pub fn with_access_list_json(mut self, json: &str) -> Result<Self, serde_json::Error> {
+        let access_list: AccessList = serde_json::from_str(json)?;
+        self.access_list = access_list.into();
+        Ok(self)
+    }
+}
+
+/// EIP-2930 access list.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct AccessList(pub Vec<AccessListItem>);
+
+/// Address and storage keys that are warm loaded before transaction execution.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct AccessListItem {
+    pub address: B160,
+    pub storage_keys: Vec<B256>,
+}
+
+impl From<AccessList> for Vec<(B160, Vec<U256>)> {
+    fn from(access_list: AccessList) -> Self {
+        access_list
+            .0
+            .into_iter()
+            .map(|item| {
+                let keys = item
+                    .storage_keys
+                    .into_iter()
+                    .map(|key| U256::from_be_bytes(key.0))
+                    .collect();
+                (item.address, keys)
+            })
+            .collect()
+    }
+}
+
+impl From<Vec<(B160, Vec<U256>)>> for AccessList {
+    fn from(access_list: Vec<(B160, Vec<U256>)>) -> Self {
+        Self(
+            access_list
+                .into_iter()
+                .map(|(address, keys)| AccessListItem {
+                    address,
+                    storage_keys: keys
+                        .into_iter()
+                        .map(|key| B256(key.to_be_bytes()))
+                        .collect(),
+                })
+                .collect(),
+        )
+    }
+}
+
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub enum TransactTo {
     Call(B160),
     Create(CreateScheme),
@@ -66,6 +262,7 @@ impl TransactTo {
 /// Create scheme.
 #[derive(Clone, Copy, Eq, PartialEq, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub enum CreateScheme {
     /// Legacy create scheme of `CREATE`.
     Create,
@@ -78,6 +275,7 @@ pub enum CreateScheme {
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "camelCase"))]
 pub struct CfgEnv {
     pub chain_id: U256,
     pub spec_id: SpecId,
@@ -95,6 +293,7 @@ pub struct CfgEnv {
     /// a sane value to prevent memory allocation panics. Defaults to `2^32 - 1` bytes per
     /// EIP-1985.
     #[cfg(feature = "memory_limit")]
+    #[cfg_attr(feature = "serde", serde(with = "crate::utilities::serde_hex_u64"))]
     pub memory_limit: u64,
     /// Skip balance checks if true. Adds transaction cost to balance to ensure execution doesn't fail.
     #[cfg(feature = "optional_balance_check")]
@@ -104,6 +303,11 @@ pub struct CfgEnv {
     /// By default, it is set to `false`.
     #[cfg(feature = "optional_block_gas_limit")]
     pub disable_block_gas_limit: bool,
+    /// EIP-3541 rejects new contract code starting with the 0xEF byte. Some test chains deploy
+    /// such code, which this setting allows.
+    /// By default, it is set to `false`.
+    #[cfg(feature = "optional_eip3541")]
+    pub disable_eip3541: bool,
     /// EIP-3607 rejects transactions from senders with deployed code. In development, it can be desirable to simulate
     /// calls from contracts, which this setting allows.
     /// By default, it is set to `false`.
@@ -114,13 +318,43 @@ pub struct CfgEnv {
     /// By default, it is set to `false`.
     #[cfg(feature = "optional_gas_refund")]
     pub disable_gas_refund: bool,
+    /// Selects EIP-3529 (reduction in refunds) rules independent of `spec_id`. This is useful for
+    /// chains with different refund schedule and for comparing refunds between the two rule sets.
+    /// By default, rules are selected by `spec_id`.
+    #[cfg(feature = "optional_gas_refund")]
+    pub refund_rules: RefundRules,
     /// Disables base fee checks for EIP-1559 transactions.
     /// This is useful for testing method calls with zero gas price.
     #[cfg(feature = "optional_no_base_fee")]
     pub disable_base_fee: bool,
+    /// Enables or disables individual opcodes regardless of the `spec_id` they were introduced in.
+    /// This is useful for chains that activated hardfork opcodes (e.g. PUSH0) at a different time.
+    /// By default, there are no overrides.
+    #[cfg(feature = "optional_opcode_overrides")]
+    pub opcode_overrides: OpcodeOverrides,
+    /// Selects what happens to the balance of an account that selfdestructs with itself as the
+    /// beneficiary. This is useful for chains that keep the balance instead of burning it.
+    /// By default, the balance is handled as defined by the spec.
+    #[cfg(feature = "optional_selfdestruct_to_self")]
+    pub selfdestruct_to_self: SelfdestructToSelf,
+    /// Selects how much gas is consumed by a precompile call that fails with an error. Some chains
+    /// return the remaining gas for errors of their custom precompiles.
+    /// By default, a failed precompile call consumes all gas.
+    #[cfg(feature = "optional_precompile_failure")]
+    pub precompile_failure: PrecompileFailureRules,
 }
 
 impl CfgEnv {
+    #[cfg(feature = "optional_eip3541")]
+    pub fn is_eip3541_disabled(&self) -> bool {
+        self.disable_eip3541
+    }
+
+    #[cfg(not(feature = "optional_eip3541"))]
+    pub fn is_eip3541_disabled(&self) -> bool {
+        false
+    }
+
     #[cfg(feature = "optional_eip3607")]
     pub fn is_eip3607_disabled(&self) -> bool {
         self.disable_eip3607
@@ -151,6 +385,40 @@ impl CfgEnv {
         false
     }
 
+    /// Returns true if EIP-3529 refund rules are used, `spec_enabled` is the value for the current spec.
+    #[cfg(feature = "optional_gas_refund")]
+    pub fn is_eip3529_enabled(&self, spec_enabled: bool) -> bool {
+        match self.refund_rules {
+            RefundRules::Spec => spec_enabled,
+            RefundRules::PreLondon => false,
+            RefundRules::London => true,
+        }
+    }
+
+    /// Returns true if EIP-3529 refund rules are used, `spec_enabled` is the value for the current spec.
+    #[cfg(not(feature = "optional_gas_refund"))]
+    pub fn is_eip3529_enabled(&self, spec_enabled: bool) -> bool {
+        spec_enabled
+    }
+
+    /// Returns true if the balance of an account that selfdestructs to itself is burned,
+    /// `spec_burns` is the value for the current spec.
+    #[cfg(feature = "optional_selfdestruct_to_self")]
+    pub fn is_selfdestruct_to_self_burned(&self, spec_burns: bool) -> bool {
+        match self.selfdestruct_to_self {
+            SelfdestructToSelf::Spec => spec_burns,
+            SelfdestructToSelf::Burn => true,
+            SelfdestructToSelf::Keep => false,
+        }
+    }
+
+    /// Returns true if the balance of an account that selfdestructs to itself is burned,
+    /// `spec_burns` is the value for the current spec.
+    #[cfg(not(feature = "optional_selfdestruct_to_self"))]
+    pub fn is_selfdestruct_to_self_burned(&self, spec_burns: bool) -> bool {
+        spec_burns
+    }
+
     #[cfg(feature = "optional_no_base_fee")]
     pub fn is_base_fee_check_disabled(&self) -> bool {
         self.disable_base_fee
@@ -170,6 +438,164 @@ impl CfgEnv {
     pub fn is_block_gas_limit_disabled(&self) -> bool {
         false
     }
+
+    /// Returns if `opcode` is enabled, `spec_enabled` is used if there is no override for it.
+    #[cfg(feature = "optional_opcode_overrides")]
+    pub fn is_opcode_enabled(&self, opcode: u8, spec_enabled: bool) -> bool {
+        self.opcode_overrides.get(opcode).unwrap_or(spec_enabled)
+    }
+
+    #[cfg(not(feature = "optional_opcode_overrides"))]
+    pub fn is_opcode_enabled(&self, _opcode: u8, spec_enabled: bool) -> bool {
+        spec_enabled
+    }
+
+    /// Returns how a failed call of the precompile at `address` is handled.
+    #[cfg(feature = "optional_precompile_failure")]
+    pub fn precompile_failure(&self, address: &B160) -> PrecompileFailure {
+        self.precompile_failure.get(address)
+    }
+
+    #[cfg(not(feature = "optional_precompile_failure"))]
+    pub fn precompile_failure(&self, _address: &B160) -> PrecompileFailure {
+        PrecompileFailure::ConsumeAllGas
+    }
+}
+
+/// Set of opcodes that are forced to be enabled or disabled, independent of the spec.
+///
+/// Any opcode can be disabled. Enabling only changes opcodes that are activated by a hardfork,
+/// other opcodes are always enabled and opcodes that are not defined stay undefined.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpcodeOverrides {
+    /// Bitmap of opcodes that are always enabled.
+    enabled: [u64; 4],
+    /// Bitmap of opcodes that are always disabled.
+    disabled: [u64; 4],
+}
+
+impl OpcodeOverrides {
+    /// Enable `opcode` even if it is not yet activated by the spec.
+    ///
+    /// It has no effect on opcodes that are not activated by a hardfork.
+    pub fn enable(&mut self, opcode: u8) -> &mut Self {
+        let (i, bit) = Self::position(opcode);
+        self.enabled[i] |= bit;
+        self.disabled[i] &= !bit;
+        self
+    }
+
+    /// Disable `opcode` even if it is activated by the spec.
+    pub fn disable(&mut self, opcode: u8) -> &mut Self {
+        let (i, bit) = Self::position(opcode);
+        self.disabled[i] |= bit;
+        self.enabled[i] &= !bit;
+        self
+    }
+
+    /// Remove override for `opcode` so spec activation is used again.
+    pub fn reset(&mut self, opcode: u8) -> &mut Self {
+        let (i, bit) = Self::position(opcode);
+        self.enabled[i] &= !bit;
+        self.disabled[i] &= !bit;
+        self
+    }
+
+    /// Returns `Some(true)` if opcode is forced enabled, `Some(false)` if it is forced disabled
+    /// and `None` if there is no override.
+    #[inline]
+    pub fn get(&self, opcode: u8) -> Option<bool> {
+        let (i, bit) = Self::position(opcode);
+        if self.enabled[i] & bit != 0 {
+            Some(true)
+        } else if self.disabled[i] & bit != 0 {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if there are no overrides.
+    pub fn is_empty(&self) -> bool {
+        self.enabled == [0; 4] && self.disabled == [0; 4]
+    }
+
+    #[inline(always)]
+    fn position(opcode: u8) -> (usize, u64) {
+        ((opcode / 64) as usize, 1u64 << (opcode % 64))
+    }
+}
+
+/// Gas handling of a precompile call that fails with an error, see `CfgEnv::precompile_failure`.
+#[derive(Clone, Copy, Default, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PrecompileFailure {
+    /// Call halts with `PrecompileError` and all gas given to the call is consumed.
+    #[default]
+    ConsumeAllGas,
+    /// Call reverts with empty output, the cost of the precompile is charged and the rest
+    /// of the gas is returned to the caller.
+    ///
+    /// If the cost is not known, as for custom precompiles or inputs with invalid length,
+    /// the call is handled as with [PrecompileFailure::ConsumeAllGas].
+    ReturnRemainingGas,
+}
+
+/// Precompile failure handling, with overrides for individual precompile addresses.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrecompileFailureRules {
+    /// Handling used for precompiles without an override.
+    pub default: PrecompileFailure,
+    /// Handling of the precompile at the given address.
+    pub overrides: HashMap<B160, PrecompileFailure>,
+}
+
+impl PrecompileFailureRules {
+    /// Sets the handling for the precompile at `address`.
+    pub fn set(&mut self, address: B160, failure: PrecompileFailure) -> &mut Self {
+        self.overrides.insert(address, failure);
+        self
+    }
+
+    /// Remove override for `address` so the default handling is used again.
+    pub fn reset(&mut self, address: &B160) -> &mut Self {
+        self.overrides.remove(address);
+        self
+    }
+
+    /// Returns the handling for the precompile at `address`.
+    pub fn get(&self, address: &B160) -> PrecompileFailure {
+        self.overrides.get(address).copied().unwrap_or(self.default)
+    }
+}
+
+/// Gas refund rules, see `CfgEnv::refund_rules`.
+#[derive(Clone, Copy, Default, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RefundRules {
+    /// Rules are selected by the spec.
+    #[default]
+    Spec,
+    /// Refunds before EIP-3529, max refund is half of the gas used.
+    PreLondon,
+    /// EIP-3529 reduced refunds, max refund is fifth of the gas used.
+    London,
+}
+
+/// Balance handling of selfdestruct with the destroyed account as the beneficiary,
+/// see `CfgEnv::selfdestruct_to_self`.
+#[derive(Clone, Copy, Default, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SelfdestructToSelf {
+    /// Handling is selected by the spec.
+    #[default]
+    Spec,
+    /// Balance is removed from the account and is lost.
+    Burn,
+    /// Account is not destroyed and the balance stays on it.
+    Keep,
 }
 
 #[derive(Clone, Default, Debug, Eq, PartialEq)]
@@ -194,12 +620,22 @@ impl Default for CfgEnv {
             disable_balance_check: false,
             #[cfg(feature = "optional_block_gas_limit")]
             disable_block_gas_limit: false,
+            #[cfg(feature = "optional_eip3541")]
+            disable_eip3541: false,
             #[cfg(feature = "optional_eip3607")]
             disable_eip3607: false,
             #[cfg(feature = "optional_gas_refund")]
             disable_gas_refund: false,
+            #[cfg(feature = "optional_gas_refund")]
+            refund_rules: RefundRules::Spec,
             #[cfg(feature = "optional_no_base_fee")]
             disable_base_fee: false,
+            #[cfg(feature = "optional_opcode_overrides")]
+            opcode_overrides: OpcodeOverrides::default(),
+            #[cfg(feature = "optional_selfdestruct_to_self")]
+            selfdestruct_to_self: SelfdestructToSelf::Spec,
+            #[cfg(feature = "optional_precompile_failure")]
+            precompile_failure: PrecompileFailureRules::default(),
         }
     }
 }
@@ -214,6 +650,7 @@ impl Default for BlockEnv {
             difficulty: U256::ZERO,
             prevrandao: Some(B256::zero()),
             basefee: U256::ZERO,
+            blob_excess_gas_and_price: Some(BlobExcessGasAndPrice::new(0)),
         }
     }
 }
@@ -236,6 +673,12 @@ impl Default for TxEnv {
 }
 
 impl Env {
+    /// Resets transaction to the default one, keeping block and config environment.
+    #[inline]
+    pub fn clear_tx(&mut self) {
+        self.tx.clear();
+    }
+
     pub fn effective_gas_price(&self) -> U256 {
         if self.tx.gas_priority_fee.is_none() {
             self.tx.gas_price
@@ -249,13 +692,17 @@ impl Env {
 
     /// Validate ENV data of the block.
     ///
-    /// It can be skip if you are sure that PREVRANDAO is set.
+    /// It can be skip if you are sure that PREVRANDAO and excess blob gas are set.
     #[inline]
     pub fn validate_block_env<SPEC: Spec, T>(&self) -> Result<(), EVMError<T>> {
         // Prevrandao is required for merge
         if SPEC::enabled(SpecId::MERGE) && self.block.prevrandao.is_none() {
             return Err(EVMError::PrevrandaoNotSet);
         }
+        // `excess_blob_gas` is required for Cancun
+        if SPEC::enabled(SpecId::CANCUN) && self.block.blob_excess_gas_and_price.is_none() {
+            return Err(EVMError::ExcessBlobGasNotSet);
+        }
         Ok(())
     }
 
@@ -268,6 +715,14 @@ impl Env {
         let effective_gas_price = self.effective_gas_price();
         let is_create = self.tx.transact_to.is_create();
 
+        // Fields that are not supported by the spec are usually left from the previous transaction.
+        if !SPEC::enabled(SpecId::LONDON) && self.tx.gas_priority_fee.is_some() {
+            return Err(InvalidTransaction::PriorityFeeNotSupported);
+        }
+        if !SPEC::enabled(SpecId::SPURIOUS_DRAGON) && self.tx.chain_id.is_some() {
+            return Err(InvalidTransaction::ChainIdNotSupported);
+        }
+
         // BASEFEE tx check
         if SPEC::enabled(SpecId::LONDON) {
             if let Some(priority_fee) = self.tx.gas_priority_fee {
@@ -350,3 +805,215 @@ impl Env {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_rpc_names() {
+        let tx: TxEnv = serde_json::from_str(
+            r#"{
+                "from": "0x0000000000000000000000000000000000000001",
+                "gas": "0x5208",
+                "gasPrice": "0x3b9aca00",
+                "input": "0x1234",
+                "nonce": "0x2",
+                "chainId": 1,
+                "to": "0x0000000000000000000000000000000000000003"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(tx.caller, B160::from_low_u64_be(1));
+        assert!(matches!(tx.transact_to, TransactTo::Call(to) if to == B160::from_low_u64_be(3)));
+        assert_eq!(tx.gas_limit, 21000);
+        assert_eq!(tx.gas_price, U256::from(1_000_000_000));
+        assert_eq!(tx.data, Bytes::from_static(&[0x12, 0x34]));
+        assert_eq!(tx.nonce, Some(2));
+        assert_eq!(tx.chain_id, Some(1));
+        assert_eq!(tx.value, U256::ZERO);
+
+        let json = serde_json::to_value(&tx).unwrap();
+        assert_eq!(json["gasLimit"], "0x5208");
+        assert_eq!(json["nonce"], "0x2");
+        assert_eq!(json["to"], "0x0000000000000000000000000000000000000003");
+
+        // missing or null `to` is contract creation.
+        let tx: TxEnv = serde_json::from_str(r#"{"maxFeePerGas": "0x2"}"#).unwrap();
+        assert!(tx.transact_to.is_create());
+        assert_eq!(tx.gas_price, U256::from(2));
+        let tx: TxEnv = serde_json::from_str(r#"{"to": null}"#).unwrap();
+        assert!(tx.transact_to.is_create());
+        assert!(serde_json::to_value(&tx).unwrap()["to"].is_null());
+
+        let tx = TxEnv {
+            access_list: vec![
+                (B160::from_low_u64_be(2), vec![U256::from(3), U256::MAX]),
+                (B160::from_low_u64_be(4), vec![]),
+            ],
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&tx).unwrap();
+        assert_eq!(
+            json["accessList"][0]["address"],
+            "0x0000000000000000000000000000000000000002"
+        );
+        assert_eq!(
+            json["accessList"][0]["storageKeys"][1],
+            "0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"
+        );
+        let decoded: TxEnv = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.access_list, tx.access_list);
+
+        let env = Env::default();
+        let json = serde_json::to_string(&env).unwrap();
+        let decoded: Env = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.block, env.block);
+        assert_eq!(decoded.cfg, env.cfg);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn access_list_json() {
+        let access_list = r#"[{
+            "address": "0x0000000000000000000000000000000000000002",
+            "storageKeys": [
+                "0x0000000000000000000000000000000000000000000000000000000000000003"
+            ]
+        }]"#;
+        let tx = TxEnv::default().with_access_list_json(access_list).unwrap();
+        assert_eq!(
+            tx.access_list,
+            vec![(B160::from_low_u64_be(2), vec![U256::from(3)])]
+        );
+
+        // same access list as part of the JSON-RPC transaction.
+        let rpc_tx: TxEnv =
+            serde_json::from_str(&format!(r#"{{"accessList": {access_list}}}"#)).unwrap();
+        assert_eq!(rpc_tx.access_list, tx.access_list);
+        assert_eq!(
+            serde_json::to_value(&rpc_tx).unwrap()["accessList"],
+            serde_json::from_str::<serde_json::Value>(access_list).unwrap()
+        );
+        // storage key that is not 32 bytes is rejected.
+        assert!(TxEnv::default()
+            .with_access_list_json(
+                r#"[{"address": "0x0000000000000000000000000000000000000002", "storageKeys": ["0x03"]}]"#
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn clear_tx() {
+        let mut env = Env::default();
+        env.block.number = U256::from(1);
+        env.tx.gas_priority_fee = Some(U256::from(1));
+        env.tx.nonce = Some(1);
+        env.tx.chain_id = Some(1);
+        env.tx.access_list = vec![(B160::zero(), vec![U256::ZERO])];
+
+        env.tx.clear_access_list();
+        assert!(env.tx.access_list.is_empty());
+        env.tx.clear_gas_priority_fee();
+        assert_eq!(env.tx.gas_priority_fee, None);
+        env.tx.clear_nonce_and_chain_id();
+        assert_eq!((env.tx.nonce, env.tx.chain_id), (None, None));
+
+        env.tx.data = Bytes::from_static(&[1]);
+        env.tx.nonce = Some(2);
+        env.clear_tx();
+        assert!(env.tx.data.is_empty());
+        assert_eq!(env.tx.nonce, None);
+        assert_eq!(env.block.number, U256::from(1));
+    }
+
+    #[test]
+    fn unsupported_tx_field() {
+        let mut env = Env::default();
+        env.tx.gas_price = U256::from(1);
+        env.tx.gas_priority_fee = Some(U256::from(1));
+        env.tx.chain_id = Some(1);
+        assert_eq!(env.validate_tx::<crate::LatestSpec>(), Ok(()));
+
+        assert_eq!(
+            env.validate_tx::<crate::BerlinSpec>(),
+            Err(InvalidTransaction::PriorityFeeNotSupported)
+        );
+        env.tx.clear_gas_priority_fee();
+        assert_eq!(env.validate_tx::<crate::BerlinSpec>(), Ok(()));
+
+        assert_eq!(
+            env.validate_tx::<crate::HomesteadSpec>(),
+            Err(InvalidTransaction::ChainIdNotSupported)
+        );
+        env.clear_tx();
+        assert_eq!(env.validate_tx::<crate::HomesteadSpec>(), Ok(()));
+    }
+
+    #[cfg(feature = "optional_gas_refund")]
+    #[test]
+    fn refund_rules() {
+        let mut cfg = CfgEnv::default();
+        assert!(cfg.is_eip3529_enabled(true));
+        assert!(!cfg.is_eip3529_enabled(false));
+
+        cfg.refund_rules = RefundRules::PreLondon;
+        assert!(!cfg.is_eip3529_enabled(true));
+
+        cfg.refund_rules = RefundRules::London;
+        assert!(cfg.is_eip3529_enabled(false));
+    }
+
+    #[cfg(feature = "optional_selfdestruct_to_self")]
+    #[test]
+    fn selfdestruct_to_self() {
+        let mut cfg = CfgEnv::default();
+        assert!(cfg.is_selfdestruct_to_self_burned(true));
+        assert!(!cfg.is_selfdestruct_to_self_burned(false));
+
+        cfg.selfdestruct_to_self = SelfdestructToSelf::Burn;
+        assert!(cfg.is_selfdestruct_to_self_burned(false));
+
+        cfg.selfdestruct_to_self = SelfdestructToSelf::Keep;
+        assert!(!cfg.is_selfdestruct_to_self_burned(true));
+    }
+
+    #[test]
+    fn precompile_failure_rules() {
+        let custom = B160::from_low_u64_be(0x100);
+        let mut rules = PrecompileFailureRules::default();
+        assert_eq!(rules.get(&custom), PrecompileFailure::ConsumeAllGas);
+
+        rules.set(custom, PrecompileFailure::ReturnRemainingGas);
+        assert_eq!(rules.get(&custom), PrecompileFailure::ReturnRemainingGas);
+        assert_eq!(
+            rules.get(&B160::from_low_u64_be(1)),
+            PrecompileFailure::ConsumeAllGas
+        );
+
+        rules.default = PrecompileFailure::ReturnRemainingGas;
+        rules.set(custom, PrecompileFailure::ConsumeAllGas);
+        assert_eq!(rules.get(&custom), PrecompileFailure::ConsumeAllGas);
+        rules.reset(&custom);
+        assert_eq!(rules.get(&custom), PrecompileFailure::ReturnRemainingGas);
+    }
+
+    #[test]
+    fn opcode_overrides() {
+        let mut overrides = OpcodeOverrides::default();
+        assert!(overrides.is_empty());
+        assert_eq!(overrides.get(0x5f), None);
+
+        overrides.enable(0x5f).disable(0xff);
+        assert_eq!(overrides.get(0x5f), Some(true));
+        assert_eq!(overrides.get(0xff), Some(false));
+        assert_eq!(overrides.get(0x00), None);
+
+        overrides.disable(0x5f);
+        assert_eq!(overrides.get(0x5f), Some(false));
+
+        overrides.reset(0x5f).reset(0xff);
+        assert!(overrides.is_empty());
+    }
+}
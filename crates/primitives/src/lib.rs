@@ -2,6 +2,8 @@
 
 pub mod bits;
 pub mod bytecode;
+#[cfg(feature = "compact")]
+pub mod compact;
 pub mod constants;
 pub mod db;
 pub mod env;
@@ -10,10 +12,15 @@ pub mod precompile;
 pub mod result;
 pub mod specification;
 pub mod state;
+pub mod state_diff;
 pub mod utilities;
 
 extern crate alloc;
 
+/// Types of the alloy ecosystem. [U256] is the same type as `alloy_primitives::U256` and
+/// [B160] and [B256] convert from and into `alloy_primitives::Address` and `alloy_primitives::B256`.
+#[cfg(feature = "alloy")]
+pub use alloy_primitives;
 pub use bits::B160;
 pub use bits::B256;
 pub use bytes;
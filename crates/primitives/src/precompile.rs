@@ -1,4 +1,5 @@
 use alloc::vec::Vec;
+use core::fmt;
 
 /// A precompile operation result.
 pub type PrecompileResult = Result<(u64, Vec<u8>), PrecompileError>;
@@ -21,4 +22,34 @@ pub enum PrecompileError {
     Bn128FieldPointNotAMember,
     Bn128AffineGFailedToCreate,
     Bn128PairLength,
+    /// Precompile is excluded from the build by its `no_*` cargo feature.
+    Disabled,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PrecompileError {}
+
+impl fmt::Display for PrecompileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrecompileError::OutOfGas => f.write_str("Out of gas"),
+            PrecompileError::Blake2WrongLength => f.write_str("Wrong input length for blake2"),
+            PrecompileError::Blake2WrongFinalIndicatorFlag => {
+                f.write_str("Wrong final indicator flag for blake2")
+            }
+            PrecompileError::ModexpExpOverflow => f.write_str("Modexp exponent overflow"),
+            PrecompileError::ModexpBaseOverflow => f.write_str("Modexp base overflow"),
+            PrecompileError::ModexpModOverflow => f.write_str("Modexp modulus overflow"),
+            PrecompileError::Bn128FieldPointNotAMember => {
+                f.write_str("Field point is not a member of bn128 curve")
+            }
+            PrecompileError::Bn128AffineGFailedToCreate => {
+                f.write_str("Failed to create bn128 affine point")
+            }
+            PrecompileError::Bn128PairLength => f.write_str("Wrong input length for bn128 pairing"),
+            PrecompileError::Disabled => {
+                f.write_str("Precompile is disabled, enable its feature to use it")
+            }
+        }
+    }
 }
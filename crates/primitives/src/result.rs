@@ -13,6 +13,111 @@ pub struct ResultAndState {
     pub result: ExecutionResult,
     /// State that got updated
     pub state: State,
+    /// Number of database calls done during execution
+    pub db_reads: DatabaseReadCounts,
+    /// Gas used by the execution, grouped by the kind of work.
+    #[cfg(feature = "gas_by_category")]
+    pub gas_by_category: GasByCategory,
+    /// Last state change that was attempted inside a static call.
+    pub static_call_violation: Option<StaticCallViolation>,
+}
+
+/// State change that was attempted inside a static call and halted the frame with
+/// [Halt::StateChangeDuringStaticCall] or [Halt::CallNotAllowedInsideStatic].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StaticCallViolation {
+    /// `SSTORE`, `TSTORE`, `LOG*`, `CREATE*`, `SELFDESTRUCT` or `CALL` with value.
+    pub opcode: u8,
+    /// Depth of the halted frame, top level call has depth 1.
+    pub depth: u64,
+    /// Address of the contract that was executed.
+    pub address: B160,
+}
+
+/// Number of calls done to each [`crate::db::Database`] function during a transaction.
+///
+/// Only calls that reached the database are counted, values cached in journal are not.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DatabaseReadCounts {
+    /// Number of `basic` calls.
+    pub basic: u64,
+    /// Number of `storage` calls.
+    pub storage: u64,
+    /// Number of `code_by_hash` calls.
+    pub code_by_hash: u64,
+    /// Number of `block_hash` calls.
+    pub block_hash: u64,
+}
+
+impl DatabaseReadCounts {
+    /// Total number of database calls.
+    pub fn total(&self) -> u64 {
+        self.basic + self.storage + self.code_by_hash + self.block_hash
+    }
+}
+
+/// Kind of work the gas is used for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GasCategory {
+    /// Arithmetic, stack, control flow, environment and all other opcodes.
+    Compute,
+    /// Memory opcodes and opcodes that copy data to memory.
+    Memory,
+    /// `SLOAD` opcode.
+    StorageRead,
+    /// `SSTORE` opcode.
+    StorageWrite,
+    /// Call opcodes, without the gas used by the called contract.
+    Call,
+    /// Create opcodes, without the gas used by the init code.
+    Create,
+    /// Gas used by precompiles.
+    Precompile,
+}
+
+/// Gas used by the execution of a transaction, grouped by [GasCategory].
+///
+/// Intrinsic gas and refunds are not included.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GasByCategory {
+    pub compute: u64,
+    pub memory: u64,
+    pub storage_reads: u64,
+    pub storage_writes: u64,
+    pub calls: u64,
+    pub creates: u64,
+    pub precompiles: u64,
+}
+
+impl GasByCategory {
+    /// Adds gas to the category.
+    pub fn record(&mut self, category: GasCategory, gas: u64) {
+        let counter = match category {
+            GasCategory::Compute => &mut self.compute,
+            GasCategory::Memory => &mut self.memory,
+            GasCategory::StorageRead => &mut self.storage_reads,
+            GasCategory::StorageWrite => &mut self.storage_writes,
+            GasCategory::Call => &mut self.calls,
+            GasCategory::Create => &mut self.creates,
+            GasCategory::Precompile => &mut self.precompiles,
+        };
+        *counter = counter.saturating_add(gas);
+    }
+
+    /// Total gas of all categories.
+    pub fn total(&self) -> u64 {
+        self.compute
+            + self.memory
+            + self.storage_reads
+            + self.storage_writes
+            + self.calls
+            + self.creates
+            + self.precompiles
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -23,16 +128,31 @@ pub enum ExecutionResult {
         reason: Eval,
         gas_used: u64,
         gas_refunded: u64,
+        /// Price paid per unit of gas, see [`crate::Env::effective_gas_price`].
+        effective_gas_price: U256,
+        /// Total amount deducted from the caller, `gas_used * effective_gas_price`.
+        fee_paid: U256,
         logs: Vec<Log>,
         output: Output,
     },
     /// Reverted by `REVERT` opcode that doesn't spend all gas.
-    Revert { gas_used: u64, output: Bytes },
+    Revert {
+        gas_used: u64,
+        /// Price paid per unit of gas, see [`crate::Env::effective_gas_price`].
+        effective_gas_price: U256,
+        /// Total amount deducted from the caller, `gas_used * effective_gas_price`.
+        fee_paid: U256,
+        output: Bytes,
+    },
     /// Reverted for various reasons and spend all gas.
     Halt {
         reason: Halt,
         /// Halting will spend all the gas, and will be equal to gas_limit.
         gas_used: u64,
+        /// Price paid per unit of gas, see [`crate::Env::effective_gas_price`].
+        effective_gas_price: U256,
+        /// Total amount deducted from the caller, `gas_used * effective_gas_price`.
+        fee_paid: U256,
     },
 }
 
@@ -89,6 +209,43 @@ impl ExecutionResult {
 
         *gas_used
     }
+
+    /// Returns the gas refunded to the caller.
+    ///
+    /// Refunds are only applied on success, so this is zero for reverts and halts.
+    pub fn gas_refunded(&self) -> u64 {
+        match self {
+            Self::Success { gas_refunded, .. } => *gas_refunded,
+            _ => 0,
+        }
+    }
+
+    /// Returns the price paid per unit of gas.
+    pub fn effective_gas_price(&self) -> U256 {
+        let (Self::Success {
+            effective_gas_price,
+            ..
+        }
+        | Self::Revert {
+            effective_gas_price,
+            ..
+        }
+        | Self::Halt {
+            effective_gas_price,
+            ..
+        }) = self;
+
+        *effective_gas_price
+    }
+
+    /// Returns the total fee paid by the caller for this execution.
+    pub fn fee_paid(&self) -> U256 {
+        let (Self::Success { fee_paid, .. }
+        | Self::Revert { fee_paid, .. }
+        | Self::Halt { fee_paid, .. }) = self;
+
+        *fee_paid
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -126,7 +283,13 @@ pub enum EVMError<DBError> {
     Transaction(InvalidTransaction),
     /// REVM specific and related to environment.
     PrevrandaoNotSet,
+    /// `excess_blob_gas` is not set for Cancun and above.
+    ExcessBlobGasNotSet,
+    /// Database is not set on the EVM.
+    DatabaseNotSet,
     Database(DBError),
+    /// EVM reached a state that should not be possible.
+    Internal(InternalError),
 }
 
 #[cfg(feature = "std")]
@@ -138,9 +301,12 @@ where
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            EVMError::Transaction(v) => write!(f, "Transaction error: {:?}", v),
+            EVMError::Transaction(v) => write!(f, "Transaction error: {}", v),
             EVMError::PrevrandaoNotSet => f.write_str("Prevrandao not set"),
+            EVMError::ExcessBlobGasNotSet => f.write_str("Excess blob gas not set"),
+            EVMError::DatabaseNotSet => f.write_str("Database not set"),
             EVMError::Database(v) => write!(f, "Database error: {}", v),
+            EVMError::Internal(v) => write!(f, "Internal error: {}", v),
         }
     }
 }
@@ -151,6 +317,35 @@ impl<DBError> From<InvalidTransaction> for EVMError<DBError> {
     }
 }
 
+/// Broken invariants of the EVM, they are returned as errors instead of panicking.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InternalError {
+    /// Execution stopped with fatal external error, but database did not return an error.
+    FatalExternalError,
+    /// Instruction result that is used only inside of the interpreter was returned.
+    InternalContinue,
+    /// Account is used before it is loaded.
+    AccountNotLoaded(B160),
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InternalError {}
+
+impl fmt::Display for InternalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InternalError::FatalExternalError => {
+                f.write_str("Fatal external error without database error")
+            }
+            InternalError::InternalContinue => f.write_str("Internal instruction result returned"),
+            InternalError::AccountNotLoaded(address) => {
+                write!(f, "Account {address:?} is not loaded")
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InvalidTransaction {
@@ -183,6 +378,82 @@ pub enum InvalidTransaction {
     /// Access list is not supported is not supported
     /// for blocks before Berlin hardfork.
     AccessListNotSupported,
+    /// EIP-1559 priority fee is not supported for blocks before London hardfork.
+    ///
+    /// Usually left from the previous transaction when the environment is reused
+    /// without [crate::Env::clear_tx].
+    PriorityFeeNotSupported,
+    /// EIP-155 chain id is not supported for blocks before Spurious Dragon hardfork.
+    ///
+    /// Usually left from the previous transaction when the environment is reused
+    /// without [crate::Env::clear_tx].
+    ChainIdNotSupported,
+    /// Transaction gas limit is more than the gas that is left in the block.
+    /// Only returned in block building mode.
+    BlockGasExceeded {
+        gas_limit: u64,
+        remaining: u64,
+    },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidTransaction {}
+
+impl fmt::Display for InvalidTransaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidTransaction::GasMaxFeeGreaterThanPriorityFee => {
+                f.write_str("Priority fee is greater than max fee")
+            }
+            InvalidTransaction::GasPriceLessThanBasefee => {
+                f.write_str("Gas price is less than basefee")
+            }
+            InvalidTransaction::CallerGasLimitMoreThanBlock => {
+                f.write_str("Transaction gas limit is more than block gas limit")
+            }
+            InvalidTransaction::CallGasCostMoreThanGasLimit => {
+                f.write_str("Initial gas cost is more than gas limit")
+            }
+            InvalidTransaction::RejectCallerWithCode => {
+                f.write_str("Transaction caller has deployed code")
+            }
+            InvalidTransaction::LackOfFundForMaxFee { fee, balance } => {
+                write!(f, "Lack of funds ({balance}) for max fee ({fee})")
+            }
+            InvalidTransaction::OverflowPaymentInTransaction => {
+                f.write_str("Overflow payment in transaction")
+            }
+            InvalidTransaction::NonceOverflowInTransaction => {
+                f.write_str("Nonce overflow in transaction")
+            }
+            InvalidTransaction::NonceTooHigh { tx, state } => {
+                write!(f, "Nonce too high: tx nonce {tx}, state nonce {state}")
+            }
+            InvalidTransaction::NonceTooLow { tx, state } => {
+                write!(f, "Nonce too low: tx nonce {tx}, state nonce {state}")
+            }
+            InvalidTransaction::CreateInitcodeSizeLimit => {
+                f.write_str("Initcode size limit exceeded")
+            }
+            InvalidTransaction::InvalidChainId => f.write_str("Invalid chain id"),
+            InvalidTransaction::AccessListNotSupported => {
+                f.write_str("Access list is not supported before Berlin")
+            }
+            InvalidTransaction::PriorityFeeNotSupported => {
+                f.write_str("Priority fee is not supported before London")
+            }
+            InvalidTransaction::ChainIdNotSupported => {
+                f.write_str("Chain id is not supported before Spurious Dragon")
+            }
+            InvalidTransaction::BlockGasExceeded {
+                gas_limit,
+                remaining,
+            } => write!(
+                f,
+                "Transaction gas limit ({gas_limit}) is more than remaining block gas ({remaining})"
+            ),
+        }
+    }
 }
 
 /// When transaction return successfully without halts.
@@ -216,6 +487,10 @@ pub enum Halt {
     CreateContractStartingWithEF,
     /// EIP-3860: Limit and meter initcode. Initcode size limit exceeded.
     CreateInitcodeSizeLimit,
+    /// Caller or called address is denied by the address policy.
+    AddressDenied,
+    /// Called precompile is excluded from the build by its cargo feature.
+    PrecompileDisabled,
 
     /* Internal Halts that can be only found inside Inspector */
     OverflowPayment,
@@ -225,6 +500,37 @@ pub enum Halt {
     CallTooDeep,
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for Halt {}
+
+impl fmt::Display for Halt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Halt::OutOfGas(e) => write!(f, "Out of gas: {e}"),
+            Halt::OpcodeNotFound => f.write_str("Opcode not found"),
+            Halt::InvalidFEOpcode => f.write_str("Invalid 0xFE opcode"),
+            Halt::InvalidJump => f.write_str("Invalid jump destination"),
+            Halt::NotActivated => f.write_str("Feature or opcode not activated"),
+            Halt::StackUnderflow => f.write_str("Stack underflow"),
+            Halt::StackOverflow => f.write_str("Stack overflow"),
+            Halt::OutOfOffset => f.write_str("Out of offset"),
+            Halt::CreateCollision => f.write_str("Create collision"),
+            Halt::PrecompileError => f.write_str("Precompile error"),
+            Halt::NonceOverflow => f.write_str("Nonce overflow"),
+            Halt::CreateContractSizeLimit => f.write_str("Create contract size limit"),
+            Halt::CreateContractStartingWithEF => f.write_str("Created contract starts with 0xEF"),
+            Halt::CreateInitcodeSizeLimit => f.write_str("Initcode size limit exceeded"),
+            Halt::AddressDenied => f.write_str("Address denied by policy"),
+            Halt::PrecompileDisabled => f.write_str("Precompile is disabled"),
+            Halt::OverflowPayment => f.write_str("Overflow payment"),
+            Halt::StateChangeDuringStaticCall => f.write_str("State change during static call"),
+            Halt::CallNotAllowedInsideStatic => f.write_str("Call not allowed inside static"),
+            Halt::OutOfFund => f.write_str("Out of funds"),
+            Halt::CallTooDeep => f.write_str("Call too deep"),
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OutOfGasError {
@@ -240,3 +546,18 @@ pub enum OutOfGasError {
     // i.e. in `as_usize_or_fail`
     InvalidOperand,
 }
+
+#[cfg(feature = "std")]
+impl std::error::Error for OutOfGasError {}
+
+impl fmt::Display for OutOfGasError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutOfGasError::BasicOutOfGas => f.write_str("basic"),
+            OutOfGasError::MemoryLimit => f.write_str("memory limit"),
+            OutOfGasError::Memory => f.write_str("memory expansion"),
+            OutOfGasError::Precompile => f.write_str("precompile"),
+            OutOfGasError::InvalidOperand => f.write_str("invalid operand"),
+        }
+    }
+}
@@ -0,0 +1,211 @@
+//! Canonical RLP encoding of accounts, storage entries and [State] changesets.
+//!
+//! Encodings are deterministic: accounts are sorted by address and storage entries by key,
+//! so nodes exchanging state diffs produce byte-identical outputs for the same changes.
+
+use crate::{AccountInfo, Bytes, State, B160, B256, U256};
+use alloc::vec::Vec;
+use rlp::RlpStream;
+
+/// RLP encoding of account info as `[nonce, balance, code_hash]`.
+pub fn encode_account_info(info: &AccountInfo) -> Vec<u8> {
+    let mut stream = RlpStream::new();
+    append_account_info(&mut stream, info);
+    stream.out().to_vec()
+}
+
+/// RLP encoding of account as it is stored in the state trie,
+/// `[nonce, balance, storage_root, code_hash]`.
+pub fn encode_trie_account(info: &AccountInfo, storage_root: B256) -> Vec<u8> {
+    let mut stream = RlpStream::new_list(4);
+    stream.append(&info.nonce);
+    stream.append(&info.balance);
+    stream.append(&storage_root.as_bytes());
+    stream.append(&info.code_hash.as_bytes());
+    stream.out().to_vec()
+}
+
+/// RLP encoding of storage entry as `[key, value]`.
+pub fn encode_storage_entry(key: U256, value: U256) -> Vec<u8> {
+    let mut stream = RlpStream::new_list(2);
+    stream.append(&key).append(&value);
+    stream.out().to_vec()
+}
+
+/// Status of the changeset account that was created by the transaction.
+pub const CHANGESET_CREATED: u8 = 1;
+/// Status of the changeset account that was selfdestructed by the transaction.
+pub const CHANGESET_DESTROYED: u8 = 2;
+
+/// RLP encoding of changes that transaction made to the state.
+///
+/// Only touched accounts and changed storage slots are included. Encoding is a list of
+/// `[address, [nonce, balance, code_hash], status, code, [[key, present_value], ..]]`
+/// sorted by address, with storage entries sorted by key.
+///
+/// `status` combines [CHANGESET_CREATED] and [CHANGESET_DESTROYED] flags. `code` is the
+/// bytecode of created accounts, it is empty for other accounts as their code is unchanged.
+pub fn encode_changeset(state: &State) -> Vec<u8> {
+    let mut accounts: Vec<_> = state
+        .iter()
+        .filter(|(_, account)| account.is_touched())
+        .collect();
+    accounts.sort_unstable_by_key(|(address, _)| **address);
+
+    let mut stream = RlpStream::new_list(accounts.len());
+    for (address, account) in accounts {
+        let mut storage: Vec<_> = account
+            .storage
+            .iter()
+            .filter(|(_, slot)| slot.is_changed())
+            .collect();
+        storage.sort_unstable_by_key(|(key, _)| **key);
+
+        let mut status = 0;
+        if account.is_newly_created() {
+            status |= CHANGESET_CREATED;
+        }
+        if account.is_selfdestructed() {
+            status |= CHANGESET_DESTROYED;
+        }
+        let code = match &account.info.code {
+            Some(code) if account.is_newly_created() => code.original_bytes(),
+            _ => Bytes::new(),
+        };
+
+        stream.begin_list(5);
+        append_address(&mut stream, address);
+        append_account_info(&mut stream, &account.info);
+        stream.append(&status);
+        stream.append(&code.as_ref());
+        stream.begin_list(storage.len());
+        for (key, slot) in storage {
+            stream.begin_list(2);
+            stream.append(key).append(&slot.present_value);
+        }
+    }
+    stream.out().to_vec()
+}
+
+fn append_address(stream: &mut RlpStream, address: &B160) {
+    stream.append(&address.as_bytes());
+}
+
+fn append_account_info(stream: &mut RlpStream, info: &AccountInfo) {
+    stream.begin_list(3);
+    stream.append(&info.nonce);
+    stream.append(&info.balance);
+    stream.append(&info.code_hash.as_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Account, Bytecode, StorageSlot, KECCAK_EMPTY};
+    use hex_literal::hex;
+
+    const EMPTY_ROOT: B256 = B256(hex!(
+        "56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+    ));
+
+    #[test]
+    fn account_encoding() {
+        let info = AccountInfo {
+            nonce: 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            encode_account_info(&info),
+            [&hex!("e30180a0")[..], KECCAK_EMPTY.as_bytes()].concat()
+        );
+
+        let empty = AccountInfo::default();
+        assert_eq!(
+            encode_trie_account(&empty, EMPTY_ROOT),
+            [
+                &hex!("f8448080a0")[..],
+                EMPTY_ROOT.as_bytes(),
+                &hex!("a0")[..],
+                KECCAK_EMPTY.as_bytes()
+            ]
+            .concat()
+        );
+
+        assert_eq!(
+            encode_storage_entry(U256::from(1), U256::ZERO),
+            hex!("c20180").to_vec()
+        );
+    }
+
+    #[test]
+    fn changeset_is_canonical() {
+        let mut state = State::new();
+        for i in [3u64, 1, 2] {
+            let mut account = Account::from(AccountInfo {
+                nonce: i,
+                ..Default::default()
+            });
+            // account 2 is only loaded, it should be skipped.
+            if i != 2 {
+                account.mark_touch();
+            }
+            account
+                .storage
+                .insert(U256::from(10 - i), StorageSlot::new(U256::from(1)));
+            account.storage.insert(
+                U256::from(i),
+                StorageSlot {
+                    original_value: U256::ZERO,
+                    present_value: U256::from(i),
+                },
+            );
+            state.insert(B160::from_low_u64_be(i), account);
+        }
+
+        let encoded = encode_changeset(&state);
+        let mut other = State::new();
+        let mut accounts: Vec<_> = state.into_iter().collect();
+        accounts.reverse();
+        for (address, account) in accounts {
+            other.insert(address, account);
+        }
+        assert_eq!(encode_changeset(&other), encoded);
+
+        let rlp = rlp::Rlp::new(&encoded);
+        assert_eq!(rlp.item_count(), Ok(2));
+        let first = rlp.at(0).unwrap();
+        assert_eq!(
+            first.at(0).unwrap().data().unwrap(),
+            B160::from_low_u64_be(1).as_bytes()
+        );
+        assert_eq!(first.val_at::<u8>(2), Ok(0));
+        assert_eq!(first.at(3).unwrap().data(), Ok(&[][..]));
+        // only changed slot is encoded.
+        assert_eq!(first.at(4).unwrap().item_count(), Ok(1));
+    }
+
+    #[test]
+    fn changeset_status_and_code() {
+        let code = Bytecode::new_raw(Bytes::from_static(&[0x60, 0x00]));
+        let mut created = Account::from(AccountInfo::new(U256::ZERO, 1, code.clone()));
+        created.mark_touch();
+        created.mark_created();
+        let mut destroyed = Account::from(AccountInfo::new(U256::ZERO, 1, code));
+        destroyed.mark_touch();
+        destroyed.mark_selfdestruct();
+
+        let mut state = State::new();
+        state.insert(B160::from_low_u64_be(1), created);
+        state.insert(B160::from_low_u64_be(2), destroyed);
+        let encoded = encode_changeset(&state);
+        let rlp = rlp::Rlp::new(&encoded);
+
+        let created = rlp.at(0).unwrap();
+        assert_eq!(created.val_at::<u8>(2), Ok(CHANGESET_CREATED));
+        assert_eq!(created.at(3).unwrap().data(), Ok(&[0x60, 0x00][..]));
+        // code of existing account is not changed by the transaction.
+        let destroyed = rlp.at(1).unwrap();
+        assert_eq!(destroyed.val_at::<u8>(2), Ok(CHANGESET_DESTROYED));
+        assert_eq!(destroyed.at(3).unwrap().data(), Ok(&[][..]));
+    }
+}
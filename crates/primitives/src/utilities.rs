@@ -1,4 +1,4 @@
-use crate::{B160, B256, U256};
+use crate::{B160, B256, BLOB_GASPRICE_UPDATE_FRACTION, MIN_BLOB_GASPRICE, U256};
 use hex_literal::hex;
 use sha3::{Digest, Keccak256};
 
@@ -31,6 +31,68 @@ pub fn create2_address(caller: B160, code_hash: B256, salt: U256) -> B160 {
     B160(hasher.finalize().as_slice()[12..].try_into().unwrap())
 }
 
+/// Returns the storage slot of the value for `key` in the Solidity `mapping` at slot `base`,
+/// `keccak256(key . base)`.
+///
+/// Value type keys (`address`, `uint256`, `bytes32`, ..) are left padded to 32 bytes,
+/// [B160] can be converted into such key with `B256::from(address)`.
+pub fn map_slot(base: U256, key: B256) -> U256 {
+    let mut input = [0u8; 64];
+    input[..32].copy_from_slice(&key[..]);
+    input[32..].copy_from_slice(&base.to_be_bytes::<{ U256::BYTES }>());
+    U256::from_be_bytes(keccak256(&input).0)
+}
+
+/// Returns the storage slot of the element at `index` of the Solidity dynamic array at slot
+/// `base`, `keccak256(base) + index`. Elements are assumed to take one slot each.
+pub fn array_slot(base: U256, index: U256) -> U256 {
+    let start = keccak256(&base.to_be_bytes::<{ U256::BYTES }>());
+    U256::from_be_bytes(start.0).wrapping_add(index)
+}
+
+/// Calculates the `blob_gasprice` from the header's excess blob gas field.
+///
+/// See also [the EIP-4844 helpers](https://eips.ethereum.org/EIPS/eip-4844#helpers)
+/// (`get_blob_gasprice`).
+#[inline]
+pub fn calc_blob_gasprice(excess_blob_gas: u64) -> u64 {
+    fake_exponential(
+        MIN_BLOB_GASPRICE,
+        excess_blob_gas,
+        BLOB_GASPRICE_UPDATE_FRACTION,
+    )
+}
+
+/// Approximates `factor * e ** (numerator / denominator)` using Taylor expansion.
+///
+/// This is used to calculate the blob price.
+///
+/// See also [the EIP-4844 helpers](https://eips.ethereum.org/EIPS/eip-4844#helpers)
+/// (`fake_exponential`).
+///
+/// # Panics
+///
+/// This function panics if `denominator` is zero.
+#[inline]
+pub fn fake_exponential(factor: u64, numerator: u64, denominator: u64) -> u64 {
+    assert_ne!(denominator, 0, "attempt to divide by zero");
+    let factor = factor as u128;
+    let numerator = numerator as u128;
+    let denominator = denominator as u128;
+
+    let mut i = 1;
+    let mut output = 0;
+    let mut numerator_accum = factor * denominator;
+    while numerator_accum > 0 {
+        output += numerator_accum;
+
+        // Denominator is asserted as not zero at the start of the function.
+        numerator_accum = (numerator_accum * numerator) / (denominator * i);
+        i += 1;
+    }
+    (output / denominator) as u64
+}
+
 /// Serde functions to serde as [bytes::Bytes] hex string
 #[cfg(feature = "serde")]
 pub mod serde_hex_bytes {
@@ -59,3 +121,181 @@ pub mod serde_hex_bytes {
         .map_err(|e| serde::de::Error::custom(e.to_string()))
     }
 }
+
+/// Serde functions to serde `u64` as JSON-RPC hex quantity (e.g. `"0x1a"`).
+///
+/// Deserialization also accepts plain numbers and decimal strings.
+#[cfg(feature = "serde")]
+pub mod serde_hex_u64 {
+    use alloc::string::{String, ToString};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    pub(crate) enum NumberOrString {
+        Number(u64),
+        String(String),
+    }
+
+    impl NumberOrString {
+        pub(crate) fn into_u64<E: serde::de::Error>(self) -> Result<u64, E> {
+            match self {
+                NumberOrString::Number(n) => Ok(n),
+                NumberOrString::String(s) => if let Some(hex) = s.strip_prefix("0x") {
+                    u64::from_str_radix(hex, 16)
+                } else {
+                    s.parse()
+                }
+                .map_err(|e| E::custom(e.to_string())),
+            }
+        }
+    }
+
+    pub fn serialize<S: Serializer>(x: &u64, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&alloc::format!("{x:#x}"))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<u64, D::Error> {
+        NumberOrString::deserialize(d)?.into_u64()
+    }
+}
+
+/// Same as [serde_hex_u64] but for `Option<u64>`.
+#[cfg(feature = "serde")]
+pub mod serde_hex_u64_opt {
+    use super::serde_hex_u64::NumberOrString;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(x: &Option<u64>, s: S) -> Result<S::Ok, S::Error> {
+        match x {
+            Some(x) => super::serde_hex_u64::serialize(x, s),
+            None => s.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<u64>, D::Error> {
+        Option::<NumberOrString>::deserialize(d)?
+            .map(NumberOrString::into_u64)
+            .transpose()
+    }
+}
+
+/// Serde functions to serde [crate::TransactTo] as JSON-RPC `to` field.
+///
+/// Call is serialized as the address and create as `null`, a missing field is also create.
+/// `CREATE2` has no JSON-RPC form, it is serialized as tagged [crate::TransactTo]
+/// which is accepted when deserializing as well.
+#[cfg(feature = "serde")]
+pub mod serde_transact_to {
+    use crate::{CreateScheme, TransactTo, B160};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum To {
+        Address(B160),
+        TransactTo(TransactTo),
+    }
+
+    pub fn serialize<S: Serializer>(x: &TransactTo, s: S) -> Result<S::Ok, S::Error> {
+        match x {
+            TransactTo::Call(address) => address.serialize(s),
+            TransactTo::Create(CreateScheme::Create) => s.serialize_none(),
+            TransactTo::Create(CreateScheme::Create2 { .. }) => x.serialize(s),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<TransactTo, D::Error> {
+        Ok(match Option::<To>::deserialize(d)? {
+            Some(To::Address(address)) => TransactTo::Call(address),
+            Some(To::TransactTo(to)) => to,
+            None => TransactTo::create(),
+        })
+    }
+}
+
+/// Serde functions to serde the access list of [crate::TxEnv] as JSON-RPC access list,
+/// e.g. `[{"address": "0x..", "storageKeys": ["0x.."]}]`.
+#[cfg(feature = "serde")]
+pub mod serde_access_list {
+    use crate::{AccessList, B160, U256};
+    use alloc::vec::Vec;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(x: &[(B160, Vec<U256>)], s: S) -> Result<S::Ok, S::Error> {
+        AccessList::from(x.to_vec()).serialize(s)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        d: D,
+    ) -> Result<Vec<(B160, Vec<U256>)>, D::Error> {
+        AccessList::deserialize(d).map(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mapping_and_array_slots() {
+        assert_eq!(
+            map_slot(U256::ZERO, B256::zero()),
+            U256::from_be_bytes(hex!(
+                "ad3228b676f7d3cd4284a5443f17f1962b36e491b30a40b2405849e597ba5fb5"
+            ))
+        );
+        // `balanceOf[0x1111..]` with balances at slot 3.
+        assert_eq!(
+            map_slot(U256::from(3), B256::from(B160([0x11; 20]))),
+            U256::from_be_bytes(hex!(
+                "fc40ea33816453f766ebc0872d4b5152b468882abe7b6b35528069db4d6e41c4"
+            ))
+        );
+        assert_eq!(
+            array_slot(U256::ZERO, U256::from(2)),
+            U256::from_be_bytes(hex!(
+                "290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e565"
+            ))
+        );
+    }
+
+    // https://github.com/ethereum/go-ethereum/blob/28857080d732857030eda80c69b9ba2c8926f221/consensus/misc/eip4844/eip4844_test.go#L27
+    #[test]
+    fn test_calc_blob_fee() {
+        let blob_fee_vectors = &[(0, 1), (2314057, 1), (2314058, 2), (10 * 1024 * 1024, 23)];
+
+        for &(excess, expected) in blob_fee_vectors {
+            let actual = calc_blob_gasprice(excess);
+            assert_eq!(actual, expected, "test: {excess}");
+        }
+    }
+
+    // https://github.com/ethereum/go-ethereum/blob/28857080d732857030eda80c69b9ba2c8926f221/consensus/misc/eip4844/eip4844_test.go#L60
+    #[test]
+    fn fake_exp() {
+        for &(factor, numerator, denominator, expected) in &[
+            (1u64, 0u64, 1u64, 1u64),
+            (38493, 0, 1000, 38493),
+            (0, 1234, 2345, 0),
+            (1, 2, 1, 6), // approximate 7.389
+            (1, 4, 2, 6),
+            (1, 3, 1, 16), // approximate 20.09
+            (1, 6, 2, 18),
+            (1, 4, 1, 49), // approximate 54.60
+            (1, 8, 2, 50),
+            (10, 8, 2, 542), // approximate 540.598
+            (11, 8, 2, 596), // approximate 600.58
+            (1, 5, 1, 136),  // approximate 148.4
+            (1, 5, 2, 11),   // approximate 12.18
+            (2, 5, 2, 23),   // approximate 24.36
+            (1, 50000000, 2225652, 5709098764),
+        ] {
+            let actual = fake_exponential(factor, numerator, denominator);
+            assert_eq!(
+                actual, expected,
+                "test: {factor}, {numerator}, {denominator}"
+            );
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use crate::inspectors::CallFrame;
+#[cfg(feature = "gas_by_category")]
+use crate::primitives::GasByCategory;
+use crate::primitives::{
+    DatabaseReadCounts, ExecutionResult, ResultAndState, State, StaticCallViolation, B160, U256,
+};
+use alloc::collections::{BTreeMap, BTreeSet};
+
+/// Accounts and their storage slots that were accessed by the transaction, sorted.
+pub type AccessSet = BTreeMap<B160, BTreeSet<U256>>;
+
+/// Everything that is known about the execution of one transaction,
+/// returned by [crate::EVM::transact_artifacts].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExecutionArtifacts {
+    /// Status of execution.
+    pub result: ExecutionResult,
+    /// State changes of the transaction, not committed to the database.
+    pub state: State,
+    /// Accounts and storage slots that were read or written.
+    pub access: AccessSet,
+    pub metrics: ExecutionMetrics,
+    /// Last state change that was attempted inside a static call.
+    pub static_call_violation: Option<StaticCallViolation>,
+    /// Call trace, if it was requested.
+    pub trace: Option<CallFrame>,
+}
+
+/// Gas and resources used by the transaction.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExecutionMetrics {
+    pub gas_used: u64,
+    pub gas_refunded: u64,
+    /// Total amount deducted from the caller.
+    pub fee_paid: U256,
+    /// Number of database calls done during execution.
+    pub db_reads: DatabaseReadCounts,
+    /// Peak size of interpreter memory in bytes.
+    pub peak_memory: usize,
+    /// Gas used by the execution, grouped by the kind of work.
+    #[cfg(feature = "gas_by_category")]
+    pub gas_by_category: GasByCategory,
+}
+
+impl ExecutionArtifacts {
+    pub fn new(out: ResultAndState, trace: Option<CallFrame>, peak_memory: usize) -> Self {
+        let access = out
+            .state
+            .iter()
+            .map(|(address, account)| (*address, account.storage.keys().copied().collect()))
+            .collect();
+        let metrics = ExecutionMetrics {
+            gas_used: out.result.gas_used(),
+            gas_refunded: out.result.gas_refunded(),
+            fee_paid: out.result.fee_paid(),
+            db_reads: out.db_reads,
+            peak_memory,
+            #[cfg(feature = "gas_by_category")]
+            gas_by_category: out.gas_by_category,
+        };
+        Self {
+            result: out.result,
+            state: out.state,
+            access,
+            metrics,
+            static_call_violation: out.static_call_violation,
+            trace,
+        }
+    }
+}
@@ -0,0 +1,300 @@
+use crate::db::DatabaseRef;
+use crate::interpreter::{opcode, InstructionResult, Interpreter};
+use crate::primitives::{
+    AccountInfo, BlockEnv, Bytecode, CfgEnv, EVMError, Env, HashMap, ResultAndState, TxEnv, B160,
+    B256, U256,
+};
+use crate::{evm_inner, Database, EVMData, Inspector};
+use alloc::vec::Vec;
+
+/// Provides block environment and state of historical blocks for [backtest].
+pub trait BlockStateProvider {
+    type State: DatabaseRef;
+    type Error;
+
+    /// Block environment of block `number`.
+    fn block_env(&mut self, number: u64) -> Result<BlockEnv, Self::Error>;
+
+    /// State on top of which transactions of block `number` are executed.
+    fn state_at(&mut self, number: u64) -> Result<Self::State, Self::Error>;
+}
+
+/// Result of transaction executed by [backtest] in one block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BacktestResult<DBError> {
+    /// Block number.
+    pub number: u64,
+    /// Error if the transaction could not be executed in this block.
+    pub result: Result<ResultAndState, EVMError<DBError>>,
+    /// True if transaction was not executed and the result of the previous block was reused.
+    pub reused: bool,
+}
+
+/// Executes the same transaction on top of the state of every block in `blocks`.
+///
+/// State and block environment of each block are taken from the `provider`. Transaction is
+/// executed again only if it could have a different outcome: result of the previous execution
+/// is reused if all accounts, storage slots and block hashes it read are the same in the new
+/// state and the block environment differs only in fields that the transaction did not read
+/// (number, timestamp, difficulty and prevrandao).
+///
+/// Blocks in which the transaction fails with [EVMError] have the error as their result and
+/// the run continues with the next block. Only errors of the `provider` stop the run.
+#[allow(clippy::type_complexity)]
+pub fn backtest<P: BlockStateProvider>(
+    provider: &mut P,
+    cfg: CfgEnv,
+    tx: TxEnv,
+    blocks: impl IntoIterator<Item = u64>,
+) -> Result<Vec<BacktestResult<<P::State as DatabaseRef>::Error>>, P::Error> {
+    let mut env = Env {
+        cfg,
+        block: BlockEnv::default(),
+        tx,
+    };
+    let mut results = Vec::new();
+    let mut last: Option<Execution> = None;
+    for number in blocks {
+        let block = provider.block_env(number)?;
+        let state = provider.state_at(number)?;
+
+        if let Some(execution) = &last {
+            match execution.is_reusable(&block, &state) {
+                Ok(true) => {
+                    results.push(BacktestResult {
+                        number,
+                        result: Ok(execution.result.clone()),
+                        reused: true,
+                    });
+                    continue;
+                }
+                Ok(false) => {}
+                Err(error) => {
+                    results.push(BacktestResult {
+                        number,
+                        result: Err(EVMError::Database(error)),
+                        reused: false,
+                    });
+                    last = None;
+                    continue;
+                }
+            }
+        }
+
+        env.block = block;
+        let mut db = RecordingDB {
+            db: &state,
+            reads: Reads::default(),
+        };
+        let mut inspector = BlockOpcodeInspector::default();
+        let result = evm_inner::<_, true>(&mut env, &mut db, &mut inspector).transact();
+
+        last = match &result {
+            Ok(result) => Some(Execution {
+                block: env.block.clone(),
+                reads: db.reads,
+                reads_block: inspector.used,
+                result: result.clone(),
+            }),
+            Err(_) => None,
+        };
+        results.push(BacktestResult {
+            number,
+            result,
+            reused: false,
+        });
+    }
+    Ok(results)
+}
+
+/// Execution of the transaction and everything it depended on.
+struct Execution {
+    block: BlockEnv,
+    reads: Reads,
+    /// True if transaction read block number, timestamp, difficulty or block hash.
+    reads_block: bool,
+    result: ResultAndState,
+}
+
+impl Execution {
+    fn is_reusable<DB: DatabaseRef>(&self, block: &BlockEnv, db: &DB) -> Result<bool, DB::Error> {
+        if self.reads_block || mask_block(&self.block) != mask_block(block) {
+            return Ok(false);
+        }
+        for (address, info) in &self.reads.basic {
+            if db.basic(*address)? != *info {
+                return Ok(false);
+            }
+        }
+        for ((address, index), value) in &self.reads.storage {
+            if db.storage(*address, *index)? != *value {
+                return Ok(false);
+            }
+        }
+        for (number, hash) in &self.reads.block_hash {
+            if db.block_hash(*number)? != *hash {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Block environment without fields that are only observable through opcodes.
+fn mask_block(block: &BlockEnv) -> BlockEnv {
+    BlockEnv {
+        number: U256::ZERO,
+        timestamp: U256::ZERO,
+        difficulty: U256::ZERO,
+        prevrandao: None,
+        ..block.clone()
+    }
+}
+
+#[derive(Default)]
+struct Reads {
+    basic: HashMap<B160, Option<AccountInfo>>,
+    storage: HashMap<(B160, U256), U256>,
+    block_hash: HashMap<U256, B256>,
+}
+
+/// Database that records values that were read from it.
+///
+/// Code is not recorded as it is addressed by its hash.
+struct RecordingDB<'a, DB> {
+    db: &'a DB,
+    reads: Reads,
+}
+
+impl<DB: DatabaseRef> Database for RecordingDB<'_, DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: B160) -> Result<Option<AccountInfo>, Self::Error> {
+        let info = self.db.basic(address)?;
+        self.reads.basic.insert(address, info.clone());
+        Ok(info)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.db.code_by_hash(code_hash)
+    }
+
+    fn storage(&mut self, address: B160, index: U256) -> Result<U256, Self::Error> {
+        let value = self.db.storage(address, index)?;
+        self.reads.storage.insert((address, index), value);
+        Ok(value)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        let hash = self.db.block_hash(number)?;
+        self.reads.block_hash.insert(number, hash);
+        Ok(hash)
+    }
+}
+
+/// Detects opcodes that read block fields that differ between every block.
+#[derive(Default)]
+struct BlockOpcodeInspector {
+    used: bool,
+}
+
+impl<DB: Database> Inspector<DB> for BlockOpcodeInspector {
+    fn step(&mut self, interp: &mut Interpreter, _data: &mut EVMData<'_, DB>) -> InstructionResult {
+        if matches!(
+            interp.current_opcode(),
+            opcode::NUMBER | opcode::TIMESTAMP | opcode::DIFFICULTY | opcode::BLOCKHASH
+        ) {
+            self.used = true;
+        }
+        InstructionResult::Continue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{Bytes, InvalidTransaction, TransactTo};
+    use crate::test_utils::{contract, OUTER};
+    use crate::InMemoryDB;
+    use core::convert::Infallible;
+
+    /// Every block has the contract with a different code.
+    struct Provider {
+        codes: Vec<Bytes>,
+        /// Block with zero gas limit, transaction fails in it.
+        full_block: Option<u64>,
+    }
+
+    impl BlockStateProvider for Provider {
+        type State = InMemoryDB;
+        type Error = ();
+
+        fn block_env(&mut self, number: u64) -> Result<BlockEnv, ()> {
+            let mut block = BlockEnv {
+                number: U256::from(number),
+                timestamp: U256::from(number * 12),
+                ..Default::default()
+            };
+            if self.full_block == Some(number) {
+                block.gas_limit = U256::ZERO;
+            }
+            Ok(block)
+        }
+
+        fn state_at(&mut self, number: u64) -> Result<InMemoryDB, ()> {
+            let code = self.codes.get(number as usize).ok_or(())?;
+            let mut db = InMemoryDB::default();
+            db.insert_account_info(OUTER, contract(code));
+            Ok(db)
+        }
+    }
+
+    fn run(codes: &[&[u8]], full_block: Option<u64>) -> Vec<BacktestResult<Infallible>> {
+        let mut provider = Provider {
+            codes: codes
+                .iter()
+                .map(|code| Bytes::copy_from_slice(code))
+                .collect(),
+            full_block,
+        };
+        let tx = TxEnv {
+            transact_to: TransactTo::Call(OUTER),
+            ..Default::default()
+        };
+        backtest(&mut provider, CfgEnv::default(), tx, 0..codes.len() as u64).unwrap()
+    }
+
+    #[test]
+    fn reuses_result_if_state_is_same() {
+        // PUSH1 1 PUSH1 0 SSTORE
+        let store: &[u8] = &[0x60, 0x01, 0x60, 0x00, 0x55];
+        let results = run(&[store, store, &[0x00], &[0x00]], None);
+        let reused: Vec<_> = results.iter().map(|r| r.reused).collect();
+        assert_eq!(reused, [false, true, false, true]);
+        assert_eq!(results[0].result, results[1].result);
+        assert_ne!(results[1].result, results[2].result);
+    }
+
+    #[test]
+    fn executes_again_if_block_is_read() {
+        // NUMBER POP
+        let number: &[u8] = &[0x43, 0x50];
+        let results = run(&[number, number], None);
+        assert!(results.iter().all(|r| !r.reused));
+    }
+
+    #[test]
+    fn continues_after_failed_block() {
+        let results = run(&[&[0x00], &[0x00], &[0x00]], Some(1));
+        assert!(results[0].result.is_ok());
+        assert_eq!(
+            results[1].result,
+            Err(EVMError::Transaction(
+                InvalidTransaction::CallerGasLimitMoreThanBlock
+            ))
+        );
+        // failed block is not reused.
+        assert!(results[2].result.is_ok());
+        assert!(!results[2].reused);
+    }
+}
@@ -1,14 +1,31 @@
+pub mod checked_db;
 pub mod in_memory_db;
+pub mod remap_db;
+pub mod state_override;
+pub mod witness_db;
 
 #[cfg(feature = "ethersdb")]
 pub mod ethersdb;
 #[cfg(feature = "ethersdb")]
 pub use ethersdb::EthersDB;
 
+#[cfg(feature = "asyncdb")]
+pub mod async_db;
+#[cfg(feature = "asyncdb")]
+pub mod async_rpcdb;
+#[cfg(feature = "asyncdb")]
+pub use async_db::{DatabaseAsync, SyncDB};
+#[cfg(feature = "asyncdb")]
+pub use async_rpcdb::AsyncRpcDB;
+
 #[cfg(all(not(feature = "ethersdb"), feature = "web3db"))]
 compile_error!(
     "`web3db` feature is deprecated, drop-in replacement can be found with feature `ethersdb`"
 );
 
 pub use crate::primitives::db::*;
+pub use checked_db::*;
 pub use in_memory_db::*;
+pub use remap_db::RemapDB;
+pub use state_override::{AccountOverride, StateOverride};
+pub use witness_db::*;
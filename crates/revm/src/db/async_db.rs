@@ -0,0 +1,136 @@
+use crate::primitives::{AccountInfo, Bytecode, B160, B256, U256};
+use crate::Database;
+use async_trait::async_trait;
+use tokio::runtime::{Handle, Runtime};
+
+/// Asynchronous version of [Database].
+///
+/// It can be used with the EVM through the [SyncDB] adapter.
+#[async_trait]
+pub trait DatabaseAsync: Send {
+    type Error: Send;
+
+    /// Get basic account information.
+    async fn basic(&mut self, address: B160) -> Result<Option<AccountInfo>, Self::Error>;
+    /// Get account code by its hash
+    async fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error>;
+    /// Get storage value of address at index.
+    async fn storage(&mut self, address: B160, index: U256) -> Result<U256, Self::Error>;
+    /// Get block hash by block number
+    async fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error>;
+}
+
+/// Adapter that implements [Database] for [DatabaseAsync] by blocking on every request.
+///
+/// If it is created outside of the tokio runtime, new runtime is started. Inside of the
+/// runtime, it needs to be the multi-threaded one, as requests are blocked on in place.
+pub struct SyncDB<DB> {
+    pub db: DB,
+    runtime: HandleOrRuntime,
+}
+
+enum HandleOrRuntime {
+    Handle(Handle),
+    Runtime(Runtime),
+}
+
+impl<DB: DatabaseAsync> SyncDB<DB> {
+    /// Returns an error if the runtime is needed and it can't be started.
+    pub fn new(db: DB) -> std::io::Result<Self> {
+        let runtime = match Handle::try_current() {
+            Ok(handle) => HandleOrRuntime::Handle(handle),
+            Err(_) => HandleOrRuntime::Runtime(Runtime::new()?),
+        };
+        Ok(Self { db, runtime })
+    }
+
+    pub fn into_inner(self) -> DB {
+        self.db
+    }
+
+    /// internal utility function to call tokio feature and wait for output
+    fn block_on<F: core::future::Future>(runtime: &HandleOrRuntime, f: F) -> F::Output {
+        match runtime {
+            HandleOrRuntime::Handle(handle) => tokio::task::block_in_place(|| handle.block_on(f)),
+            HandleOrRuntime::Runtime(runtime) => runtime.block_on(f),
+        }
+    }
+}
+
+impl<DB: DatabaseAsync> Database for SyncDB<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: B160) -> Result<Option<AccountInfo>, Self::Error> {
+        Self::block_on(&self.runtime, self.db.basic(address))
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        Self::block_on(&self.runtime, self.db.code_by_hash(code_hash))
+    }
+
+    fn storage(&mut self, address: B160, index: U256) -> Result<U256, Self::Error> {
+        Self::block_on(&self.runtime, self.db.storage(address, index))
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        Self::block_on(&self.runtime, self.db.block_hash(number))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::Bytes;
+    use crate::InMemoryDB;
+    use core::convert::Infallible;
+
+    struct AsyncInMemoryDB(InMemoryDB);
+
+    #[async_trait]
+    impl DatabaseAsync for AsyncInMemoryDB {
+        type Error = Infallible;
+
+        async fn basic(&mut self, address: B160) -> Result<Option<AccountInfo>, Infallible> {
+            self.0.basic(address)
+        }
+
+        async fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Infallible> {
+            self.0.code_by_hash(code_hash)
+        }
+
+        async fn storage(&mut self, address: B160, index: U256) -> Result<U256, Infallible> {
+            self.0.storage(address, index)
+        }
+
+        async fn block_hash(&mut self, number: U256) -> Result<B256, Infallible> {
+            self.0.block_hash(number)
+        }
+    }
+
+    #[test]
+    fn sync_adapter() {
+        let address = B160([0x10; 20]);
+        let code = Bytecode::new_raw(Bytes::from_static(&[0x00]));
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(address, AccountInfo::new(U256::from(1), 0, code.clone()));
+        db.insert_account_storage(address, U256::ZERO, U256::from(2))
+            .unwrap();
+
+        let mut db = SyncDB::new(AsyncInMemoryDB(db)).unwrap();
+        let info = db.basic(address).unwrap().unwrap();
+        assert_eq!(info.balance, U256::from(1));
+        assert_eq!(db.code_by_hash(code.hash()).unwrap().hash(), code.hash());
+        assert_eq!(db.storage(address, U256::ZERO).unwrap(), U256::from(2));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn sync_adapter_in_runtime() {
+        let address = B160([0x10; 20]);
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(address, AccountInfo::from_balance(U256::from(1)));
+
+        let mut db = SyncDB::new(AsyncInMemoryDB(db)).unwrap();
+        let info = db.basic(address).unwrap().unwrap();
+        assert_eq!(info.balance, U256::from(1));
+    }
+}
@@ -0,0 +1,437 @@
+use super::{DatabaseAsync, InMemoryDB};
+use crate::primitives::{
+    AccountInfo, Bytecode, HashSet, TransactTo, TxEnv, B160, B256, KECCAK_EMPTY, U256,
+};
+use async_trait::async_trait;
+use ethers_core::types::{
+    BlockId, Bytes as eBytes, H160 as eH160, H256, U256 as eU256, U64 as eU64,
+};
+use ethers_providers::{Middleware, MiddlewareError, ProviderError};
+use futures::future::try_join_all;
+use reqwest::{header::CONTENT_TYPE, Url};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// Remote database that loads state of the given block over JSON-RPC.
+///
+/// Loaded accounts, storage slots and block hashes are cached, so every value is requested
+/// only once. Multiple values can be loaded at once with [`AsyncRpcDB::prefetch`], their
+/// requests are sent concurrently instead of one after another, or in JSON-RPC batches
+/// if [`AsyncRpcDB::with_batching`] is used.
+///
+/// Use [super::SyncDB] to execute transactions with it.
+pub struct AsyncRpcDB<M: Middleware> {
+    client: Arc<M>,
+    block: BlockId,
+    cache: InMemoryDB,
+    batch: Option<BatchClient>,
+}
+
+impl<M: Middleware> AsyncRpcDB<M> {
+    /// Creates database for the given block, or for the latest block if it is `None`.
+    pub async fn new(client: Arc<M>, block: Option<BlockId>) -> Result<Self, M::Error> {
+        let block = match block {
+            Some(block) => block,
+            None => BlockId::from(client.get_block_number().await?),
+        };
+        Ok(Self {
+            client,
+            block,
+            cache: InMemoryDB::default(),
+            batch: None,
+        })
+    }
+
+    /// Sends requests of [`AsyncRpcDB::prefetch`] to the HTTP endpoint `url` in JSON-RPC
+    /// batches of at most `max_size` requests, instead of one HTTP request per value.
+    ///
+    /// `url` should be the endpoint of the node the client is connected to, as ethers
+    /// middleware has no batch API.
+    pub fn with_batching(mut self, url: Url, max_size: usize) -> Self {
+        self.batch = Some(BatchClient {
+            http: reqwest::Client::new(),
+            url,
+            max_size: max_size.max(1),
+        });
+        self
+    }
+
+    /// Returns the block which state is loaded.
+    pub fn block(&self) -> BlockId {
+        self.block
+    }
+
+    /// Returns cached state.
+    pub fn cache(&self) -> &InMemoryDB {
+        &self.cache
+    }
+
+    pub fn into_cache(self) -> InMemoryDB {
+        self.cache
+    }
+
+    /// Loads accounts and their storage slots that are not already cached.
+    pub async fn prefetch<I>(&mut self, accounts: I) -> Result<(), M::Error>
+    where
+        I: IntoIterator<Item = (B160, Vec<U256>)>,
+    {
+        let mut missing_accounts = HashSet::new();
+        let mut missing_slots = HashSet::new();
+        for (address, slots) in accounts {
+            let account = self.cache.accounts.get(&address);
+            if account.is_none() {
+                missing_accounts.insert(address);
+            }
+            missing_slots.extend(
+                slots
+                    .into_iter()
+                    .filter(|index| account.map_or(true, |acc| !acc.storage.contains_key(index)))
+                    .map(|index| (address, index)),
+            );
+        }
+
+        let missing_accounts: Vec<_> = missing_accounts.into_iter().collect();
+        let missing_slots: Vec<_> = missing_slots.into_iter().collect();
+        let (infos, values) = match &self.batch {
+            Some(batch) => {
+                self.fetch_batched(batch, &missing_accounts, &missing_slots)
+                    .await?
+            }
+            None => futures::try_join!(
+                try_join_all(
+                    missing_accounts
+                        .iter()
+                        .map(|address| self.fetch_account(*address))
+                ),
+                try_join_all(
+                    missing_slots
+                        .iter()
+                        .map(|(address, index)| self.fetch_storage(*address, *index))
+                ),
+            )?,
+        };
+
+        for (address, info) in missing_accounts.into_iter().zip(infos) {
+            self.cache.insert_account_info(address, info);
+        }
+        for ((address, index), value) in missing_slots.into_iter().zip(values) {
+            self.cache
+                .accounts
+                .entry(address)
+                .or_default()
+                .storage
+                .insert(index, value);
+        }
+        Ok(())
+    }
+
+    /// Loads caller, called contract and everything from the access list of the transaction.
+    pub async fn prefetch_tx(&mut self, tx: &TxEnv) -> Result<(), M::Error> {
+        let mut accounts = tx.access_list.clone();
+        accounts.push((tx.caller, Vec::new()));
+        if let TransactTo::Call(address) = tx.transact_to {
+            accounts.push((address, Vec::new()));
+        }
+        self.prefetch(accounts).await
+    }
+
+    async fn fetch_account(&self, address: B160) -> Result<AccountInfo, M::Error> {
+        let address = eH160::from(address.0);
+        let block = Some(self.block);
+        let (nonce, balance, code) = futures::try_join!(
+            self.client.get_transaction_count(address, block),
+            self.client.get_balance(address, block),
+            self.client.get_code(address, block),
+        )?;
+        Ok(AccountInfo::new(
+            U256::from_limbs(balance.0),
+            nonce.as_u64(),
+            Bytecode::new_raw(code.0),
+        ))
+    }
+
+    async fn fetch_storage(&self, address: B160, index: U256) -> Result<U256, M::Error> {
+        let value = self
+            .client
+            .get_storage_at(
+                eH160::from(address.0),
+                H256::from(index.to_be_bytes()),
+                Some(self.block),
+            )
+            .await?;
+        Ok(U256::from_be_bytes(value.to_fixed_bytes()))
+    }
+
+    /// Fetches accounts and storage slots with the batch client, in the order they are given.
+    async fn fetch_batched(
+        &self,
+        batch: &BatchClient,
+        accounts: &[B160],
+        slots: &[(B160, U256)],
+    ) -> Result<(Vec<AccountInfo>, Vec<U256>), M::Error> {
+        let block = serde_json::to_value(self.block).map_err(custom_error::<M>)?;
+        let mut requests = Vec::with_capacity(accounts.len() * 3 + slots.len());
+        for address in accounts {
+            let address = eH160::from(address.0);
+            for method in ["eth_getTransactionCount", "eth_getBalance", "eth_getCode"] {
+                requests.push((method, json!([address, block])));
+            }
+        }
+        for (address, index) in slots {
+            let params = json!([
+                eH160::from(address.0),
+                H256::from(index.to_be_bytes()),
+                block
+            ]);
+            requests.push(("eth_getStorageAt", params));
+        }
+
+        let mut results = batch.send(&requests).await.map_err(custom_error::<M>)?;
+        let mut infos = Vec::with_capacity(accounts.len());
+        for _ in accounts {
+            let nonce: eU64 = decode(results.next()).map_err(custom_error::<M>)?;
+            let balance: eU256 = decode(results.next()).map_err(custom_error::<M>)?;
+            let code: eBytes = decode(results.next()).map_err(custom_error::<M>)?;
+            infos.push(AccountInfo::new(
+                U256::from_limbs(balance.0),
+                nonce.as_u64(),
+                Bytecode::new_raw(code.0),
+            ));
+        }
+        let mut values = Vec::with_capacity(slots.len());
+        for _ in slots {
+            let value: H256 = decode(results.next()).map_err(custom_error::<M>)?;
+            values.push(U256::from_be_bytes(value.to_fixed_bytes()));
+        }
+        Ok((infos, values))
+    }
+}
+
+/// HTTP client that sends JSON-RPC requests in batches.
+struct BatchClient {
+    http: reqwest::Client,
+    url: Url,
+    max_size: usize,
+}
+
+impl BatchClient {
+    /// Sends requests in batches of at most `max_size`, batches are sent concurrently.
+    ///
+    /// Results are returned in the order of the requests.
+    async fn send(
+        &self,
+        requests: &[(&str, Value)],
+    ) -> Result<impl Iterator<Item = Value>, String> {
+        let batches = try_join_all(
+            requests
+                .chunks(self.max_size)
+                .map(|requests| self.send_batch(requests)),
+        )
+        .await?;
+        Ok(batches.into_iter().flatten())
+    }
+
+    async fn send_batch(&self, requests: &[(&str, Value)]) -> Result<Vec<Value>, String> {
+        let body: Vec<Value> = requests
+            .iter()
+            .enumerate()
+            .map(|(id, (method, params))| {
+                json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+            })
+            .collect();
+        let body = serde_json::to_vec(&body).map_err(|e| e.to_string())?;
+        let response = self
+            .http
+            .post(self.url.clone())
+            .header(CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?
+            .bytes()
+            .await
+            .map_err(|e| e.to_string())?;
+        batch_results(&response, requests.len())
+    }
+}
+
+/// Returns results of the batch response ordered by their request ids, which are
+/// indexes of the requests in the batch of `len` requests.
+fn batch_results(response: &[u8], len: usize) -> Result<Vec<Value>, String> {
+    let responses: Vec<Value> = serde_json::from_slice(response)
+        .map_err(|e| format!("invalid JSON-RPC batch response: {e}"))?;
+    let mut results = vec![None; len];
+    for mut response in responses {
+        let id = response.get("id").cloned().unwrap_or_default();
+        let result = id
+            .as_u64()
+            .and_then(|id| results.get_mut(id as usize))
+            .ok_or_else(|| format!("JSON-RPC response with unknown id: {id}"))?;
+        if let Some(error) = response.get("error") {
+            return Err(format!("JSON-RPC error: {error}"));
+        }
+        *result = Some(
+            response
+                .get_mut("result")
+                .map(Value::take)
+                .unwrap_or_default(),
+        );
+    }
+    results
+        .into_iter()
+        .enumerate()
+        .map(|(id, result)| result.ok_or_else(|| format!("missing JSON-RPC response {id}")))
+        .collect()
+}
+
+fn decode<T: DeserializeOwned>(result: Option<Value>) -> Result<T, String> {
+    let result = result.ok_or("missing JSON-RPC result")?;
+    serde_json::from_value(result).map_err(|e| format!("invalid JSON-RPC result: {e}"))
+}
+
+fn custom_error<M: Middleware>(error: impl ToString) -> M::Error {
+    M::Error::from_provider_err(ProviderError::CustomError(error.to_string()))
+}
+
+#[async_trait]
+impl<M: Middleware> DatabaseAsync for AsyncRpcDB<M> {
+    type Error = M::Error;
+
+    async fn basic(&mut self, address: B160) -> Result<Option<AccountInfo>, Self::Error> {
+        self.prefetch([(address, Vec::new())]).await?;
+        Ok(self
+            .cache
+            .accounts
+            .get(&address)
+            .map(|acc| acc.info.clone()))
+    }
+
+    async fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        if code_hash == KECCAK_EMPTY {
+            return Ok(Bytecode::new());
+        }
+        // code is loaded together with the account, JSON-RPC can't load it by hash.
+        self.cache
+            .contracts
+            .get(&code_hash)
+            .cloned()
+            .ok_or_else(|| custom_error::<M>(format!("code {code_hash:?} is not loaded")))
+    }
+
+    async fn storage(&mut self, address: B160, index: U256) -> Result<U256, Self::Error> {
+        self.prefetch([(address, vec![index])]).await?;
+        self.cache
+            .accounts
+            .get(&address)
+            .and_then(|acc| acc.storage.get(&index))
+            .copied()
+            .ok_or_else(|| custom_error::<M>(format!("storage {address:?}[{index}] is not loaded")))
+    }
+
+    async fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        // saturate usize
+        let Ok(block_number) = u64::try_from(number) else {
+            return Ok(KECCAK_EMPTY);
+        };
+        if let Some(hash) = self.cache.block_hashes.get(&number) {
+            return Ok(*hash);
+        }
+        let block = self
+            .client
+            .get_block(BlockId::from(eU64::from(block_number)))
+            .await?;
+        let hash = block
+            .and_then(|block| block.hash)
+            .map(|hash| B256(hash.0))
+            .unwrap_or_default();
+        self.cache.block_hashes.insert(number, hash);
+        Ok(hash)
+    }
+}
+
+/// Run tests with `cargo test -- --nocapture` to see print statements
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::SyncDB;
+    use crate::Database;
+    use ethers_providers::{Http, Provider};
+
+    #[test]
+    fn batch_results_by_id() {
+        let response = br#"[
+            {"jsonrpc": "2.0", "id": 1, "result": "0x2"},
+            {"jsonrpc": "2.0", "id": 0, "result": "0x1"}
+        ]"#;
+        assert_eq!(
+            batch_results(response, 2).unwrap(),
+            vec![json!("0x1"), json!("0x2")]
+        );
+        // response 2 is missing.
+        assert!(batch_results(response, 3).is_err());
+
+        let response =
+            br#"[{"jsonrpc": "2.0", "id": 0, "error": {"code": -32000, "message": "no"}}]"#;
+        assert!(batch_results(response, 1).unwrap_err().contains("no"));
+        assert!(batch_results(br#"{"jsonrpc": "2.0"}"#, 1).is_err());
+
+        let nonce: eU64 = decode(Some(json!("0x10"))).unwrap();
+        assert_eq!(nonce.as_u64(), 16);
+    }
+
+    const RPC_URL: &str = "https://mainnet.infura.io/v3/c60b0bb42f8a4c6481ecd229eddaca27";
+
+    #[test]
+    #[ignore = "requires a JSON-RPC endpoint"]
+    fn can_prefetch() {
+        can_prefetch_with(None);
+    }
+
+    #[test]
+    #[ignore = "requires a JSON-RPC endpoint"]
+    fn can_prefetch_batched() {
+        can_prefetch_with(Some(Url::parse(RPC_URL).unwrap()));
+    }
+
+    fn can_prefetch_with(batch_url: Option<Url>) {
+        let client = Provider::<Http>::try_from(RPC_URL).unwrap();
+        // ETH/USDT pair on Uniswap V2
+        let address = "0x0d4a11d5EEaaC28EC3F61d100daF4d40471f1852"
+            .parse::<eH160>()
+            .unwrap();
+        let address = B160(address.0);
+        let tx = TxEnv {
+            transact_to: TransactTo::Call(address),
+            access_list: vec![(address, vec![U256::from(5), U256::from(6)])],
+            ..Default::default()
+        };
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let db = runtime.block_on(async {
+            let mut db = AsyncRpcDB::new(Arc::new(client), Some(BlockId::from(16148323)))
+                .await
+                .unwrap();
+            if let Some(url) = batch_url {
+                db = db.with_batching(url, 2);
+            }
+            db.prefetch_tx(&tx).await.unwrap();
+            db
+        });
+        drop(runtime);
+        assert!(db.cache().accounts[&address]
+            .storage
+            .contains_key(&U256::from(6)));
+
+        let mut db = SyncDB::new(db).unwrap();
+        assert!(db.basic(address).unwrap().unwrap().exists());
+        // storage[5] -> factory: address
+        assert_eq!(
+            db.storage(address, U256::from(5)).unwrap(),
+            U256::try_from_be_slice(
+                &hex::decode("5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f").unwrap()
+            )
+            .unwrap()
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use super::{Database, DatabaseCommit};
+use crate::primitives::{Account, AccountInfo, Bytecode, HashMap, HashSet, B160, B256, U256};
+
+/// Database wrapper that asserts that the wrapped database and its user uphold
+/// protocol invariants. It is intended to be used in tests of database integrations.
+///
+/// It panics if:
+/// * Storage read of selfdestructed account that was not created again returns non zero value,
+///   or its account read returns non empty account.
+/// * Account is committed without being loaded from the database first. This also catches
+///   the same changes being committed twice, as commit requires accounts to be loaded again.
+/// * Code hash of the account changed without being committed.
+/// * Bytecode returned by `code_by_hash` has different hash than the requested one.
+///
+/// Selfdestructed accounts can be read again, database is expected to return them as empty.
+#[derive(Debug, Clone)]
+pub struct CheckedDb<DB> {
+    pub db: DB,
+    /// Accounts that are loaded and not yet committed.
+    loaded: HashSet<B160>,
+    /// Selfdestructed accounts that were not created again.
+    destroyed: HashSet<B160>,
+    /// Last known code hash of accounts.
+    code_hashes: HashMap<B160, B256>,
+}
+
+impl<DB> CheckedDb<DB> {
+    pub fn new(db: DB) -> Self {
+        Self {
+            db,
+            loaded: HashSet::new(),
+            destroyed: HashSet::new(),
+            code_hashes: HashMap::new(),
+        }
+    }
+
+    pub fn into_inner(self) -> DB {
+        self.db
+    }
+}
+
+impl<DB: Database> Database for CheckedDb<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: B160) -> Result<Option<AccountInfo>, Self::Error> {
+        let info = self.db.basic(address)?;
+        if self.destroyed.contains(&address) {
+            assert!(
+                info.as_ref().map_or(true, AccountInfo::is_empty),
+                "Account {address:?} is selfdestructed but database returned {info:?}"
+            );
+        }
+        if let Some(info) = &info {
+            if let Some(code_hash) = self.code_hashes.insert(address, info.code_hash) {
+                assert_eq!(
+                    code_hash, info.code_hash,
+                    "Code hash of account {address:?} changed without commit"
+                );
+            }
+        }
+        self.loaded.insert(address);
+        Ok(info)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        let code = self.db.code_by_hash(code_hash)?;
+        // zero hash is used by some databases for accounts without code.
+        if !(code_hash.is_zero() && code.is_empty()) {
+            assert_eq!(
+                code.hash(),
+                code_hash,
+                "Database returned bytecode with different hash"
+            );
+        }
+        Ok(code)
+    }
+
+    fn storage(&mut self, address: B160, index: U256) -> Result<U256, Self::Error> {
+        let value = self.db.storage(address, index)?;
+        if self.destroyed.contains(&address) {
+            assert_eq!(
+                value,
+                U256::ZERO,
+                "Storage {index} of selfdestructed account {address:?} is not cleared"
+            );
+        }
+        Ok(value)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        self.db.block_hash(number)
+    }
+}
+
+impl<DB: DatabaseCommit> DatabaseCommit for CheckedDb<DB> {
+    fn commit(&mut self, changes: HashMap<B160, Account>) {
+        for (address, account) in &changes {
+            assert!(
+                self.loaded.remove(address),
+                "Account {address:?} is committed without being loaded"
+            );
+            if !account.is_touched() {
+                continue;
+            }
+            if account.is_selfdestructed() {
+                self.destroyed.insert(*address);
+                self.code_hashes.remove(address);
+            } else {
+                if account.is_newly_created() {
+                    self.destroyed.remove(address);
+                }
+                self.code_hashes.insert(*address, account.info.code_hash);
+            }
+        }
+        self.db.commit(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::Bytes;
+    use crate::InMemoryDB;
+
+    const ADDRESS: B160 = B160([0x10; 20]);
+
+    fn destroyed_account() -> HashMap<B160, Account> {
+        let mut account = Account::from(AccountInfo::default());
+        account.mark_touch();
+        account.mark_selfdestruct();
+        [(ADDRESS, account)].into_iter().collect()
+    }
+
+    fn checked_db() -> CheckedDb<InMemoryDB> {
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(ADDRESS, AccountInfo::from_balance(U256::from(1)));
+        db.insert_account_storage(ADDRESS, U256::ZERO, U256::from(1))
+            .unwrap();
+        CheckedDb::new(db)
+    }
+
+    #[test]
+    fn destroyed_account_is_cleared() {
+        let mut db = checked_db();
+        db.basic(ADDRESS).unwrap();
+        db.commit(destroyed_account());
+        assert_eq!(db.basic(ADDRESS).unwrap(), None);
+        assert_eq!(db.storage(ADDRESS, U256::ZERO).unwrap(), U256::ZERO);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not cleared")]
+    fn stale_storage_after_destroy() {
+        let mut db = checked_db();
+        db.basic(ADDRESS).unwrap();
+        db.commit(destroyed_account());
+        // storage is restored behind the back of the checker.
+        db.db
+            .insert_account_storage(ADDRESS, U256::ZERO, U256::from(1))
+            .unwrap();
+        db.storage(ADDRESS, U256::ZERO).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "without being loaded")]
+    fn double_commit() {
+        let mut db = checked_db();
+        db.basic(ADDRESS).unwrap();
+        db.commit(destroyed_account());
+        db.commit(destroyed_account());
+    }
+
+    #[test]
+    #[should_panic(expected = "changed without commit")]
+    fn code_hash_changed() {
+        let mut db = checked_db();
+        db.basic(ADDRESS).unwrap();
+        db.db.insert_account_info(
+            ADDRESS,
+            AccountInfo::new(
+                U256::ZERO,
+                0,
+                Bytecode::new_raw(Bytes::from_static(&[0x00])),
+            ),
+        );
+        db.basic(ADDRESS).unwrap();
+    }
+}
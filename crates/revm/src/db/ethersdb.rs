@@ -20,9 +20,10 @@ where
 {
     /// create ethers db connector inputs are url and block on what we are basing our database (None for latest)
     pub fn new(client: Arc<M>, block_number: Option<BlockId>) -> Option<Self> {
-        let runtime = Handle::try_current()
-            .is_err()
-            .then(|| Runtime::new().unwrap());
+        let runtime = match Handle::try_current() {
+            Ok(_) => None,
+            Err(_) => Some(Runtime::new().ok()?),
+        };
 
         let client = client;
 
@@ -68,26 +69,16 @@ where
             tokio::join!(nonce, balance, code)
         };
         let (nonce, balance, code) = self.block_on(f);
-        // panic on not getting data?
         Ok(Some(AccountInfo::new(
-            U256::from_limbs(
-                balance
-                    .unwrap_or_else(|e| panic!("ethers get balance error: {e:?}"))
-                    .0,
-            ),
-            nonce
-                .unwrap_or_else(|e| panic!("ethers get nonce error: {e:?}"))
-                .as_u64(),
-            Bytecode::new_raw(
-                code.unwrap_or_else(|e| panic!("ethers get code error: {e:?}"))
-                    .0,
-            ),
+            U256::from_limbs(balance.map_err(|_| ())?.0),
+            nonce.map_err(|_| ())?.as_u64(),
+            Bytecode::new_raw(code.map_err(|_| ())?.0),
         )))
     }
 
     fn code_by_hash(&mut self, _code_hash: B256) -> Result<Bytecode, Self::Error> {
-        panic!("Should not be called. Code is already loaded");
-        // not needed because we already load code with basic info
+        // Should not be called. Code is already loaded with basic info.
+        Err(())
     }
 
     fn storage(&mut self, address: B160, index: U256) -> Result<U256, Self::Error> {
@@ -98,18 +89,18 @@ where
                 .client
                 .get_storage_at(add, index, self.block_number)
                 .await
-                .unwrap();
-            U256::from_be_bytes(storage.to_fixed_bytes())
+                .map_err(|_| ())?;
+            Ok::<_, ()>(U256::from_be_bytes(storage.to_fixed_bytes()))
         };
-        Ok(self.block_on(f))
+        self.block_on(f)
     }
 
     fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
         // saturate usize
-        if number > U256::from(u64::MAX) {
+        let Ok(number) = u64::try_from(number) else {
             return Ok(KECCAK_EMPTY);
-        }
-        let number = eU64::from(u64::try_from(number).unwrap());
+        };
+        let number = eU64::from(number);
         let f = async {
             self.client
                 .get_block(BlockId::from(number))
@@ -117,7 +108,8 @@ where
                 .ok()
                 .flatten()
         };
-        Ok(B256(self.block_on(f).unwrap().hash.unwrap().0))
+        let hash = self.block_on(f).and_then(|block| block.hash).ok_or(())?;
+        Ok(B256(hash.0))
     }
 }
 
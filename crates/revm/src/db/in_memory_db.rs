@@ -482,4 +482,93 @@ mod tests {
         assert_eq!(new_state.storage(account, key0), Ok(U256::ZERO));
         assert_eq!(new_state.storage(account, key1), Ok(value1));
     }
+
+    #[test]
+    pub fn test_dyn_database() {
+        use crate::primitives::db::{DatabaseDyn, DatabaseMapErr};
+        use alloc::{boxed::Box, string::String};
+        use core::convert::Infallible;
+
+        fn map_err(e: Infallible) -> String {
+            match e {}
+        }
+
+        let account = 42.into();
+        let mut cache = CacheDB::new(EmptyDB::default());
+        cache.insert_account_info(
+            account,
+            AccountInfo {
+                nonce: 42,
+                ..Default::default()
+            },
+        );
+
+        let mut dbs: [Box<dyn DatabaseDyn<String>>; 2] = [
+            Box::new(DatabaseMapErr::new(cache, map_err)),
+            Box::new(DatabaseMapErr::new(
+                CacheDB::new(EmptyDB::default()),
+                map_err,
+            )),
+        ];
+        assert_eq!(dbs[0].basic(account).unwrap().unwrap().nonce, 42);
+        assert_eq!(dbs[1].basic(account), Ok(None));
+    }
+
+    #[test]
+    pub fn test_db_reads() {
+        use crate::primitives::{Bytecode, Bytes, DatabaseReadCounts, TransactTo, B160};
+
+        let contract = B160([0x10; 20]);
+        let other = B160([0x20; 20]);
+        // SLOAD(0) twice, SLOAD(1), EXTCODESIZE(other), BALANCE(other), BLOCKHASH(9)
+        let mut code = vec![
+            0x60, 0x00, 0x54, 0x50, 0x60, 0x00, 0x54, 0x50, 0x60, 0x01, 0x54, 0x50, 0x73,
+        ];
+        code.extend(other.0);
+        code.extend([0x3b, 0x50, 0x73]);
+        code.extend(other.0);
+        code.extend([0x31, 0x50, 0x60, 0x09, 0x40, 0x50]);
+        let code = Bytecode::new_raw(Bytes::from(code));
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        // code of the contract is loaded by hash, code of the other account with it.
+        db.insert_account_info(
+            contract,
+            AccountInfo {
+                nonce: 1,
+                code_hash: code.hash(),
+                code: None,
+                ..Default::default()
+            },
+        );
+        db.contracts.insert(code.hash(), code);
+        db.insert_account_info(
+            other,
+            AccountInfo::new(
+                U256::ZERO,
+                1,
+                Bytecode::new_raw(Bytes::from_static(&[0x00])),
+            ),
+        );
+
+        let mut evm = crate::new();
+        evm.database(db);
+        evm.env.block.number = U256::from(10);
+        evm.env.tx.caller = B160([0x01; 20]);
+        evm.env.tx.transact_to = TransactTo::Call(contract);
+        let out = evm.transact().unwrap();
+        assert!(out.result.is_success());
+        // coinbase, caller, contract and other account. Second reads of slot 0 and
+        // of the other account are served by the journal.
+        assert_eq!(
+            out.db_reads,
+            DatabaseReadCounts {
+                basic: 4,
+                storage: 2,
+                code_by_hash: 1,
+                block_hash: 1,
+            }
+        );
+        assert_eq!(out.db_reads.total(), 8);
+    }
 }
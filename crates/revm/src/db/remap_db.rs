@@ -0,0 +1,156 @@
+use super::{Database, DatabaseCommit, DatabaseRef};
+use crate::primitives::{Account, AccountInfo, Bytecode, HashMap, B160, B256, U256};
+
+/// Database wrapper that swaps pairs of addresses in all reads and commits.
+///
+/// After `remap(a, b)` the account `a` is read from and committed to the account `b`
+/// of the wrapped database and `b` to `a`. This moves mainnet contracts to test addresses,
+/// or isolates simulations that share the same cache, without copying their state.
+///
+/// Only addresses used as database keys are remapped, addresses stored in contract code
+/// or storage are not changed.
+#[derive(Debug, Clone)]
+pub struct RemapDB<DB> {
+    pub db: DB,
+    /// Address pairs, every pair is present in both directions.
+    addresses: HashMap<B160, B160>,
+}
+
+impl<DB> RemapDB<DB> {
+    pub fn new(db: DB) -> Self {
+        Self {
+            db,
+            addresses: HashMap::new(),
+        }
+    }
+
+    pub fn into_inner(self) -> DB {
+        self.db
+    }
+
+    /// Swaps addresses `a` and `b`. Previous pairs of `a` and `b` are removed.
+    pub fn remap(&mut self, a: B160, b: B160) -> &mut Self {
+        self.remove(&a);
+        self.remove(&b);
+        if a != b {
+            self.addresses.insert(a, b);
+            self.addresses.insert(b, a);
+        }
+        self
+    }
+
+    /// Removes the pair of `address`, so both addresses of the pair are used as they are.
+    pub fn remove(&mut self, address: &B160) -> &mut Self {
+        if let Some(other) = self.addresses.remove(address) {
+            self.addresses.remove(&other);
+        }
+        self
+    }
+
+    /// Returns the address that `address` is mapped to, in either direction.
+    pub fn map(&self, address: B160) -> B160 {
+        self.addresses.get(&address).copied().unwrap_or(address)
+    }
+}
+
+impl<DB: Database> Database for RemapDB<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: B160) -> Result<Option<AccountInfo>, Self::Error> {
+        self.db.basic(self.map(address))
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.db.code_by_hash(code_hash)
+    }
+
+    fn storage(&mut self, address: B160, index: U256) -> Result<U256, Self::Error> {
+        self.db.storage(self.map(address), index)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        self.db.block_hash(number)
+    }
+}
+
+impl<DB: DatabaseRef> DatabaseRef for RemapDB<DB> {
+    type Error = DB::Error;
+
+    fn basic(&self, address: B160) -> Result<Option<AccountInfo>, Self::Error> {
+        self.db.basic(self.map(address))
+    }
+
+    fn code_by_hash(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.db.code_by_hash(code_hash)
+    }
+
+    fn storage(&self, address: B160, index: U256) -> Result<U256, Self::Error> {
+        self.db.storage(self.map(address), index)
+    }
+
+    fn block_hash(&self, number: U256) -> Result<B256, Self::Error> {
+        self.db.block_hash(number)
+    }
+}
+
+impl<DB: DatabaseCommit> DatabaseCommit for RemapDB<DB> {
+    fn commit(&mut self, changes: HashMap<B160, Account>) {
+        let changes = changes
+            .into_iter()
+            .map(|(address, account)| (self.map(address), account))
+            .collect();
+        self.db.commit(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{contract, evm_calling};
+    use crate::InMemoryDB;
+
+    const MAINNET: B160 = B160([0x10; 20]);
+    const TEST: B160 = B160([0x20; 20]);
+
+    #[test]
+    fn remap() {
+        let mut db = RemapDB::new(());
+        db.remap(MAINNET, TEST);
+        assert_eq!(db.map(MAINNET), TEST);
+        assert_eq!(db.map(TEST), MAINNET);
+
+        let other = B160([0x30; 20]);
+        db.remap(other, TEST);
+        assert_eq!(db.map(MAINNET), MAINNET);
+        assert_eq!(db.map(TEST), other);
+
+        db.remove(&other);
+        assert_eq!(db.map(TEST), TEST);
+        assert_eq!(db.map(other), other);
+    }
+
+    #[test]
+    fn transact_on_remapped_contract() {
+        let mut backend = InMemoryDB::default();
+        // PUSH1 0 SLOAD PUSH1 1 ADD PUSH1 0 SSTORE
+        let code = [0x60, 0x00, 0x54, 0x60, 0x01, 0x01, 0x60, 0x00, 0x55];
+        backend.insert_account_info(MAINNET, contract(&code));
+        backend
+            .insert_account_storage(MAINNET, U256::ZERO, U256::from(5))
+            .unwrap();
+
+        let mut db = RemapDB::new(backend);
+        db.remap(MAINNET, TEST);
+        assert_eq!(DatabaseRef::basic(&db, MAINNET).unwrap(), None);
+
+        let mut evm = evm_calling(db, TEST);
+        evm.transact_commit().unwrap();
+
+        let backend = evm.take_db().unwrap().into_inner();
+        assert_eq!(
+            DatabaseRef::storage(&backend, MAINNET, U256::ZERO).unwrap(),
+            U256::from(6)
+        );
+        assert!(!backend.accounts.contains_key(&TEST));
+    }
+}
@@ -0,0 +1,153 @@
+use super::{AccountState, CacheDB, DatabaseRef};
+use crate::primitives::{map_slot, Bytecode, Bytes, HashMap, B160, B256, U256};
+
+/// Overrides of the account fields, same as the account overrides of geth `eth_call`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccountOverride {
+    pub balance: Option<U256>,
+    pub nonce: Option<u64>,
+    pub code: Option<Bytes>,
+    /// Replaces the whole storage of the account.
+    pub state: Option<HashMap<U256, U256>>,
+    /// Overrides only the given storage slots.
+    pub state_diff: HashMap<U256, U256>,
+}
+
+/// Changes to the state that are applied to [CacheDB] before transactions are executed,
+/// for example to fund an account or to replace the code of a contract in simulations.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StateOverride {
+    pub accounts: HashMap<B160, AccountOverride>,
+}
+
+impl StateOverride {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the overrides of the account, empty overrides are created if there are none.
+    pub fn account(&mut self, address: B160) -> &mut AccountOverride {
+        self.accounts.entry(address).or_default()
+    }
+
+    pub fn set_balance(&mut self, address: B160, balance: U256) -> &mut Self {
+        self.account(address).balance = Some(balance);
+        self
+    }
+
+    pub fn set_nonce(&mut self, address: B160, nonce: u64) -> &mut Self {
+        self.account(address).nonce = Some(nonce);
+        self
+    }
+
+    pub fn set_code(&mut self, address: B160, code: Bytes) -> &mut Self {
+        self.account(address).code = Some(code);
+        self
+    }
+
+    pub fn set_storage(&mut self, address: B160, slot: U256, value: U256) -> &mut Self {
+        self.account(address).state_diff.insert(slot, value);
+        self
+    }
+
+    /// Sets the value for `key` of the Solidity `mapping` at slot `base_slot`.
+    /// See [map_slot] for how the slot of the value is computed.
+    ///
+    /// ERC-20 balance of `holder` for a token that has balances at slot 0 is overridden with
+    /// `set_mapping_value(token, U256::ZERO, holder.into(), balance)`.
+    pub fn set_mapping_value(
+        &mut self,
+        address: B160,
+        base_slot: U256,
+        key: B256,
+        value: U256,
+    ) -> &mut Self {
+        self.set_storage(address, map_slot(base_slot, key), value)
+    }
+
+    /// Applies the overrides to the database. Accounts that are not cached are loaded first,
+    /// so fields that are not overridden keep their values.
+    pub fn apply<ExtDB: DatabaseRef>(&self, db: &mut CacheDB<ExtDB>) -> Result<(), ExtDB::Error> {
+        for (address, overrides) in &self.accounts {
+            let code_hash = overrides.code.as_ref().map(|code| {
+                let code = Bytecode::new_raw(code.clone());
+                let code_hash = code.hash();
+                db.contracts.insert(code_hash, code);
+                code_hash
+            });
+
+            let account = db.load_account(*address)?;
+            if account.account_state == AccountState::NotExisting {
+                // account is created by the override, it has no storage.
+                account.account_state = AccountState::StorageCleared;
+            }
+            if let Some(balance) = overrides.balance {
+                account.info.balance = balance;
+            }
+            if let Some(nonce) = overrides.nonce {
+                account.info.nonce = nonce;
+            }
+            if let Some(code_hash) = code_hash {
+                account.info.code_hash = code_hash;
+                account.info.code = None;
+            }
+            if let Some(state) = &overrides.state {
+                account.account_state = AccountState::StorageCleared;
+                account.storage = state.clone();
+            }
+            account.storage.extend(overrides.state_diff.iter());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::KECCAK_EMPTY;
+    use crate::test_utils::{contract, evm_calling};
+    use crate::InMemoryDB;
+
+    const TOKEN: B160 = B160([0x10; 20]);
+    const HOLDER: B160 = B160([0x01; 20]);
+
+    #[test]
+    fn set_mapping_value() {
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(TOKEN, contract(&[0x00]));
+        db.insert_account_storage(TOKEN, U256::from(7), U256::from(1))
+            .unwrap();
+
+        let mut overrides = StateOverride::new();
+        overrides
+            .set_balance(HOLDER, U256::from(100))
+            .set_mapping_value(TOKEN, U256::ZERO, HOLDER.into(), U256::from(5));
+        overrides.apply(&mut db).unwrap();
+
+        assert_eq!(db.basic(HOLDER).unwrap().unwrap().balance, U256::from(100));
+        assert_eq!(db.basic(HOLDER).unwrap().unwrap().code_hash, KECCAK_EMPTY);
+        let slot = map_slot(U256::ZERO, B256::from(HOLDER));
+        assert_eq!(db.storage(TOKEN, slot).unwrap(), U256::from(5));
+        // other slots and the code are kept.
+        assert_eq!(db.storage(TOKEN, U256::from(7)).unwrap(), U256::from(1));
+        let code_hash = db.basic(TOKEN).unwrap().unwrap().code_hash;
+        assert_eq!(db.code_by_hash(code_hash).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn override_code() {
+        // PUSH1 1 PUSH1 0 SSTORE
+        let code = Bytes::from_static(&[0x60, 0x01, 0x60, 0x00, 0x55]);
+        let mut overrides = StateOverride::new();
+        overrides.set_code(TOKEN, code);
+        let mut db = InMemoryDB::default();
+        overrides.apply(&mut db).unwrap();
+
+        let mut evm = evm_calling(db, TOKEN);
+        let state = evm.transact().unwrap().state;
+        assert_eq!(
+            state[&TOKEN].storage[&U256::ZERO].present_value,
+            U256::from(1)
+        );
+    }
+}
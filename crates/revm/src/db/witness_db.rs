@@ -0,0 +1,177 @@
+use super::{Database, DatabaseCommit, DbAccount, InMemoryDB};
+use crate::primitives::{
+    hash_map::Entry, Account, AccountInfo, Bytecode, HashMap, B160, B256, U256,
+};
+use alloc::vec::Vec;
+
+/// Key of the value that was read from the database.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WitnessKey {
+    Account(B160),
+    Storage(B160, U256),
+    Code(B256),
+    BlockHash(U256),
+}
+
+/// Database wrapper that records every value read from the wrapped database.
+///
+/// Only the first read of the key is recorded, so when changes are committed
+/// in between transactions, recorded values are the state before the first transaction.
+/// Recorded values can be exported with [`WitnessDB::to_in_memory_db`] as a database
+/// that is enough to execute the same transactions again without the original database.
+#[derive(Debug, Clone)]
+pub struct WitnessDB<DB> {
+    pub db: DB,
+    accounts: HashMap<B160, Option<AccountInfo>>,
+    storage: HashMap<(B160, U256), U256>,
+    contracts: HashMap<B256, Bytecode>,
+    block_hashes: HashMap<U256, B256>,
+}
+
+impl<DB> WitnessDB<DB> {
+    pub fn new(db: DB) -> Self {
+        Self {
+            db,
+            accounts: HashMap::new(),
+            storage: HashMap::new(),
+            contracts: HashMap::new(),
+            block_hashes: HashMap::new(),
+        }
+    }
+
+    pub fn into_inner(self) -> DB {
+        self.db
+    }
+
+    /// Returns all keys that were read, sorted.
+    pub fn accessed_keys(&self) -> Vec<WitnessKey> {
+        let mut keys: Vec<_> = self
+            .accounts
+            .keys()
+            .map(|address| WitnessKey::Account(*address))
+            .chain(
+                self.storage
+                    .keys()
+                    .map(|(address, index)| WitnessKey::Storage(*address, *index)),
+            )
+            .chain(self.contracts.keys().map(|hash| WitnessKey::Code(*hash)))
+            .chain(
+                self.block_hashes
+                    .keys()
+                    .map(|number| WitnessKey::BlockHash(*number)),
+            )
+            .collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    /// Creates database that contains only recorded values.
+    ///
+    /// Accounts that did not exist are marked as not existing, so their storage
+    /// is not requested from the empty underlying database.
+    pub fn to_in_memory_db(&self) -> InMemoryDB {
+        let mut db = InMemoryDB::default();
+        db.contracts.extend(
+            self.contracts
+                .iter()
+                .map(|(hash, code)| (*hash, code.clone())),
+        );
+        db.block_hashes.extend(self.block_hashes.iter());
+        for (address, info) in &self.accounts {
+            match info {
+                Some(info) => db.insert_account_info(*address, info.clone()),
+                None => {
+                    db.accounts.insert(*address, DbAccount::new_not_existing());
+                }
+            }
+        }
+        for ((address, index), value) in &self.storage {
+            db.accounts
+                .entry(*address)
+                .or_default()
+                .storage
+                .insert(*index, *value);
+        }
+        db
+    }
+}
+
+impl<DB: Database> Database for WitnessDB<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: B160) -> Result<Option<AccountInfo>, Self::Error> {
+        let info = self.db.basic(address)?;
+        if let Entry::Vacant(entry) = self.accounts.entry(address) {
+            if let Some(code) = info.as_ref().and_then(|info| info.code.as_ref()) {
+                self.contracts
+                    .entry(code.hash())
+                    .or_insert_with(|| code.clone());
+            }
+            entry.insert(info.clone());
+        }
+        Ok(info)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        let code = self.db.code_by_hash(code_hash)?;
+        self.contracts
+            .entry(code_hash)
+            .or_insert_with(|| code.clone());
+        Ok(code)
+    }
+
+    fn storage(&mut self, address: B160, index: U256) -> Result<U256, Self::Error> {
+        let value = self.db.storage(address, index)?;
+        self.storage.entry((address, index)).or_insert(value);
+        Ok(value)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        let hash = self.db.block_hash(number)?;
+        self.block_hashes.entry(number).or_insert(hash);
+        Ok(hash)
+    }
+}
+
+impl<DB: DatabaseCommit> DatabaseCommit for WitnessDB<DB> {
+    fn commit(&mut self, changes: HashMap<B160, Account>) {
+        self.db.commit(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{contract, evm_calling, CALLER, OUTER};
+
+    fn transact<DB: Database>(db: DB) -> crate::primitives::ResultAndState {
+        let mut evm = evm_calling(db, OUTER);
+        evm.env.block.number = U256::from(10);
+        evm.transact().ok().unwrap()
+    }
+
+    #[test]
+    fn replay_on_exported_db() {
+        let mut backend = InMemoryDB::default();
+        // PUSH1 0 SLOAD PUSH1 9 BLOCKHASH ADD PUSH1 0 SSTORE PUSH1 1 SLOAD POP
+        let code = [
+            0x60, 0x00, 0x54, 0x60, 0x09, 0x40, 0x01, 0x60, 0x00, 0x55, 0x60, 0x01, 0x54, 0x50,
+        ];
+        backend.insert_account_info(OUTER, contract(&code));
+        backend
+            .insert_account_storage(OUTER, U256::ZERO, U256::from(5))
+            .unwrap();
+
+        let mut db = WitnessDB::new(&mut backend);
+        let expected = transact(&mut db);
+        let keys = db.accessed_keys();
+        assert!(keys.contains(&WitnessKey::Storage(OUTER, U256::ZERO)));
+        assert!(keys.contains(&WitnessKey::Storage(OUTER, U256::from(1))));
+        assert!(keys.contains(&WitnessKey::BlockHash(U256::from(9))));
+        // caller does not exist.
+        assert!(keys.contains(&WitnessKey::Account(CALLER)));
+
+        let exported = db.to_in_memory_db();
+        assert_eq!(transact(exported), expected);
+    }
+}
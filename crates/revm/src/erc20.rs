@@ -0,0 +1,358 @@
+use crate::db::{CacheDB, DatabaseRef, StateOverride};
+use crate::evm_inner;
+use crate::inspectors::NoOpInspector;
+use crate::primitives::{
+    keccak256, map_slot, Bytes, EVMError, Env, ExecutionResult, Output, TransactTo, TxEnv, B160,
+    B256, U256,
+};
+use alloc::{vec, vec::Vec};
+
+/// Number of storage slots that are probed for the mapping, `0..PROBED_SLOTS`.
+pub const PROBED_SLOTS: u64 = 100;
+
+/// Value written to the probed slots, it is distinct enough to change any balance.
+const PROBE_VALUE: U256 = U256::from_limbs([0x1357_9bdf_2468_ace0, 1, 0, 0]);
+
+/// Gas limit of `balanceOf` and `allowance` calls.
+const CALL_GAS_LIMIT: u64 = 1_000_000;
+
+/// How the slot of the value is computed from the slot of the mapping and the key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MappingLayout {
+    /// `keccak256(key . slot)`, see [map_slot].
+    Solidity,
+    /// `keccak256(slot . key)`.
+    Vyper,
+}
+
+impl MappingLayout {
+    pub fn slot(&self, base_slot: U256, key: B256) -> U256 {
+        match self {
+            Self::Solidity => map_slot(base_slot, key),
+            Self::Vyper => {
+                let mut input = [0u8; 64];
+                input[..32].copy_from_slice(&base_slot.to_be_bytes::<{ U256::BYTES }>());
+                input[32..].copy_from_slice(&key[..]);
+                U256::from_be_bytes(keccak256(&input).0)
+            }
+        }
+    }
+}
+
+/// Storage slot of the `balanceOf` or `allowance` mapping of the token.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TokenSlot {
+    pub base_slot: U256,
+    pub layout: MappingLayout,
+}
+
+impl TokenSlot {
+    /// Slot of the balance of `holder`, if this is the `balanceOf` mapping.
+    pub fn balance_slot(&self, holder: B160) -> U256 {
+        self.layout.slot(self.base_slot, holder.into())
+    }
+
+    /// Slot of the amount `spender` can spend from `owner`, if this is the `allowance` mapping.
+    pub fn allowance_slot(&self, owner: B160, spender: B160) -> U256 {
+        let owner_slot = self.layout.slot(self.base_slot, owner.into());
+        self.layout.slot(owner_slot, spender.into())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Erc20Error<DBError> {
+    Evm(EVMError<DBError>),
+    /// `balanceOf` or `allowance` call did not return a value.
+    CallFailed,
+    /// None of the probed slots changed the returned value.
+    SlotNotFound,
+    /// Value returned after the override is not the overridden value,
+    /// token does not store balances as they are returned (e.g. rebasing tokens).
+    ValueMismatch {
+        expected: U256,
+        actual: Option<U256>,
+    },
+}
+
+impl<DBError> From<EVMError<DBError>> for Erc20Error<DBError> {
+    fn from(error: EVMError<DBError>) -> Self {
+        Self::Evm(error)
+    }
+}
+
+/// Finds the slot of the `balanceOf` mapping of the `token`.
+///
+/// See [override_balance] for how the slot is found.
+pub fn find_balance_slot<ExtDB: DatabaseRef>(
+    db: &CacheDB<ExtDB>,
+    env: &Env,
+    token: B160,
+    holder: B160,
+) -> Result<TokenSlot, Erc20Error<ExtDB::Error>> {
+    let input = balance_of_input(holder);
+    find_slot(db, env, token, &input, |slot| slot.balance_slot(holder))
+}
+
+/// Finds the slot of the `allowance` mapping of the `token`.
+///
+/// See [override_balance] for how the slot is found.
+pub fn find_allowance_slot<ExtDB: DatabaseRef>(
+    db: &CacheDB<ExtDB>,
+    env: &Env,
+    token: B160,
+    owner: B160,
+    spender: B160,
+) -> Result<TokenSlot, Erc20Error<ExtDB::Error>> {
+    let input = allowance_input(owner, spender);
+    find_slot(db, env, token, &input, |slot| {
+        slot.allowance_slot(owner, spender)
+    })
+}
+
+/// Sets ERC-20 balance of the `holder` to `balance`, for example to simulate a swap from
+/// an account that does not have the tokens.
+///
+/// Slot of the balance is not known, so it is found by probing: values are written to
+/// candidate slots (slots `0..PROBED_SLOTS` with both [MappingLayout]s) in a scratch copy of
+/// the database and `balanceOf` is called to see if the returned balance changed. Candidates
+/// are halved until one slot is left. Balance is then written to the found slot and the
+/// override is applied to `db` only if `balanceOf` returns the new balance.
+///
+/// Returns the found slot, so it can be reused for other holders without probing.
+pub fn override_balance<ExtDB: DatabaseRef>(
+    db: &mut CacheDB<ExtDB>,
+    env: &Env,
+    token: B160,
+    holder: B160,
+    balance: U256,
+) -> Result<TokenSlot, Erc20Error<ExtDB::Error>> {
+    let slot = find_balance_slot(db, env, token, holder)?;
+    let input = balance_of_input(holder);
+    override_value(db, env, token, &input, slot.balance_slot(holder), balance)?;
+    Ok(slot)
+}
+
+/// Sets the amount `spender` can spend from `owner` to `amount`.
+///
+/// Slot is found the same way as in [override_balance].
+pub fn override_allowance<ExtDB: DatabaseRef>(
+    db: &mut CacheDB<ExtDB>,
+    env: &Env,
+    token: B160,
+    owner: B160,
+    spender: B160,
+    amount: U256,
+) -> Result<TokenSlot, Erc20Error<ExtDB::Error>> {
+    let slot = find_allowance_slot(db, env, token, owner, spender)?;
+    let input = allowance_input(owner, spender);
+    let storage_slot = slot.allowance_slot(owner, spender);
+    override_value(db, env, token, &input, storage_slot, amount)?;
+    Ok(slot)
+}
+
+fn find_slot<ExtDB: DatabaseRef>(
+    db: &CacheDB<ExtDB>,
+    env: &Env,
+    token: B160,
+    input: &Bytes,
+    storage_slot: impl Fn(&TokenSlot) -> U256,
+) -> Result<TokenSlot, Erc20Error<ExtDB::Error>> {
+    let original = call(db, env, token, input, &[])?.ok_or(Erc20Error::CallFailed)?;
+
+    // value is changed if writing to any of the candidates changes the returned value.
+    let changes_value = |candidates: &[TokenSlot]| -> Result<bool, Erc20Error<ExtDB::Error>> {
+        let storage: Vec<_> = candidates
+            .iter()
+            .map(|slot| (storage_slot(slot), PROBE_VALUE))
+            .collect();
+        Ok(call(db, env, token, input, &storage)? != Some(original))
+    };
+
+    let mut candidates: Vec<_> = [MappingLayout::Solidity, MappingLayout::Vyper]
+        .into_iter()
+        .flat_map(|layout| {
+            (0..PROBED_SLOTS).map(move |base_slot| TokenSlot {
+                base_slot: U256::from(base_slot),
+                layout,
+            })
+        })
+        .collect();
+    if !changes_value(&candidates)? {
+        return Err(Erc20Error::SlotNotFound);
+    }
+    while candidates.len() > 1 {
+        let second_half = candidates.split_off(candidates.len() / 2);
+        if !changes_value(&candidates)? {
+            candidates = second_half;
+        }
+    }
+    candidates.pop().ok_or(Erc20Error::SlotNotFound)
+}
+
+/// Writes `value` to the slot and applies it to `db` if the token returns it.
+fn override_value<ExtDB: DatabaseRef>(
+    db: &mut CacheDB<ExtDB>,
+    env: &Env,
+    token: B160,
+    input: &Bytes,
+    storage_slot: U256,
+    value: U256,
+) -> Result<(), Erc20Error<ExtDB::Error>> {
+    let actual = call(db, env, token, input, &[(storage_slot, value)])?;
+    if actual != Some(value) {
+        return Err(Erc20Error::ValueMismatch {
+            expected: value,
+            actual,
+        });
+    }
+    let mut overrides = StateOverride::new();
+    overrides.set_storage(token, storage_slot, value);
+    overrides
+        .apply(db)
+        .map_err(|e| Erc20Error::Evm(EVMError::Database(e)))
+}
+
+/// Calls the token with `storage` written to a scratch copy of `db`,
+/// returns the first word of the output if the call succeeded.
+fn call<ExtDB: DatabaseRef>(
+    db: &CacheDB<ExtDB>,
+    env: &Env,
+    token: B160,
+    input: &Bytes,
+    storage: &[(U256, U256)],
+) -> Result<Option<U256>, EVMError<ExtDB::Error>> {
+    let mut scratch = CacheDB::new(db);
+    for (slot, value) in storage {
+        scratch
+            .insert_account_storage(token, *slot, *value)
+            .map_err(EVMError::Database)?;
+    }
+
+    let mut env = env.clone();
+    // call is free, so the zero address can make it.
+    env.block.basefee = U256::ZERO;
+    env.tx = TxEnv {
+        gas_limit: CALL_GAS_LIMIT,
+        transact_to: TransactTo::Call(token),
+        data: input.clone(),
+        ..Default::default()
+    };
+    let result = evm_inner::<_, false>(&mut env, &mut scratch, &mut NoOpInspector())
+        .transact()?
+        .result;
+    Ok(match result {
+        ExecutionResult::Success {
+            output: Output::Call(output),
+            ..
+        } => output.get(..32).and_then(U256::try_from_be_slice),
+        _ => None,
+    })
+}
+
+/// Input of `balanceOf(address)`.
+fn balance_of_input(holder: B160) -> Bytes {
+    let mut input = vec![0x70, 0xa0, 0x82, 0x31];
+    input.extend_from_slice(B256::from(holder).as_bytes());
+    input.into()
+}
+
+/// Input of `allowance(address,address)`.
+fn allowance_input(owner: B160, spender: B160) -> Bytes {
+    let mut input = vec![0xdd, 0x62, 0xed, 0x3e];
+    input.extend_from_slice(B256::from(owner).as_bytes());
+    input.extend_from_slice(B256::from(spender).as_bytes());
+    input.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::contract;
+    use crate::InMemoryDB;
+
+    const TOKEN: B160 = B160([0x10; 20]);
+    const HOLDER: B160 = B160([0x01; 20]);
+
+    /// Token that returns the value of the mapping at `base_slot` for the first argument.
+    fn token_db(base_slot: u8, layout: MappingLayout) -> InMemoryDB {
+        // key is stored at memory 0 for Solidity layout and at 32 for Vyper layout.
+        let (key_offset, slot_offset) = match layout {
+            MappingLayout::Solidity => (0x00, 0x20),
+            MappingLayout::Vyper => (0x20, 0x00),
+        };
+        // CALLDATALOAD(4) MSTORE, base_slot MSTORE, SLOAD(KECCAK256(0, 64)), return it.
+        let code = vec![
+            0x60,
+            0x04,
+            0x35,
+            0x60,
+            key_offset,
+            0x52,
+            0x60,
+            base_slot,
+            0x60,
+            slot_offset,
+            0x52,
+            0x60,
+            0x40,
+            0x60,
+            0x00,
+            0x20,
+            0x54,
+            0x60,
+            0x00,
+            0x52,
+            0x60,
+            0x20,
+            0x60,
+            0x00,
+            0xf3,
+        ];
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(TOKEN, contract(&code));
+        db
+    }
+
+    #[test]
+    fn override_balance_of_solidity_token() {
+        let mut db = token_db(3, MappingLayout::Solidity);
+        let env = Env::default();
+        let slot = override_balance(&mut db, &env, TOKEN, HOLDER, U256::from(1000)).unwrap();
+        assert_eq!(
+            slot,
+            TokenSlot {
+                base_slot: U256::from(3),
+                layout: MappingLayout::Solidity
+            }
+        );
+        assert_eq!(
+            call(&db, &env, TOKEN, &balance_of_input(HOLDER), &[]).unwrap(),
+            Some(U256::from(1000))
+        );
+    }
+
+    #[test]
+    fn override_balance_of_vyper_token() {
+        let mut db = token_db(5, MappingLayout::Vyper);
+        let env = Env::default();
+        let slot = override_balance(&mut db, &env, TOKEN, HOLDER, U256::from(7)).unwrap();
+        assert_eq!(slot.base_slot, U256::from(5));
+        assert_eq!(slot.layout, MappingLayout::Vyper);
+        assert_eq!(
+            db.accounts[&TOKEN].storage[&slot.balance_slot(HOLDER)],
+            U256::from(7)
+        );
+    }
+
+    #[test]
+    fn slot_not_found() {
+        // PUSH1 7 PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+        let code = [0x60, 0x07, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3];
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(TOKEN, contract(&code));
+        assert_eq!(
+            override_balance(&mut db, &Env::default(), TOKEN, HOLDER, U256::from(1)),
+            Err(Erc20Error::SlotNotFound)
+        );
+    }
+}
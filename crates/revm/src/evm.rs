@@ -1,12 +1,16 @@
-use crate::primitives::{specification, EVMError, EVMResult, Env, ExecutionResult, SpecId};
+use crate::primitives::{
+    specification, EVMError, EVMResult, Env, ExecutionResult, InvalidTransaction, Log, SpecId,
+};
 use crate::{
+    artifacts::ExecutionArtifacts,
     db::{Database, DatabaseCommit, DatabaseRef, RefDBWrapper},
-    evm_impl::{EVMImpl, Transact},
-    inspectors::NoOpInspector,
+    evm_impl::{validate_env_with_spec, EVMImpl, Transact},
+    inspectors::{CallTracer, NoOpInspector},
     Inspector,
 };
-use alloc::boxed::Box;
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use revm_interpreter::primitives::ResultAndState;
+use revm_interpreter::MemoryPool;
 use revm_precompile::Precompiles;
 
 /// Struct that takes Database and enabled transact to update state directly to database.
@@ -25,12 +29,38 @@ use revm_precompile::Precompiles;
 /// want to update anything on it. It enabled `transact_ref` and `inspect_ref` functions
 /// * Database+DatabaseCommit allow directly committing changes of transaction. it enabled `transact_commit`
 /// and `inspect_commit`
+///
+/// Interpreter memories are pooled in `memory_pool` and reused between transactions executed
+/// with `transact` and `inspect`.
+///
+/// In block building mode (see [EVM::enable_block_building]) transactions that have gas limit
+/// bigger than the gas left in the block are rejected before execution.
+///
+/// Logs of successful transactions can be filtered or modified before they are returned
+/// by setting [EVM::set_logs_hook].
+///
+/// EVM is `Send` and `Sync` if `DB` is, so it can be moved to a worker thread and reused there.
+/// See `SyncEvmPool` for sharing EVMs between worker threads.
 #[derive(Clone)]
 pub struct EVM<DB> {
     pub env: Env,
     pub db: Option<DB>,
+    pub memory_pool: MemoryPool,
+    /// Gas used by transactions committed in block building mode.
+    /// `None` if block building mode is disabled.
+    pub block_gas_used: Option<u64>,
+    /// Index in the block of the next transaction, passed to [Inspector::initialize].
+    /// It is incremented when a transaction is committed.
+    pub tx_index: usize,
+    /// Hook that post-processes logs before the result is returned.
+    pub logs_hook: Option<LogsHook>,
 }
 
+/// Hook called with the environment and logs of the successful transaction.
+///
+/// Logs are changed in place, e.g. dropped with `Vec::retain` or their data cleared.
+pub type LogsHook = Arc<dyn Fn(&Env, &mut Vec<Log>) + Send + Sync>;
+
 pub fn new<DB>() -> EVM<DB> {
     EVM::new()
 }
@@ -44,8 +74,11 @@ impl<DB> Default for EVM<DB> {
 impl<DB: Database + DatabaseCommit> EVM<DB> {
     /// Execute transaction and apply result to database
     pub fn transact_commit(&mut self) -> Result<ExecutionResult, EVMError<DB::Error>> {
-        let ResultAndState { result, state } = self.transact()?;
-        self.db.as_mut().unwrap().commit(state);
+        let ResultAndState { result, state, .. } = self.transact()?;
+        if let Some(db) = self.db.as_mut() {
+            db.commit(state);
+        }
+        self.record_block_gas(result.gas_used());
         Ok(result)
     }
     /// Inspect transaction and commit changes to database.
@@ -53,8 +86,11 @@ impl<DB: Database + DatabaseCommit> EVM<DB> {
         &mut self,
         inspector: INSP,
     ) -> Result<ExecutionResult, EVMError<DB::Error>> {
-        let ResultAndState { result, state } = self.inspect(inspector)?;
-        self.db.as_mut().unwrap().commit(state);
+        let ResultAndState { result, state, .. } = self.inspect(inspector)?;
+        if let Some(db) = self.db.as_mut() {
+            db.commit(state);
+        }
+        self.record_block_gas(result.gas_used());
         Ok(result)
     }
 }
@@ -62,28 +98,62 @@ impl<DB: Database + DatabaseCommit> EVM<DB> {
 impl<DB: Database> EVM<DB> {
     /// Execute transaction without writing to DB, return change state.
     pub fn transact(&mut self) -> EVMResult<DB::Error> {
+        self.check_block_gas()?;
         if let Some(db) = self.db.as_mut() {
             let mut noop = NoOpInspector {};
-            let out = evm_inner::<DB, false>(&mut self.env, db, &mut noop).transact();
-            out
+            self.memory_pool.reset_peak();
+            let out = evm_inner_with_memory_pool::<DB, false>(
+                &mut self.env,
+                db,
+                &mut noop,
+                Some(&mut self.memory_pool),
+            )
+            .transact();
+            self.apply_logs_hook(out)
         } else {
-            panic!("Database needs to be set");
+            Err(EVMError::DatabaseNotSet)
         }
     }
 
     /// Execute transaction with given inspector, without wring to DB. Return change state.
     pub fn inspect<INSP: Inspector<DB>>(&mut self, mut inspector: INSP) -> EVMResult<DB::Error> {
+        self.check_block_gas()?;
         if let Some(db) = self.db.as_mut() {
-            evm_inner::<DB, true>(&mut self.env, db, &mut inspector).transact()
+            inspector.initialize(&self.env, self.tx_index);
+            self.memory_pool.reset_peak();
+            let out = evm_inner_with_memory_pool::<DB, true>(
+                &mut self.env,
+                db,
+                &mut inspector,
+                Some(&mut self.memory_pool),
+            )
+            .transact();
+            self.apply_logs_hook(out)
         } else {
-            panic!("Database needs to be set");
+            Err(EVMError::DatabaseNotSet)
         }
     }
+
+    /// Execute transaction without writing to DB and return the result, state changes,
+    /// accessed state and metrics together. Call trace is collected if `trace` is true.
+    pub fn transact_artifacts(
+        &mut self,
+        trace: bool,
+    ) -> Result<ExecutionArtifacts, EVMError<DB::Error>> {
+        let mut tracer = trace.then(CallTracer::new);
+        let out = match tracer.as_mut() {
+            Some(tracer) => self.inspect(tracer)?,
+            None => self.transact()?,
+        };
+        let trace = tracer.and_then(|tracer| tracer.into_trace(&out.result));
+        Ok(ExecutionArtifacts::new(out, trace, self.peak_memory()))
+    }
 }
 
 impl<'a, DB: DatabaseRef> EVM<DB> {
     /// Execute transaction without writing to DB, return change state.
     pub fn transact_ref(&self) -> EVMResult<DB::Error> {
+        self.check_block_gas()?;
         if let Some(db) = self.db.as_ref() {
             let mut noop = NoOpInspector {};
             let mut db = RefDBWrapper::new(db);
@@ -91,9 +161,9 @@ impl<'a, DB: DatabaseRef> EVM<DB> {
             let out =
                 evm_inner::<RefDBWrapper<DB::Error>, false>(&mut self.env.clone(), db, &mut noop)
                     .transact();
-            out
+            self.apply_logs_hook(out)
         } else {
-            panic!("Database needs to be set");
+            Err(EVMError::DatabaseNotSet)
         }
     }
 
@@ -102,7 +172,9 @@ impl<'a, DB: DatabaseRef> EVM<DB> {
         &'a self,
         mut inspector: INSP,
     ) -> EVMResult<DB::Error> {
+        self.check_block_gas()?;
         if let Some(db) = self.db.as_ref() {
+            inspector.initialize(&self.env, self.tx_index);
             let mut db = RefDBWrapper::new(db);
             let db = &mut db;
             let out = evm_inner::<RefDBWrapper<DB::Error>, true>(
@@ -111,9 +183,9 @@ impl<'a, DB: DatabaseRef> EVM<DB> {
                 &mut inspector,
             )
             .transact();
-            out
+            self.apply_logs_hook(out)
         } else {
-            panic!("Database needs to be set");
+            Err(EVMError::DatabaseNotSet)
         }
     }
 }
@@ -126,7 +198,105 @@ impl<DB> EVM<DB> {
 
     /// Creates a new [EVM] instance with the given environment.
     pub fn with_env(env: Env) -> Self {
-        Self { env, db: None }
+        Self {
+            env,
+            db: None,
+            memory_pool: MemoryPool::new(),
+            block_gas_used: None,
+            tx_index: 0,
+            logs_hook: None,
+        }
+    }
+
+    /// Enables block building mode and resets the block gas used and the transaction index.
+    ///
+    /// Gas used by transactions executed with `transact_commit` and `inspect_commit` is accumulated
+    /// and transactions with gas limit bigger than the remaining block gas are rejected
+    /// with [InvalidTransaction::BlockGasExceeded].
+    pub fn enable_block_building(&mut self) {
+        self.block_gas_used = Some(0);
+        self.tx_index = 0;
+    }
+
+    /// Disables block building mode.
+    pub fn disable_block_building(&mut self) {
+        self.block_gas_used = None;
+    }
+
+    /// Gas left in the block in block building mode.
+    pub fn remaining_block_gas(&self) -> Option<u64> {
+        self.block_gas_used.map(|used| {
+            let block_gas_limit = self.env.block.gas_limit.saturating_to::<u64>();
+            block_gas_limit.saturating_sub(used)
+        })
+    }
+
+    /// Moves to the next transaction index and adds `gas_used` to the block gas used
+    /// if block building mode is enabled.
+    ///
+    /// It is called by `transact_commit` and `inspect_commit`, if transaction result from
+    /// `transact` is added to the block this needs to be called manually.
+    pub fn record_block_gas(&mut self, gas_used: u64) {
+        self.tx_index += 1;
+        if let Some(used) = self.block_gas_used.as_mut() {
+            *used = used.saturating_add(gas_used);
+        }
+    }
+
+    /// Checks that transaction gas limit fits inside remaining block gas.
+    fn check_block_gas<E>(&self) -> Result<(), EVMError<E>> {
+        match self.remaining_block_gas() {
+            Some(remaining) if self.env.tx.gas_limit > remaining => {
+                Err(InvalidTransaction::BlockGasExceeded {
+                    gas_limit: self.env.tx.gas_limit,
+                    remaining,
+                }
+                .into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Sets the hook that post-processes logs of successful transactions executed with
+    /// `transact`, `inspect` and their `_ref` and `_commit` variants.
+    pub fn set_logs_hook<F>(&mut self, hook: F)
+    where
+        F: Fn(&Env, &mut Vec<Log>) + Send + Sync + 'static,
+    {
+        self.logs_hook = Some(Arc::new(hook));
+    }
+
+    fn apply_logs_hook<E>(&self, mut out: EVMResult<E>) -> EVMResult<E> {
+        if let (
+            Some(hook),
+            Ok(ResultAndState {
+                result: ExecutionResult::Success { logs, .. },
+                ..
+            }),
+        ) = (&self.logs_hook, &mut out)
+        {
+            hook(&self.env, logs);
+        }
+        out
+    }
+
+    /// Sets the capacity of newly allocated interpreter memories.
+    ///
+    /// Memory heavy workloads can set it higher to avoid reallocations on memory expansion.
+    pub fn set_memory_initial_capacity(&mut self, initial_capacity: usize) {
+        self.memory_pool.set_initial_capacity(initial_capacity);
+    }
+
+    /// Sets the limit of the total size of interpreter memories kept for reuse,
+    /// see [MemoryPool::set_max_pooled_bytes].
+    pub fn set_memory_pool_limit(&mut self, max_pooled_bytes: usize) {
+        self.memory_pool.set_max_pooled_bytes(max_pooled_bytes);
+    }
+
+    /// Peak interpreter memory size in bytes of the last transaction executed
+    /// with `transact` or `inspect`.
+    pub fn peak_memory(&self) -> usize {
+        self.memory_pool.peak()
     }
 
     pub fn database(&mut self, db: DB) {
@@ -137,18 +307,20 @@ impl<DB> EVM<DB> {
         self.db.as_mut()
     }
 
-    pub fn take_db(&mut self) -> DB {
-        core::mem::take(&mut self.db).unwrap()
+    /// Takes the database out of the EVM, returns `None` if it is not set.
+    pub fn take_db(&mut self) -> Option<DB> {
+        core::mem::take(&mut self.db)
     }
 }
 
 macro_rules! create_evm {
-    ($spec:ident, $db:ident,$env:ident,$inspector:ident) => {
+    ($spec:ident, $db:ident,$env:ident,$inspector:ident,$memory_pool:ident) => {
         Box::new(EVMImpl::<'a, $spec, DB, INSPECT>::new(
             $db,
             $env,
             $inspector,
             Precompiles::new(to_precompile_id($spec::SPEC_ID)).clone(),
+            $memory_pool,
         )) as Box<dyn Transact<DB::Error> + 'a>
     };
 }
@@ -181,22 +353,470 @@ pub fn evm_inner<'a, DB: Database, const INSPECT: bool>(
     db: &'a mut DB,
     insp: &'a mut dyn Inspector<DB>,
 ) -> Box<dyn Transact<DB::Error> + 'a> {
-    use specification::*;
-    match env.cfg.spec_id {
-        SpecId::FRONTIER | SpecId::FRONTIER_THAWING => create_evm!(FrontierSpec, db, env, insp),
-        SpecId::HOMESTEAD | SpecId::DAO_FORK => create_evm!(HomesteadSpec, db, env, insp),
-        SpecId::TANGERINE => create_evm!(TangerineSpec, db, env, insp),
-        SpecId::SPURIOUS_DRAGON => create_evm!(SpuriousDragonSpec, db, env, insp),
-        SpecId::BYZANTIUM => create_evm!(ByzantiumSpec, db, env, insp),
-        SpecId::PETERSBURG | SpecId::CONSTANTINOPLE => create_evm!(PetersburgSpec, db, env, insp),
-        SpecId::ISTANBUL | SpecId::MUIR_GLACIER => create_evm!(IstanbulSpec, db, env, insp),
-        SpecId::BERLIN => create_evm!(BerlinSpec, db, env, insp),
-        SpecId::LONDON | SpecId::ARROW_GLACIER | SpecId::GRAY_GLACIER => {
-            create_evm!(LondonSpec, db, env, insp)
-        }
-        SpecId::MERGE => create_evm!(MergeSpec, db, env, insp),
-        SpecId::SHANGHAI => create_evm!(ShanghaiSpec, db, env, insp),
-        SpecId::CANCUN => create_evm!(CancunSpec, db, env, insp),
-        SpecId::LATEST => create_evm!(LatestSpec, db, env, insp),
+    evm_inner_with_memory_pool::<DB, INSPECT>(env, db, insp, None)
+}
+
+/// Evaluates `$body` with `$spec` being the [specification] type of `$spec_id`.
+macro_rules! with_spec {
+    ($spec_id:expr, $spec:ident => $body:expr) => {{
+        use specification::*;
+        match $spec_id {
+            SpecId::FRONTIER | SpecId::FRONTIER_THAWING => {
+                type $spec = FrontierSpec;
+                $body
+            }
+            SpecId::HOMESTEAD | SpecId::DAO_FORK => {
+                type $spec = HomesteadSpec;
+                $body
+            }
+            SpecId::TANGERINE => {
+                type $spec = TangerineSpec;
+                $body
+            }
+            SpecId::SPURIOUS_DRAGON => {
+                type $spec = SpuriousDragonSpec;
+                $body
+            }
+            SpecId::BYZANTIUM => {
+                type $spec = ByzantiumSpec;
+                $body
+            }
+            SpecId::PETERSBURG | SpecId::CONSTANTINOPLE => {
+                type $spec = PetersburgSpec;
+                $body
+            }
+            SpecId::ISTANBUL | SpecId::MUIR_GLACIER => {
+                type $spec = IstanbulSpec;
+                $body
+            }
+            SpecId::BERLIN => {
+                type $spec = BerlinSpec;
+                $body
+            }
+            SpecId::LONDON | SpecId::ARROW_GLACIER | SpecId::GRAY_GLACIER => {
+                type $spec = LondonSpec;
+                $body
+            }
+            SpecId::MERGE => {
+                type $spec = MergeSpec;
+                $body
+            }
+            SpecId::SHANGHAI => {
+                type $spec = ShanghaiSpec;
+                $body
+            }
+            SpecId::CANCUN => {
+                type $spec = CancunSpec;
+                $body
+            }
+            SpecId::LATEST => {
+                type $spec = LatestSpec;
+                $body
+            }
+        }
+    }};
+}
+
+/// Same as [evm_inner] but interpreter memories are taken from and returned to the `memory_pool`.
+pub fn evm_inner_with_memory_pool<'a, DB: Database, const INSPECT: bool>(
+    env: &'a mut Env,
+    db: &'a mut DB,
+    insp: &'a mut dyn Inspector<DB>,
+    memory_pool: Option<&'a mut MemoryPool>,
+) -> Box<dyn Transact<DB::Error> + 'a> {
+    with_spec!(env.cfg.spec_id, SPEC => create_evm!(SPEC, db, env, insp, memory_pool))
+}
+
+/// Stateless validation of the block and transaction of `env`, with the rules of
+/// `env.cfg.spec_id`. Returns the initial gas of the transaction.
+///
+/// It does the same checks as the EVM before execution, so it can be used by transaction
+/// pools without creating the EVM. See [validate_env_with_spec].
+pub fn validate_env<DBError>(env: &Env) -> Result<u64, EVMError<DBError>> {
+    with_spec!(env.cfg.spec_id, SPEC => validate_env_with_spec::<SPEC, DBError>(env))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::{CallInputs, Gas, InstructionResult};
+    use crate::primitives::{
+        AccountInfo, Bytes, Halt, InternalError, InvalidTransaction, TransactTo, B160, U256,
+    };
+    use crate::test_utils::{evm_calling, evm_with_contract, CALLER, OUTER};
+    use crate::InMemoryDB;
+
+    #[test]
+    fn block_building_rejects_tx_over_remaining_gas() {
+        let mut evm = evm_calling(InMemoryDB::default(), OUTER);
+        evm.env.block.gas_limit = U256::from(50_000);
+        evm.env.tx.gas_limit = 30_000;
+        evm.enable_block_building();
+
+        let result = evm.transact_commit().unwrap();
+        assert_eq!(result.gas_used(), 21_000);
+        assert_eq!(evm.remaining_block_gas(), Some(29_000));
+
+        assert_eq!(
+            evm.transact_commit(),
+            Err(EVMError::Transaction(
+                InvalidTransaction::BlockGasExceeded {
+                    gas_limit: 30_000,
+                    remaining: 29_000,
+                }
+            ))
+        );
+
+        evm.disable_block_building();
+        assert!(evm.transact_commit().is_ok());
+    }
+
+    #[test]
+    fn database_not_set() {
+        let mut evm = EVM::<InMemoryDB>::new();
+        assert_eq!(evm.transact().err(), Some(EVMError::DatabaseNotSet));
+        assert_eq!(evm.transact_ref().err(), Some(EVMError::DatabaseNotSet));
+        assert!(evm.take_db().is_none());
+    }
+
+    #[test]
+    fn logs_hook() {
+        // three times LOG0 with one byte of memory.
+        let mut evm = evm_with_contract(&[0x60, 0x01, 0x60, 0x00, 0xa0].repeat(3));
+        evm.set_logs_hook(|_, logs| {
+            logs.truncate(2);
+            logs.iter_mut().for_each(|log| log.data = Bytes::new());
+        });
+
+        let logs = evm.transact().unwrap().result.into_logs();
+        assert_eq!(logs.len(), 2);
+        assert!(logs.iter().all(|log| log.data.is_empty()));
+        assert_eq!(evm.transact_ref().unwrap().result.into_logs().len(), 2);
+
+        evm.logs_hook = None;
+        assert_eq!(evm.transact().unwrap().result.into_logs().len(), 3);
+    }
+
+    #[test]
+    fn selfdestruct_to_self_burns_balance() {
+        // ADDRESS SELFDESTRUCT
+        let mut evm = evm_with_contract(&[0x30, 0xff]);
+        evm.db()
+            .unwrap()
+            .accounts
+            .get_mut(&OUTER)
+            .unwrap()
+            .info
+            .balance = U256::from(100);
+        let state = evm.transact().unwrap().state;
+        assert!(state[&OUTER].is_selfdestructed());
+        assert_eq!(state[&OUTER].info.balance, U256::ZERO);
+
+        #[cfg(feature = "optional_selfdestruct_to_self")]
+        {
+            evm.env.cfg.selfdestruct_to_self = crate::primitives::SelfdestructToSelf::Keep;
+            let state = evm.transact().unwrap().state;
+            assert!(!state[&OUTER].is_selfdestructed());
+            assert_eq!(state[&OUTER].info.balance, U256::from(100));
+        }
+    }
+
+    #[test]
+    fn changeset_of_create() {
+        use crate::primitives::state_diff::{encode_changeset, CHANGESET_CREATED};
+
+        // MSTORE(0, 0x6000) RETURN(30, 2) deploys `PUSH1 0x00`.
+        let mut evm = evm_calling(InMemoryDB::default(), OUTER);
+        evm.env.tx.transact_to = TransactTo::create();
+        evm.env.tx.data = Bytes::from_static(&[
+            0x61, 0x60, 0x00, 0x60, 0x00, 0x52, 0x60, 0x02, 0x60, 0x1e, 0xf3,
+        ]);
+        let state = evm.transact().unwrap().state;
+        let created = crate::primitives::create_address(CALLER, 0);
+        assert!(state[&created].is_newly_created());
+
+        // status and code of the created account are encoded one after another.
+        let encoded = encode_changeset(&state);
+        assert!(encoded
+            .windows(4)
+            .any(|item| item == [CHANGESET_CREATED, 0x82, 0x60, 0x00]));
+    }
+
+    #[cfg(feature = "optional_selfdestruct_to_self")]
+    #[test]
+    fn selfdestruct_to_self_keeps_balance_on_commit() {
+        // ADDRESS SELFDESTRUCT
+        let mut evm = evm_with_contract(&[0x30, 0xff]);
+        evm.env.cfg.selfdestruct_to_self = crate::primitives::SelfdestructToSelf::Keep;
+        evm.db()
+            .unwrap()
+            .accounts
+            .get_mut(&OUTER)
+            .unwrap()
+            .info
+            .balance = U256::from(100);
+        assert!(evm.transact_commit().unwrap().is_success());
+
+        let info = crate::Database::basic(evm.db().unwrap(), OUTER)
+            .unwrap()
+            .unwrap();
+        assert_eq!(info.balance, U256::from(100));
+    }
+
+    #[cfg(feature = "no_bn128")]
+    #[test]
+    fn precompile_disabled() {
+        let mut evm = evm_calling(InMemoryDB::default(), OUTER);
+        evm.env.tx.transact_to = TransactTo::Call(B160::from_low_u64_be(6));
+        evm.env.tx.gas_limit = 100_000;
+        // disabled precompile is not affected by the precompile failure rules.
+        #[cfg(feature = "optional_precompile_failure")]
+        {
+            evm.env.cfg.precompile_failure.default =
+                crate::primitives::PrecompileFailure::ReturnRemainingGas;
+        }
+
+        let result = evm.transact().unwrap().result;
+        assert!(matches!(
+            result,
+            ExecutionResult::Halt {
+                reason: Halt::PrecompileDisabled,
+                ..
+            }
+        ));
+        assert_eq!(result.gas_used(), 100_000);
+    }
+
+    #[cfg(not(feature = "no_bn128"))]
+    #[test]
+    fn precompile_failure() {
+        // ecAdd with a point that is not on the curve.
+        let ec_add = B160::from_low_u64_be(6);
+        let mut evm = evm_calling(InMemoryDB::default(), OUTER);
+        evm.env.tx.transact_to = TransactTo::Call(ec_add);
+        evm.env.tx.data = Bytes::from_static(&[1; 64]);
+        evm.env.tx.gas_limit = 100_000;
+
+        let result = evm.transact().unwrap().result;
+        assert!(matches!(
+            result,
+            ExecutionResult::Halt {
+                reason: Halt::PrecompileError,
+                ..
+            }
+        ));
+        assert_eq!(result.gas_used(), 100_000);
+
+        #[cfg(feature = "optional_precompile_failure")]
+        {
+            use crate::primitives::PrecompileFailure;
+            evm.env
+                .cfg
+                .precompile_failure
+                .set(ec_add, PrecompileFailure::ReturnRemainingGas);
+            let result = evm.transact().unwrap().result;
+            assert!(matches!(
+                result,
+                ExecutionResult::Revert { ref output, .. } if output.is_empty()
+            ));
+            // ecAdd cost is charged, the rest of the gas is returned.
+            assert_eq!(result.gas_used(), 21_000 + 64 * 16 + 150);
+
+            // ecPairing cost of input with invalid length is not known, all gas is consumed.
+            let ec_pairing = B160::from_low_u64_be(8);
+            evm.env
+                .cfg
+                .precompile_failure
+                .set(ec_pairing, PrecompileFailure::ReturnRemainingGas);
+            evm.env.tx.transact_to = TransactTo::Call(ec_pairing);
+            let result = evm.transact().unwrap().result;
+            assert!(matches!(
+                result,
+                ExecutionResult::Halt {
+                    reason: Halt::PrecompileError,
+                    ..
+                }
+            ));
+            assert_eq!(result.gas_used(), 100_000);
+        }
+    }
+
+    #[test]
+    fn stale_tx_field() {
+        let mut evm = evm_calling(InMemoryDB::default(), OUTER);
+        evm.env.cfg.spec_id = SpecId::BERLIN;
+        evm.env.tx.gas_priority_fee = Some(U256::from(1));
+        assert_eq!(
+            evm.transact().unwrap_err(),
+            EVMError::Transaction(InvalidTransaction::PriorityFeeNotSupported)
+        );
+    }
+
+    #[test]
+    fn validate_env_without_evm() {
+        let mut env = Env::default();
+        env.tx.gas_limit = 30_000;
+        env.tx.data = Bytes::from_static(&[0x00, 0x01]);
+        assert_eq!(validate_env::<()>(&env), Ok(21_000 + 4 + 16));
+
+        env.cfg.spec_id = SpecId::FRONTIER;
+        assert_eq!(validate_env::<()>(&env), Ok(21_000 + 4 + 68));
+
+        env.tx.gas_limit = 21_000;
+        assert_eq!(
+            validate_env::<()>(&env),
+            Err(InvalidTransaction::CallGasCostMoreThanGasLimit.into())
+        );
+
+        // EVM rejects the same transaction.
+        let mut evm = evm_calling(InMemoryDB::default(), OUTER);
+        evm.env = env;
+        assert_eq!(
+            evm.transact().map(|_| ()),
+            Err(InvalidTransaction::CallGasCostMoreThanGasLimit.into())
+        );
+    }
+
+    #[test]
+    fn eip3541_rejects_ef_code() {
+        // MSTORE8(0, 0xEF) RETURN(0, 1)
+        let init_code = [0x60, 0xef, 0x60, 0x00, 0x53, 0x60, 0x01, 0x60, 0x00, 0xf3];
+        let mut evm = evm_calling(InMemoryDB::default(), OUTER);
+        evm.env.tx.transact_to = TransactTo::create();
+        evm.env.tx.data = Bytes::from(init_code.to_vec());
+
+        let result = evm.transact().unwrap().result;
+        assert!(matches!(
+            result,
+            ExecutionResult::Halt {
+                reason: Halt::CreateContractStartingWithEF,
+                ..
+            }
+        ));
+
+        #[cfg(feature = "optional_eip3541")]
+        {
+            evm.env.cfg.disable_eip3541 = true;
+            assert!(evm.transact().unwrap().result.is_success());
+        }
+    }
+
+    #[test]
+    fn fatal_external_error_without_database_error() {
+        struct FailingInspector;
+        impl<DB: Database> Inspector<DB> for FailingInspector {
+            fn call(
+                &mut self,
+                _data: &mut crate::EVMData<'_, DB>,
+                inputs: &mut CallInputs,
+            ) -> (InstructionResult, Gas, Bytes) {
+                (
+                    InstructionResult::FatalExternalError,
+                    Gas::new(inputs.gas_limit),
+                    Bytes::new(),
+                )
+            }
+        }
+
+        let mut evm = evm_calling(InMemoryDB::default(), OUTER);
+        assert_eq!(
+            evm.inspect(FailingInspector).map(|_| ()),
+            Err(EVMError::Internal(InternalError::FatalExternalError))
+        );
+    }
+
+    #[test]
+    fn transact_artifacts() {
+        // SSTORE(0, SLOAD(1)) MSTORE(0, 1)
+        let mut evm = evm_with_contract(&[
+            0x60, 0x01, 0x54, 0x60, 0x00, 0x55, 0x60, 0x01, 0x60, 0x00, 0x52,
+        ]);
+
+        let artifacts = evm.transact_artifacts(false).unwrap();
+        assert!(artifacts.result.is_success());
+        assert!(artifacts.trace.is_none());
+        assert_eq!(
+            artifacts.access[&OUTER],
+            [U256::ZERO, U256::from(1)].into_iter().collect()
+        );
+        assert!(artifacts.access.contains_key(&CALLER));
+        assert_eq!(artifacts.metrics.gas_used, artifacts.result.gas_used());
+        assert_eq!(artifacts.metrics.peak_memory, 32);
+
+        let artifacts = evm.transact_artifacts(true).unwrap();
+        let trace = artifacts.trace.unwrap();
+        assert_eq!(trace.to, Some(OUTER));
+        assert_eq!(trace.gas_used, artifacts.metrics.gas_used);
+    }
+
+    #[test]
+    fn effective_gas_price_and_fee_paid() {
+        let mut evm = evm_calling(InMemoryDB::default(), OUTER);
+        evm.db()
+            .unwrap()
+            .insert_account_info(CALLER, AccountInfo::from_balance(U256::from(10_000_000)));
+        evm.env.cfg.spec_id = SpecId::LONDON;
+        evm.env.block.basefee = U256::from(10);
+        evm.env.tx.gas_limit = 30_000;
+
+        // priority fee is capped by the max fee.
+        evm.env.tx.gas_price = U256::from(100);
+        evm.env.tx.gas_priority_fee = Some(U256::from(5));
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert_eq!(result.effective_gas_price(), U256::from(15));
+        assert_eq!(result.fee_paid(), U256::from(21_000 * 15));
+        assert_eq!(
+            state[&CALLER].info.balance,
+            U256::from(10_000_000 - 21_000 * 15)
+        );
+
+        // legacy transaction pays its gas price.
+        evm.env.tx.gas_price = U256::from(20);
+        evm.env.tx.gas_priority_fee = None;
+        let result = evm.transact().unwrap().result;
+        assert_eq!(result.effective_gas_price(), U256::from(20));
+        assert_eq!(result.fee_paid(), U256::from(21_000 * 20));
+    }
+
+    #[test]
+    fn memory_pool_limit() {
+        // MSTORE(0x10000, 0) expands memory over 64 KiB.
+        let mut evm = evm_with_contract(&[0x60, 0x00, 0x62, 0x01, 0x00, 0x00, 0x52]);
+        assert!(evm.transact().unwrap().result.is_success());
+        assert_eq!(evm.memory_pool.len(), 1);
+        assert!(evm.memory_pool.pooled_bytes() > 0x10000);
+
+        // memory over the limit is freed after the call.
+        evm.set_memory_pool_limit(0x10000);
+        assert!(evm.memory_pool.is_empty());
+        assert!(evm.transact().unwrap().result.is_success());
+        assert!(evm.memory_pool.is_empty());
+        assert_eq!(evm.peak_memory(), 0x10020);
+    }
+
+    #[cfg(feature = "gas_by_category")]
+    #[test]
+    fn gas_by_category() {
+        // SSTORE(0, 1) POP(SLOAD(0)) MSTORE(0, 1)
+        // POP(STATICCALL(0xffff, 4, 0, 32, 0, 32)) to the identity precompile.
+        let mut evm = evm_with_contract(&[
+            0x60, 0x01, 0x60, 0x00, 0x55, 0x60, 0x00, 0x54, 0x50, 0x60, 0x01, 0x60, 0x00, 0x52,
+            0x60, 0x20, 0x60, 0x00, 0x60, 0x20, 0x60, 0x00, 0x60, 0x04, 0x61, 0xff, 0xff, 0xfa,
+            0x50,
+        ]);
+        let ResultAndState {
+            result,
+            gas_by_category,
+            ..
+        } = evm.transact().unwrap();
+
+        assert!(result.is_success());
+        assert_eq!(gas_by_category.storage_writes, 22_100);
+        assert_eq!(gas_by_category.storage_reads, 100);
+        assert_eq!(gas_by_category.memory, 6);
+        // warm precompile, gas used by it is not included.
+        assert_eq!(gas_by_category.calls, 100);
+        assert_eq!(gas_by_category.precompiles, 18);
+        assert_eq!(gas_by_category.total(), result.gas_used() - 21_000);
     }
 }
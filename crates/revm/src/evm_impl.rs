@@ -1,35 +1,45 @@
 use crate::interpreter::{
     analysis::to_analysed, gas, instruction_result::SuccessOrHalt, return_ok, return_revert,
     CallContext, CallInputs, CallScheme, Contract, CreateInputs, CreateScheme, Gas, Host,
-    InstructionResult, Interpreter, SelfDestructResult, Transfer, CALL_STACK_LIMIT,
+    InstructionResult, Interpreter, Memory, MemoryPool, SelfDestructResult, Transfer,
+    CALL_STACK_LIMIT,
 };
 use crate::journaled_state::{is_precompile, JournalCheckpoint};
 use crate::primitives::{
     create2_address, create_address, keccak256, Account, AnalysisKind, Bytecode, Bytes, EVMError,
-    EVMResult, Env, ExecutionResult, HashMap, InvalidTransaction, Log, Output, ResultAndState,
-    Spec,
+    EVMResult, Env, ExecutionResult, HashMap, InternalError, Log, Output, PrecompileFailure,
+    ResultAndState, Spec,
     SpecId::{self, *},
-    TransactTo, B160, B256, U256,
+    StaticCallViolation, TransactTo, B160, B256, U256,
 };
+#[cfg(feature = "gas_by_category")]
+use crate::primitives::{GasByCategory, GasCategory};
 use crate::{db::Database, journaled_state::JournaledState, precompile, Inspector};
 use alloc::boxed::Box;
 use alloc::vec::Vec;
 use core::{cmp::min, marker::PhantomData};
-use revm_interpreter::gas::initial_tx_gas;
+use revm_interpreter::gas::validate_initial_tx_gas;
 use revm_interpreter::MAX_CODE_SIZE;
-use revm_precompile::{Precompile, Precompiles};
+use revm_precompile::{Precompile, PrecompileGas, Precompiles};
 
 pub struct EVMData<'a, DB: Database> {
     pub env: &'a mut Env,
     pub journaled_state: JournaledState,
     pub db: &'a mut DB,
     pub error: Option<DB::Error>,
+    /// Gas used by all frames of the transaction, grouped by category.
+    #[cfg(feature = "gas_by_category")]
+    pub gas_by_category: GasByCategory,
+    /// Last state change that was attempted inside a static call.
+    pub static_call_violation: Option<StaticCallViolation>,
 }
 
 pub struct EVMImpl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> {
     data: EVMData<'a, DB>,
     precompiles: Precompiles,
     inspector: &'a mut dyn Inspector<DB>,
+    /// Pool of interpreter memories. If not set every interpreter allocates its own memory.
+    memory_pool: Option<&'a mut MemoryPool>,
     _phantomdata: PhantomData<GSPEC>,
 }
 
@@ -59,6 +69,17 @@ struct CallResult {
     return_value: Bytes,
 }
 
+/// Validates the block and the transaction without loading any state, these are the checks
+/// that are done before the transaction is executed.
+///
+/// Returns the initial gas of the transaction. Checks against the caller account are done by
+/// [Env::validate_tx_agains_state].
+pub fn validate_env_with_spec<SPEC: Spec, DBError>(env: &Env) -> Result<u64, EVMError<DBError>> {
+    env.validate_block_env::<SPEC, DBError>()?;
+    env.validate_tx::<SPEC>()?;
+    Ok(validate_initial_tx_gas::<SPEC>(env)?)
+}
+
 pub trait Transact<DBError> {
     /// Do transaction.
     /// InstructionResult InstructionResult, Output for call or Address if we are creating
@@ -86,25 +107,15 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> Transact<DB::Error>
     for EVMImpl<'a, GSPEC, DB, INSPECT>
 {
     fn transact(&mut self) -> EVMResult<DB::Error> {
-        self.env().validate_block_env::<GSPEC, DB::Error>()?;
-        self.env().validate_tx::<GSPEC>()?;
+        let initial_gas_spend = validate_env_with_spec::<GSPEC, DB::Error>(self.data.env)?;
 
         let env = &self.data.env;
         let tx_caller = env.tx.caller;
         let tx_value = env.tx.value;
         let tx_data = env.tx.data.clone();
         let tx_gas_limit = env.tx.gas_limit;
-        let tx_is_create = env.tx.transact_to.is_create();
         let effective_gas_price = env.effective_gas_price();
 
-        let initial_gas_spend =
-            initial_tx_gas::<GSPEC>(&tx_data, tx_is_create, &env.tx.access_list);
-
-        // Additonal check to see if limit is big enought to cover initial gas.
-        if env.tx.gas_limit < initial_gas_spend {
-            return Err(InvalidTransaction::CallGasCostMoreThanGasLimit.into());
-        }
-
         // load coinbase
         // EIP-3651: Warm COINBASE. Starts the `COINBASE` address warm
         if GSPEC::enabled(SHANGHAI) {
@@ -193,33 +204,53 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> Transact<DB::Error>
             }
         }
 
-        let (state, logs, gas_used, gas_refunded) = self.finalize::<GSPEC>(&gas);
+        let (state, logs, gas_used, gas_refunded) = self.finalize::<GSPEC>(&gas)?;
+        let fee_paid = effective_gas_price.saturating_mul(U256::from(gas_used));
 
         let result = match exit_reason.into() {
             SuccessOrHalt::Success(reason) => ExecutionResult::Success {
                 reason,
                 gas_used,
                 gas_refunded,
+                effective_gas_price,
+                fee_paid,
                 logs,
                 output,
             },
             SuccessOrHalt::Revert => ExecutionResult::Revert {
                 gas_used,
+                effective_gas_price,
+                fee_paid,
                 output: match output {
                     Output::Call(return_value) => return_value,
                     Output::Create(return_value, _) => return_value,
                 },
             },
-            SuccessOrHalt::Halt(reason) => ExecutionResult::Halt { reason, gas_used },
+            SuccessOrHalt::Halt(reason) => ExecutionResult::Halt {
+                reason,
+                gas_used,
+                effective_gas_price,
+                fee_paid,
+            },
             SuccessOrHalt::FatalExternalError => {
-                return Err(EVMError::Database(self.data.error.take().unwrap()))
+                return Err(match self.data.error.take() {
+                    Some(error) => EVMError::Database(error),
+                    None => EVMError::Internal(InternalError::FatalExternalError),
+                })
             }
             SuccessOrHalt::InternalContinue => {
-                panic!("Internal return flags should remain internal {exit_reason:?}")
+                return Err(EVMError::Internal(InternalError::InternalContinue))
             }
         };
 
-        Ok(ResultAndState { result, state })
+        Ok(ResultAndState {
+            result,
+            state,
+            db_reads: self.data.journaled_state.db_reads,
+            #[cfg(feature = "gas_by_category")]
+            gas_by_category: self.data.gas_by_category,
+            static_call_violation: self.data.static_call_violation,
+        })
     }
 }
 
@@ -229,26 +260,37 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB,
         env: &'a mut Env,
         inspector: &'a mut dyn Inspector<DB>,
         precompiles: Precompiles,
+        memory_pool: Option<&'a mut MemoryPool>,
     ) -> Self {
-        let journaled_state = if GSPEC::enabled(SpecId::SPURIOUS_DRAGON) {
+        let mut journaled_state = if GSPEC::enabled(SpecId::SPURIOUS_DRAGON) {
             JournaledState::new(precompiles.len())
         } else {
             JournaledState::new_legacy(precompiles.len())
         };
+        // balance sent by selfdestruct to the destroyed account itself is burned in all forks.
+        journaled_state.burn_selfdestruct_to_self = env.cfg.is_selfdestruct_to_self_burned(true);
         Self {
             data: EVMData {
                 env,
                 journaled_state,
                 db,
                 error: None,
+                #[cfg(feature = "gas_by_category")]
+                gas_by_category: GasByCategory::default(),
+                static_call_violation: None,
             },
             precompiles,
             inspector,
+            memory_pool,
             _phantomdata: PhantomData {},
         }
     }
 
-    fn finalize<SPEC: Spec>(&mut self, gas: &Gas) -> (HashMap<B160, Account>, Vec<Log>, u64, u64) {
+    #[allow(clippy::type_complexity)]
+    fn finalize<SPEC: Spec>(
+        &mut self,
+        gas: &Gas,
+    ) -> Result<(HashMap<B160, Account>, Vec<Log>, u64, u64), EVMError<DB::Error>> {
         let caller = self.data.env.tx.caller;
         let coinbase = self.data.env.block.coinbase;
         let (gas_used, gas_refunded) = if crate::USE_GAS {
@@ -259,12 +301,15 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB,
                 0
             } else {
                 // EIP-3529: Reduction in refunds
-                let max_refund_quotient = if SPEC::enabled(LONDON) { 5 } else { 2 };
+                let eip3529 = self.data.env.cfg.is_eip3529_enabled(SPEC::enabled(LONDON));
+                let max_refund_quotient = if eip3529 { 5 } else { 2 };
                 min(gas.refunded() as u64, gas.spend() / max_refund_quotient)
             };
 
             // return balance of not spend gas.
-            let caller_account = self.data.journaled_state.state().get_mut(&caller).unwrap();
+            let Some(caller_account) = self.data.journaled_state.state().get_mut(&caller) else {
+                return Err(EVMError::Internal(InternalError::AccountNotLoaded(caller)));
+            };
             caller_account.info.balance = caller_account
                 .info
                 .balance
@@ -278,13 +323,11 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB,
             };
 
             // transfer fee to coinbase/beneficiary.
-            let Ok((coinbase_account, _)) = self
+            let (coinbase_account, _) = self
                 .data
                 .journaled_state
                 .load_account(coinbase, self.data.db)
-            else {
-                panic!("coinbase account not found");
-            };
+                .map_err(EVMError::Database)?;
             coinbase_account.mark_touch();
             coinbase_account.info.balance = coinbase_account
                 .info
@@ -302,7 +345,7 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB,
             (0, 0)
         };
         let (new_state, logs) = self.data.journaled_state.finalize();
-        (new_state, logs, gas_used, gas_refunded)
+        Ok((new_state, logs, gas_used, gas_refunded))
     }
 
     fn prepare_create(&mut self, inputs: &CreateInputs) -> Result<PreparedCreate, CreateResult> {
@@ -339,17 +382,25 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB,
         }
 
         // Increase nonce of caller and check if it overflows
-        let old_nonce;
-        if let Some(nonce) = self.data.journaled_state.inc_nonce(inputs.caller) {
-            old_nonce = nonce - 1;
-        } else {
-            return Err(CreateResult {
-                result: InstructionResult::Return,
-                created_address: None,
-                gas,
-                return_value: Bytes::new(),
-            });
-        }
+        let old_nonce = match self.data.journaled_state.inc_nonce(inputs.caller) {
+            Ok(Some(nonce)) => nonce - 1,
+            Ok(None) => {
+                return Err(CreateResult {
+                    result: InstructionResult::Return,
+                    created_address: None,
+                    gas,
+                    return_value: Bytes::new(),
+                })
+            }
+            Err(_) => {
+                return Err(CreateResult {
+                    result: InstructionResult::FatalExternalError,
+                    created_address: None,
+                    gas,
+                    return_value: Bytes::new(),
+                })
+            }
+        };
 
         // Create address
         let code_hash = keccak256(&inputs.init_code);
@@ -417,24 +468,27 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB,
         };
 
         // Create new interpreter and execute initcode
-        let (exit_reason, mut interpreter) =
+        let (exit_reason, mut gas, return_value) =
             self.run_interpreter(prepared_create.contract, prepared_create.gas.limit(), false);
 
         // Host error if present on execution
         match exit_reason {
             return_ok!() => {
                 // if ok, check contract creation limit and calculate gas deduction on output len.
-                let mut bytes = interpreter.return_value();
+                let mut bytes = return_value;
 
                 // EIP-3541: Reject new contract code starting with the 0xEF byte
-                if GSPEC::enabled(LONDON) && !bytes.is_empty() && bytes.first() == Some(&0xEF) {
+                if GSPEC::enabled(LONDON)
+                    && !self.data.env.cfg.is_eip3541_disabled()
+                    && bytes.first() == Some(&0xEF)
+                {
                     self.data
                         .journaled_state
                         .checkpoint_revert(prepared_create.checkpoint);
                     return CreateResult {
                         result: InstructionResult::CreateContractStartingWithEF,
                         created_address: Some(prepared_create.created_address),
-                        gas: interpreter.gas,
+                        gas,
                         return_value: bytes,
                     };
                 }
@@ -456,13 +510,13 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB,
                     return CreateResult {
                         result: InstructionResult::CreateContractSizeLimit,
                         created_address: Some(prepared_create.created_address),
-                        gas: interpreter.gas,
+                        gas,
                         return_value: bytes,
                     };
                 }
                 if crate::USE_GAS {
                     let gas_for_code = bytes.len() as u64 * gas::CODEDEPOSIT;
-                    if !interpreter.gas.record_cost(gas_for_code) {
+                    if !gas.record_cost(gas_for_code) {
                         // record code deposit gas cost and check if we are out of gas.
                         // EIP-2 point 3: If contract creation does not have enough gas to pay for the
                         // final gas fee for adding the contract code to the state, the contract
@@ -474,7 +528,7 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB,
                             return CreateResult {
                                 result: InstructionResult::OutOfGas,
                                 created_address: Some(prepared_create.created_address),
-                                gas: interpreter.gas,
+                                gas,
                                 return_value: bytes,
                             };
                         } else {
@@ -490,13 +544,18 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB,
                     AnalysisKind::Check => Bytecode::new_raw(bytes.clone()).to_checked(),
                     AnalysisKind::Analyse => to_analysed(Bytecode::new_raw(bytes.clone())),
                 };
-                self.data
+                let result = match self
+                    .data
                     .journaled_state
-                    .set_code(prepared_create.created_address, bytecode);
+                    .set_code(prepared_create.created_address, bytecode)
+                {
+                    Ok(()) => InstructionResult::Return,
+                    Err(_) => InstructionResult::FatalExternalError,
+                };
                 CreateResult {
-                    result: InstructionResult::Return,
+                    result,
                     created_address: Some(prepared_create.created_address),
-                    gas: interpreter.gas,
+                    gas,
                     return_value: bytes,
                 }
             }
@@ -507,32 +566,36 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB,
                 CreateResult {
                     result: exit_reason,
                     created_address: Some(prepared_create.created_address),
-                    gas: interpreter.gas,
-                    return_value: interpreter.return_value(),
+                    gas,
+                    return_value,
                 }
             }
         }
     }
 
     /// Create a Interpreter and run it.
-    /// Returns the exit reason and created interpreter as it contains return values and gas spend.
+    /// Returns the exit reason, gas spend and return value of the interpreter.
+    ///
+    /// If memory pool is set, interpreter memory is taken from it and returned after execution.
     pub fn run_interpreter(
         &mut self,
         contract: Box<Contract>,
         gas_limit: u64,
         is_static: bool,
-    ) -> (InstructionResult, Box<Interpreter>) {
+    ) -> (InstructionResult, Gas, Bytes) {
+        let memory = match self.memory_pool.as_mut() {
+            Some(pool) => pool.acquire(),
+            None => Memory::new(),
+        };
+
         // Create inspector
-        #[cfg(feature = "memory_limit")]
-        let mut interpreter = Box::new(Interpreter::new_with_memory_limit(
-            contract,
-            gas_limit,
-            is_static,
-            self.data.env.cfg.memory_limit,
+        let mut interpreter = Box::new(Interpreter::new_with_memory(
+            contract, gas_limit, is_static, memory,
         ));
-
-        #[cfg(not(feature = "memory_limit"))]
-        let mut interpreter = Box::new(Interpreter::new(contract, gas_limit, is_static));
+        #[cfg(feature = "memory_limit")]
+        {
+            interpreter.memory_limit = self.data.env.cfg.memory_limit;
+        }
 
         if INSPECT {
             self.inspector
@@ -543,8 +606,29 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB,
         } else {
             interpreter.run::<Self, GSPEC>(self)
         };
+        if matches!(
+            interpreter.instruction_result,
+            InstructionResult::StateChangeDuringStaticCall
+                | InstructionResult::CallNotAllowedInsideStatic
+        ) {
+            // program counter is already moved past the opcode.
+            let pc = interpreter.program_counter().wrapping_sub(1);
+            if let Some(opcode) = interpreter.contract.bytecode.bytecode().get(pc) {
+                self.data.static_call_violation = Some(StaticCallViolation {
+                    opcode: *opcode,
+                    depth: self.data.journaled_state.depth(),
+                    address: interpreter.contract.address,
+                });
+            }
+        }
+        let return_value = interpreter.return_value();
+        let Interpreter { gas, memory, .. } = *interpreter;
 
-        (exit_reason, interpreter)
+        if let Some(pool) = self.memory_pool.as_mut() {
+            pool.release(memory);
+        }
+
+        (exit_reason, gas, return_value)
     }
 
     /// Call precompile contract
@@ -552,15 +636,18 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB,
         let input_data = inputs.input.clone();
         let contract = inputs.contract;
 
-        let precompile = self
-            .precompiles
-            .get(&contract)
-            .expect("Check for precompile should be already done");
+        let Some(precompile) = self.precompiles.get(&contract) else {
+            return CallResult {
+                result: InstructionResult::PrecompileError,
+                gas,
+                return_value: Bytes::new(),
+            };
+        };
         let out = match precompile {
             Precompile::Standard(fun) => fun(&input_data, gas.limit()),
             Precompile::Custom(fun) => fun(&input_data, gas.limit()),
         };
-        match out {
+        let ret = match out {
             Ok((gas_used, data)) => {
                 if !crate::USE_GAS || gas.record_cost(gas_used) {
                     CallResult {
@@ -577,10 +664,24 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB,
                 }
             }
             Err(e) => {
-                let ret = if precompile::Error::OutOfGas == e {
-                    InstructionResult::PrecompileOOG
-                } else {
-                    InstructionResult::PrecompileError
+                let ret = match e {
+                    precompile::Error::OutOfGas => InstructionResult::PrecompileOOG,
+                    // precompile is not part of the build, it is not a failure of the call.
+                    precompile::Error::Disabled => InstructionResult::PrecompileDisabled,
+                    _ => match self.data.env.cfg.precompile_failure(&contract) {
+                        PrecompileFailure::ConsumeAllGas => InstructionResult::PrecompileError,
+                        // charge the cost of the call and return the rest.
+                        PrecompileFailure::ReturnRemainingGas => {
+                            match Self::precompile_failure_cost(&contract, input_data.len()) {
+                                Some(cost) if !crate::USE_GAS || gas.record_cost(cost) => {
+                                    InstructionResult::Revert
+                                }
+                                Some(_) => InstructionResult::PrecompileOOG,
+                                // failure can't be charged without the cost, all gas is consumed.
+                                None => InstructionResult::PrecompileError,
+                            }
+                        }
+                    },
                 };
                 CallResult {
                     result: ret,
@@ -588,6 +689,28 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB,
                     return_value: Bytes::new(),
                 }
             }
+        };
+        // failed precompile consumes all gas, unless it reverted.
+        #[cfg(feature = "gas_by_category")]
+        self.data.gas_by_category.record(
+            GasCategory::Precompile,
+            match ret.result {
+                InstructionResult::Return | InstructionResult::Revert => ret.gas.spend(),
+                _ => ret.gas.limit(),
+            },
+        );
+        ret
+    }
+
+    /// Gas charged for a failed precompile call that returns the remaining gas.
+    ///
+    /// It is the computed cost of the call, or the minimal cost if it depends on the input.
+    /// Cost of custom precompiles and of inputs with invalid length is not known.
+    fn precompile_failure_cost(contract: &B160, input_len: usize) -> Option<u64> {
+        let spec = crate::evm::to_precompile_id(GSPEC::SPEC_ID);
+        match precompile::precompile_gas(contract, input_len, spec)? {
+            PrecompileGas::Exact(cost) | PrecompileGas::Range { min: cost, .. } => Some(cost),
+            PrecompileGas::Fail => None,
         }
     }
 
@@ -661,15 +784,15 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB,
             self.call_precompile(inputs, prepared_call.gas)
         } else if !prepared_call.contract.bytecode.is_empty() {
             // Create interpreter and execute subcall
-            let (exit_reason, interpreter) = self.run_interpreter(
+            let (exit_reason, gas, return_value) = self.run_interpreter(
                 prepared_call.contract,
                 prepared_call.gas.limit(),
                 inputs.is_static,
             );
             CallResult {
                 result: exit_reason,
-                gas: interpreter.gas,
-                return_value: interpreter.return_value(),
+                gas,
+                return_value,
             }
         } else {
             CallResult {
@@ -708,6 +831,7 @@ impl<'a, GSPEC: Spec, DB: Database + 'a, const INSPECT: bool> Host
     }
 
     fn block_hash(&mut self, number: U256) -> Option<B256> {
+        self.data.journaled_state.db_reads.block_hash += 1;
         self.data
             .db
             .block_hash(number)
@@ -743,7 +867,8 @@ impl<'a, GSPEC: Spec, DB: Database + 'a, const INSPECT: bool> Host
             .load_code(address, db)
             .map_err(|e| *error = Some(e))
             .ok()?;
-        Some((acc.info.code.clone().unwrap(), is_cold))
+        // code is always set after it is loaded.
+        Some((acc.info.code.clone().unwrap_or_default(), is_cold))
     }
 
     /// Get code hash of address.
@@ -863,4 +988,9 @@ impl<'a, GSPEC: Spec, DB: Database + 'a, const INSPECT: bool> Host
             (ret.result, ret.gas, ret.return_value)
         }
     }
+
+    #[cfg(feature = "gas_by_category")]
+    fn gas_by_category(&mut self) -> &mut GasByCategory {
+        &mut self.data.gas_by_category
+    }
 }
@@ -0,0 +1,203 @@
+use crate::interpreter::MemoryPool;
+use crate::primitives::Env;
+use crate::EVM;
+use alloc::{boxed::Box, vec::Vec};
+use core::ops::{Deref, DerefMut};
+use std::sync::Mutex;
+
+/// Pool of [EVM]s that can be shared between worker threads.
+///
+/// Acquired EVM is a fresh one, with the environment of the pool and a new database, so nothing
+/// is leaked from its previous user. Only memory buffers of idle EVMs are kept and reused.
+pub struct SyncEvmPool<DB> {
+    env: Env,
+    memory_pools: Mutex<Vec<MemoryPool>>,
+    db_factory: Box<dyn Fn() -> DB + Send + Sync>,
+}
+
+impl<DB> SyncEvmPool<DB> {
+    /// Create a pool that creates new EVMs with `env` and database returned by `db_factory`.
+    pub fn new(env: Env, db_factory: impl Fn() -> DB + Send + Sync + 'static) -> Self {
+        Self {
+            env,
+            memory_pools: Mutex::new(Vec::new()),
+            db_factory: Box::new(db_factory),
+        }
+    }
+
+    /// Environment that acquired EVMs start with.
+    pub fn env(&self) -> &Env {
+        &self.env
+    }
+
+    /// Create EVM with the environment of the pool and a new database, reusing memory
+    /// buffers of an idle EVM.
+    ///
+    /// EVM is returned to the pool when [PooledEvm] is dropped.
+    pub fn acquire(&self) -> PooledEvm<'_, DB> {
+        let mut evm = EVM::with_env(self.env.clone());
+        if let Some(memory_pool) = self.lock().pop() {
+            evm.memory_pool = memory_pool;
+        }
+        evm.database((self.db_factory)());
+        PooledEvm {
+            pool: self,
+            evm,
+            detached: false,
+        }
+    }
+
+    /// Number of idle EVMs.
+    pub fn idle(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Drop all idle EVMs together with their memory buffers.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<MemoryPool>> {
+        // Memory pools are only pushed and popped while locked, so poisoned pool is still consistent.
+        self.memory_pools.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// [EVM] acquired from [SyncEvmPool], it is returned to the pool on drop.
+pub struct PooledEvm<'a, DB> {
+    pool: &'a SyncEvmPool<DB>,
+    evm: EVM<DB>,
+    detached: bool,
+}
+
+impl<DB> PooledEvm<'_, DB> {
+    /// Take EVM out of the pool, it is not going to be returned on drop.
+    pub fn detach(mut self) -> EVM<DB> {
+        self.detached = true;
+        core::mem::take(&mut self.evm)
+    }
+}
+
+impl<DB> Deref for PooledEvm<'_, DB> {
+    type Target = EVM<DB>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.evm
+    }
+}
+
+impl<DB> DerefMut for PooledEvm<'_, DB> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.evm
+    }
+}
+
+impl<DB> Drop for PooledEvm<'_, DB> {
+    fn drop(&mut self) {
+        if !self.detached {
+            let memory_pool = core::mem::take(&mut self.evm.memory_pool);
+            self.pool.lock().push(memory_pool);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{CacheDB, EmptyDB};
+    use crate::inspectors::{CustomPrintTracer, GasInspector, NoOpInspector};
+    use crate::primitives::{AccountInfo, B160, U256};
+    use crate::{InMemoryDB, JournaledState};
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn send_sync_bounds() {
+        assert_send_sync::<EVM<InMemoryDB>>();
+        assert_send_sync::<CacheDB<EmptyDB>>();
+        assert_send_sync::<JournaledState>();
+        assert_send_sync::<SyncEvmPool<InMemoryDB>>();
+        assert_send_sync::<PooledEvm<'_, InMemoryDB>>();
+        assert_send_sync::<NoOpInspector>();
+        assert_send_sync::<GasInspector>();
+        assert_send_sync::<CustomPrintTracer>();
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn tracer_is_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<crate::inspectors::TracerEip3155>();
+    }
+
+    #[test]
+    fn pool_reuses_evms_across_threads() {
+        let mut env = Env::default();
+        env.tx.caller = B160::from_low_u64_be(1);
+        let pool = SyncEvmPool::new(env, || {
+            let mut db = InMemoryDB::default();
+            db.insert_account_info(
+                B160::from_low_u64_be(1),
+                AccountInfo::from_balance(U256::from(1)),
+            );
+            db
+        });
+
+        std::thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    let mut evm = pool.acquire();
+                    evm.env.tx.value = U256::from(1);
+                    assert!(evm.transact().unwrap().result.is_success());
+                });
+            }
+        });
+        let idle = pool.idle();
+        assert!((1..=4).contains(&idle));
+
+        // environment is reset when EVM is acquired.
+        let evm = pool.acquire();
+        assert_eq!(evm.env.tx.value, U256::ZERO);
+        drop(evm);
+        assert_eq!(pool.idle(), idle);
+
+        let _evm = pool.acquire().detach();
+        assert_eq!(pool.idle(), idle - 1);
+        pool.clear();
+        assert_eq!(pool.idle(), 0);
+    }
+
+    #[test]
+    fn pool_resets_dirty_evm() {
+        let caller = B160::from_low_u64_be(1);
+        let mut env = Env::default();
+        env.tx.caller = caller;
+        env.tx.transact_to = crate::primitives::TransactTo::Call(B160::from_low_u64_be(2));
+        let pool = SyncEvmPool::new(env, move || {
+            let mut db = InMemoryDB::default();
+            db.insert_account_info(caller, AccountInfo::from_balance(U256::from(1)));
+            db
+        });
+
+        let mut evm = pool.acquire();
+        evm.enable_block_building();
+        evm.set_logs_hook(|_, logs| logs.clear());
+        evm.env.tx.value = U256::from(1);
+        assert!(evm.transact_commit().unwrap().is_success());
+        assert_eq!(evm.db().unwrap().accounts[&caller].info.balance, U256::ZERO);
+        drop(evm);
+        assert_eq!(pool.idle(), 1);
+
+        let mut evm = pool.acquire();
+        assert_eq!(evm.env.tx.value, U256::ZERO);
+        assert_eq!(evm.block_gas_used, None);
+        assert_eq!(evm.tx_index, 0);
+        assert!(evm.logs_hook.is_none());
+        assert_eq!(
+            evm.db().unwrap().accounts[&caller].info.balance,
+            U256::from(1)
+        );
+        evm.env.tx.value = U256::from(1);
+        assert!(evm.transact_commit().unwrap().is_success());
+    }
+}
@@ -1,28 +1,40 @@
 use crate::evm_impl::EVMData;
 use crate::interpreter::{CallInputs, CreateInputs, Gas, InstructionResult, Interpreter};
-use crate::primitives::{db::Database, Bytes, B160, B256};
+use crate::primitives::{db::Database, Bytes, Env, B160, B256};
 
 use auto_impl::auto_impl;
 
+pub mod address_policy;
+pub mod call_tracer;
 #[cfg(feature = "std")]
 pub mod customprinter;
 pub mod gas;
 pub mod noop;
+pub mod prestate_tracer;
 #[cfg(all(feature = "std", feature = "serde"))]
 pub mod tracer_eip3155;
 
 /// All Inspectors implementations that revm has.
 pub mod inspectors {
+    pub use super::address_policy::{AddressPolicy, AddressPolicyInspector};
+    pub use super::call_tracer::{CallFrame, CallKind, CallTracer};
     #[cfg(feature = "std")]
     pub use super::customprinter::CustomPrintTracer;
     pub use super::gas::GasInspector;
     pub use super::noop::NoOpInspector;
+    pub use super::prestate_tracer::{Prestate, PrestateAccount, PrestateDiff, PrestateTracer};
     #[cfg(all(feature = "std", feature = "serde"))]
     pub use super::tracer_eip3155::TracerEip3155;
 }
 
 #[auto_impl(&mut, Box)]
 pub trait Inspector<DB: Database> {
+    /// Called before the transaction is executed by [crate::EVM::inspect] and its variants.
+    ///
+    /// `tx_index` is the index of the transaction in the block, see [crate::EVM::tx_index].
+    /// Inspectors that are reused between transactions can use it to split their output.
+    fn initialize(&mut self, _env: &Env, _tx_index: usize) {}
+
     /// Called Before the interpreter is initialized.
     ///
     /// If anything other than [InstructionResult::Continue] is returned then execution of the interpreter is
@@ -52,12 +64,14 @@ pub trait Inspector<DB: Database> {
     }
 
     /// Called when a log is emitted.
+    ///
+    /// Data is borrowed, copy it only if the log needs to be kept.
     fn log(
         &mut self,
         _evm_data: &mut EVMData<'_, DB>,
         _address: &B160,
         _topics: &[B256],
-        _data: &Bytes,
+        _data: &[u8],
     ) {
     }
 
@@ -88,6 +102,8 @@ pub trait Inspector<DB: Database> {
     ///
     /// InstructionResulting anything other than the values passed to this function (`(ret, remaining_gas,
     /// out)`) will alter the result of the call.
+    ///
+    /// Use [InstructionResult::frame_exit] to get whether the call stopped, returned, reverted or halted.
     fn call_end(
         &mut self,
         _data: &mut EVMData<'_, DB>,
@@ -119,6 +135,8 @@ pub trait Inspector<DB: Database> {
     ///
     /// InstructionResulting anything other than the values passed to this function (`(ret, remaining_gas,
     /// address, out)`) will alter the result of the create.
+    ///
+    /// Use [InstructionResult::frame_exit] to get whether the create stopped, returned, reverted or halted.
     fn create_end(
         &mut self,
         _data: &mut EVMData<'_, DB>,
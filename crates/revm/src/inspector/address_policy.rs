@@ -0,0 +1,219 @@
+//! Inspector that denies execution that involves addresses not allowed by the policy.
+
+use crate::evm_impl::EVMData;
+use crate::interpreter::{
+    CallInputs, CreateInputs, CreateScheme, Gas, InstructionResult, Interpreter,
+};
+use crate::primitives::{
+    create2_address, create_address, db::Database, keccak256, Bytes, HashSet, B160,
+};
+use crate::Inspector;
+
+/// Addresses that are allowed to take part in the transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AddressPolicy {
+    /// Listed addresses are denied, all others are allowed.
+    Blacklist(HashSet<B160>),
+    /// Only listed addresses are allowed.
+    Whitelist(HashSet<B160>),
+}
+
+impl AddressPolicy {
+    pub fn is_allowed(&self, address: &B160) -> bool {
+        match self {
+            Self::Blacklist(addresses) => !addresses.contains(address),
+            Self::Whitelist(addresses) => addresses.contains(address),
+        }
+    }
+}
+
+/// Inspector that checks the caller and the called address of every call and
+/// the caller and the created address of every create against the [AddressPolicy],
+/// including the transaction sender and recipient.
+///
+/// When a denied address is found, the call or create is not executed and all
+/// frames that are being executed are halted, so the transaction ends with
+/// [crate::primitives::Halt::AddressDenied]. The address that was denied is
+/// available with [AddressPolicyInspector::denied].
+#[derive(Clone, Debug)]
+pub struct AddressPolicyInspector {
+    policy: AddressPolicy,
+    denied: Option<B160>,
+}
+
+impl AddressPolicyInspector {
+    pub fn new(policy: AddressPolicy) -> Self {
+        Self {
+            policy,
+            denied: None,
+        }
+    }
+
+    pub fn policy(&self) -> &AddressPolicy {
+        &self.policy
+    }
+
+    /// Returns the first denied address of the last transaction.
+    pub fn denied(&self) -> Option<B160> {
+        self.denied
+    }
+
+    /// Checks the addresses and remembers the first one that is denied.
+    fn check<DB: Database>(
+        &mut self,
+        data: &EVMData<'_, DB>,
+        addresses: &[B160],
+    ) -> InstructionResult {
+        // new transaction is started.
+        if data.journaled_state.depth() == 0 {
+            self.denied = None;
+        }
+        if let Some(address) = addresses.iter().find(|a| !self.policy.is_allowed(a)) {
+            self.denied = Some(*address);
+            return InstructionResult::AddressDenied;
+        }
+        InstructionResult::Continue
+    }
+}
+
+impl<DB: Database> Inspector<DB> for AddressPolicyInspector {
+    fn step(
+        &mut self,
+        _interp: &mut Interpreter,
+        _data: &mut EVMData<'_, DB>,
+    ) -> InstructionResult {
+        // halt the frames that made the denied call.
+        if self.denied.is_some() {
+            return InstructionResult::AddressDenied;
+        }
+        InstructionResult::Continue
+    }
+
+    fn call(
+        &mut self,
+        data: &mut EVMData<'_, DB>,
+        inputs: &mut CallInputs,
+    ) -> (InstructionResult, Gas, Bytes) {
+        let context = &inputs.context;
+        let ret = self.check(
+            data,
+            &[context.caller, context.address, context.code_address],
+        );
+        (ret, Gas::new(inputs.gas_limit), Bytes::new())
+    }
+
+    fn create(
+        &mut self,
+        data: &mut EVMData<'_, DB>,
+        inputs: &mut CreateInputs,
+    ) -> (InstructionResult, Option<B160>, Gas, Bytes) {
+        // create hook is called before the caller nonce is increased.
+        let created_address = match inputs.scheme {
+            CreateScheme::Create => data
+                .journaled_state
+                .state
+                .get(&inputs.caller)
+                .map(|account| create_address(inputs.caller, account.info.nonce)),
+            CreateScheme::Create2 { salt } => Some(create2_address(
+                inputs.caller,
+                keccak256(&inputs.init_code),
+                salt,
+            )),
+        };
+        let ret = match created_address {
+            Some(created_address) => self.check(data, &[inputs.caller, created_address]),
+            None => self.check(data, &[inputs.caller]),
+        };
+        (ret, None, Gas::new(inputs.gas_limit), Bytes::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{ExecutionResult, Halt, TransactTo, U256};
+    use crate::test_utils::{evm_with_contract, CALLER, INNER, OUTER};
+
+    fn inspect(policy: AddressPolicy) -> (ExecutionResult, Option<B160>) {
+        // CALL(0xffff, INNER, 0, 0, 0, 0, 0) PUSH1 1 PUSH1 0 SSTORE
+        let mut outer = vec![
+            0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x73,
+        ];
+        outer.extend(INNER.0);
+        outer.extend([0x61, 0xff, 0xff, 0xf1, 0x60, 0x01, 0x60, 0x00, 0x55]);
+
+        let mut evm = evm_with_contract(&outer);
+        evm.env.tx.gas_limit = 100_000;
+        let mut inspector = AddressPolicyInspector::new(policy);
+        let result = evm.inspect(&mut inspector).unwrap().result;
+        (result, inspector.denied())
+    }
+
+    fn is_denied(result: &ExecutionResult) -> bool {
+        matches!(
+            result,
+            ExecutionResult::Halt {
+                reason: Halt::AddressDenied,
+                ..
+            }
+        )
+    }
+
+    #[test]
+    fn blacklist() {
+        let (result, denied) = inspect(AddressPolicy::Blacklist(HashSet::new()));
+        assert!(result.is_success());
+        assert_eq!(denied, None);
+
+        // inner call is denied, outer frame is halted too.
+        let (result, denied) = inspect(AddressPolicy::Blacklist([INNER].into_iter().collect()));
+        assert!(is_denied(&result));
+        assert_eq!(result.gas_used(), 100_000);
+        assert_eq!(denied, Some(INNER));
+
+        // sender is denied.
+        let (result, denied) = inspect(AddressPolicy::Blacklist([CALLER].into_iter().collect()));
+        assert!(is_denied(&result));
+        assert_eq!(denied, Some(CALLER));
+    }
+
+    #[test]
+    fn whitelist() {
+        let (result, denied) = inspect(AddressPolicy::Whitelist(
+            [CALLER, OUTER, INNER].into_iter().collect(),
+        ));
+        assert!(result.is_success());
+        assert_eq!(denied, None);
+
+        let (result, denied) = inspect(AddressPolicy::Whitelist(
+            [CALLER, OUTER].into_iter().collect(),
+        ));
+        assert!(is_denied(&result));
+        assert_eq!(denied, Some(INNER));
+    }
+
+    #[test]
+    fn created_address() {
+        // CREATE2(0, 0, 0, 0) with empty init code.
+        let deployer = [0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0xf5, 0x00];
+        let target = create2_address(OUTER, keccak256(&[]), U256::ZERO);
+
+        let mut evm = evm_with_contract(&deployer);
+        evm.env.tx.gas_limit = 100_000;
+
+        let mut inspector =
+            AddressPolicyInspector::new(AddressPolicy::Blacklist([target].into_iter().collect()));
+        let result = evm.inspect(&mut inspector).unwrap().result;
+        assert!(is_denied(&result));
+        assert_eq!(inspector.denied(), Some(target));
+
+        // created address of the transaction is checked with the sender nonce.
+        let target = create_address(CALLER, 0);
+        evm.env.tx.transact_to = TransactTo::create();
+        let mut inspector =
+            AddressPolicyInspector::new(AddressPolicy::Blacklist([target].into_iter().collect()));
+        let result = evm.inspect(&mut inspector).unwrap().result;
+        assert!(is_denied(&result));
+        assert_eq!(inspector.denied(), Some(target));
+    }
+}
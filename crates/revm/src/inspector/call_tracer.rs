@@ -0,0 +1,451 @@
+//! Call tracer with the output of geth `callTracer`.
+
+use crate::evm_impl::EVMData;
+use crate::interpreter::{
+    CallInputs, CallScheme, CreateInputs, Gas, InstructionResult, Interpreter,
+};
+use crate::primitives::{db::Database, Bytes, CreateScheme, Env, ExecutionResult, B160, U256};
+use crate::Inspector;
+use alloc::{string::String, vec::Vec};
+
+/// Type of the call frame, serialized as the opcode name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "UPPERCASE"))]
+pub enum CallKind {
+    Call,
+    CallCode,
+    DelegateCall,
+    StaticCall,
+    Create,
+    Create2,
+}
+
+impl From<CallScheme> for CallKind {
+    fn from(scheme: CallScheme) -> Self {
+        match scheme {
+            CallScheme::Call => Self::Call,
+            CallScheme::CallCode => Self::CallCode,
+            CallScheme::DelegateCall => Self::DelegateCall,
+            CallScheme::StaticCall => Self::StaticCall,
+        }
+    }
+}
+
+/// Call or create with all its inner calls.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct CallFrame {
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub kind: CallKind,
+    pub from: B160,
+    /// Called address, or created address if creation succeeded.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub to: Option<B160>,
+    /// Transferred value, `None` for `DELEGATECALL` and `STATICCALL`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub value: Option<U256>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::primitives::utilities::serde_hex_u64")
+    )]
+    pub gas: u64,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::primitives::utilities::serde_hex_u64")
+    )]
+    pub gas_used: u64,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::primitives::utilities::serde_hex_bytes")
+    )]
+    pub input: Bytes,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Bytes::is_empty",
+            with = "crate::primitives::utilities::serde_hex_bytes"
+        )
+    )]
+    pub output: Bytes,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub error: Option<String>,
+    /// Decoded `Error(string)` revert reason.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub revert_reason: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub calls: Vec<CallFrame>,
+}
+
+/// Inspector that builds the tree of calls and creates made by the transaction.
+///
+/// Serialized [CallFrame] has the same shape as the output of geth `callTracer`.
+/// When the tracer is reused, the trace of the previous transaction is dropped at the start
+/// of the next one.
+#[derive(Clone, Debug, Default)]
+pub struct CallTracer {
+    /// Frames that are not finished yet.
+    stack: Vec<CallFrame>,
+    root: Option<CallFrame>,
+    /// Index in the block of the traced transaction.
+    tx_index: Option<usize>,
+    /// Last executed opcode, it is the opcode that halted the frame.
+    opcode: u8,
+}
+
+impl CallTracer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index in the block of the traced transaction, `None` if no transaction was traced.
+    pub fn tx_index(&self) -> Option<usize> {
+        self.tx_index
+    }
+
+    /// Returns the top level call frame.
+    ///
+    /// Gas used of the top level frame is set to the gas used by the transaction,
+    /// which includes intrinsic gas and refunds.
+    pub fn into_trace(self, result: &ExecutionResult) -> Option<CallFrame> {
+        self.root.map(|mut root| {
+            root.gas_used = result.gas_used();
+            root
+        })
+    }
+
+    fn start(&mut self, frame: CallFrame) {
+        self.stack.push(frame);
+    }
+
+    fn end(&mut self, ret: InstructionResult, gas: &Gas, out: &Bytes, to: Option<B160>) {
+        let Some(mut frame) = self.stack.pop() else {
+            return;
+        };
+        frame.gas_used = frame.gas.saturating_sub(gas.remaining());
+        frame.output = out.clone();
+        if frame.to.is_none() {
+            frame.to = to;
+        }
+        if let Some(exit) = ret.frame_exit() {
+            frame.error = exit.error(self.opcode);
+            if exit.is_revert() {
+                frame.revert_reason = decode_revert_reason(out);
+            }
+        }
+        match self.stack.last_mut() {
+            Some(parent) => parent.calls.push(frame),
+            None => self.root = Some(frame),
+        }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for CallTracer {
+    fn initialize(&mut self, _env: &Env, tx_index: usize) {
+        self.stack.clear();
+        self.root = None;
+        self.tx_index = Some(tx_index);
+    }
+
+    fn step(&mut self, interp: &mut Interpreter, _data: &mut EVMData<'_, DB>) -> InstructionResult {
+        self.opcode = interp.current_opcode();
+        InstructionResult::Continue
+    }
+
+    fn call(
+        &mut self,
+        data: &mut EVMData<'_, DB>,
+        inputs: &mut CallInputs,
+    ) -> (InstructionResult, Gas, Bytes) {
+        let scheme = inputs.context.scheme;
+        // caller of the delegate call is the contract that made it.
+        let from = match scheme {
+            CallScheme::DelegateCall => inputs.context.address,
+            _ => inputs.context.caller,
+        };
+        let value = match scheme {
+            CallScheme::Call | CallScheme::CallCode => Some(inputs.transfer.value),
+            CallScheme::DelegateCall | CallScheme::StaticCall => None,
+        };
+        let gas = if self.stack.is_empty() {
+            data.env.tx.gas_limit
+        } else {
+            inputs.gas_limit
+        };
+        self.start(CallFrame {
+            kind: scheme.into(),
+            from,
+            to: Some(inputs.context.code_address),
+            value,
+            gas,
+            gas_used: 0,
+            input: inputs.input.clone(),
+            output: Bytes::new(),
+            error: None,
+            revert_reason: None,
+            calls: Vec::new(),
+        });
+        (InstructionResult::Continue, Gas::new(0), Bytes::new())
+    }
+
+    fn call_end(
+        &mut self,
+        _data: &mut EVMData<'_, DB>,
+        _inputs: &CallInputs,
+        remaining_gas: Gas,
+        ret: InstructionResult,
+        out: Bytes,
+    ) -> (InstructionResult, Gas, Bytes) {
+        self.end(ret, &remaining_gas, &out, None);
+        (ret, remaining_gas, out)
+    }
+
+    fn create(
+        &mut self,
+        data: &mut EVMData<'_, DB>,
+        inputs: &mut CreateInputs,
+    ) -> (InstructionResult, Option<B160>, Gas, Bytes) {
+        let kind = match inputs.scheme {
+            CreateScheme::Create => CallKind::Create,
+            CreateScheme::Create2 { .. } => CallKind::Create2,
+        };
+        let gas = if self.stack.is_empty() {
+            data.env.tx.gas_limit
+        } else {
+            inputs.gas_limit
+        };
+        self.start(CallFrame {
+            kind,
+            from: inputs.caller,
+            to: None,
+            value: Some(inputs.value),
+            gas,
+            gas_used: 0,
+            input: inputs.init_code.clone(),
+            output: Bytes::new(),
+            error: None,
+            revert_reason: None,
+            calls: Vec::new(),
+        });
+        (InstructionResult::Continue, None, Gas::new(0), Bytes::new())
+    }
+
+    fn create_end(
+        &mut self,
+        _data: &mut EVMData<'_, DB>,
+        _inputs: &CreateInputs,
+        ret: InstructionResult,
+        address: Option<B160>,
+        remaining_gas: Gas,
+        out: Bytes,
+    ) -> (InstructionResult, Option<B160>, Gas, Bytes) {
+        self.end(ret, &remaining_gas, &out, address);
+        (ret, address, remaining_gas, out)
+    }
+}
+
+/// Decodes revert reason from the ABI encoded `Error(string)`.
+fn decode_revert_reason(out: &[u8]) -> Option<String> {
+    const SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+    let data = out.strip_prefix(&SELECTOR)?;
+    let len = U256::try_from_be_slice(data.get(32..64)?)?;
+    if len > U256::from(data.len()) {
+        return None;
+    }
+    let reason = data.get(64..64 + len.as_limbs()[0] as usize)?;
+    String::from_utf8(reason.to_vec()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{contract, evm_calling, CALLER, INNER, OUTER};
+    use crate::InMemoryDB;
+
+    #[test]
+    fn call_tree() {
+        // reverts with Error("no").
+        let mut inner = Vec::new();
+        for (offset, byte) in [
+            (0, 0x08),
+            (1, 0xc3),
+            (2, 0x79),
+            (3, 0xa0),
+            (35, 0x20),
+            (67, 0x02),
+            (68, b'n'),
+            (69, b'o'),
+        ] {
+            // PUSH1 byte PUSH1 offset MSTORE8
+            inner.extend([0x60, byte, 0x60, offset, 0x53]);
+        }
+        // PUSH1 100 PUSH1 0 REVERT
+        inner.extend([0x60, 100, 0x60, 0x00, 0xfd]);
+
+        // CALL(0xffff, INNER, 0, 0, 0, 0, 0) STOP
+        let mut outer = vec![
+            0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x73,
+        ];
+        outer.extend(INNER.0);
+        outer.extend([0x61, 0xff, 0xff, 0xf1, 0x00]);
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(OUTER, contract(&outer));
+        db.insert_account_info(INNER, contract(&inner));
+
+        let mut evm = evm_calling(db, OUTER);
+        evm.env.tx.gas_limit = 100_000;
+        let mut tracer = CallTracer::new();
+        let result = evm.inspect(&mut tracer).unwrap().result;
+        let root = tracer.into_trace(&result).unwrap();
+
+        assert_eq!(root.kind, CallKind::Call);
+        assert_eq!((root.from, root.to), (CALLER, Some(OUTER)));
+        assert_eq!(root.gas, 100_000);
+        assert_eq!(root.gas_used, result.gas_used());
+        assert_eq!(root.error, None);
+        assert_eq!(root.calls.len(), 1);
+
+        let call = &root.calls[0];
+        assert_eq!((call.from, call.to), (OUTER, Some(INNER)));
+        assert_eq!(call.value, Some(U256::ZERO));
+        assert_eq!(call.gas, 0xffff);
+        assert!(call.gas_used > 0 && call.gas_used < call.gas);
+        assert_eq!(call.output.len(), 100);
+        assert_eq!(call.error.as_deref(), Some("execution reverted"));
+        assert_eq!(call.revert_reason.as_deref(), Some("no"));
+    }
+
+    #[test]
+    fn static_call_violation() {
+        // SSTORE(0, 1)
+        let inner = vec![0x60, 0x01, 0x60, 0x00, 0x55];
+        // STATICCALL(0xffff, INNER, 0, 0, 0, 0) STOP
+        let mut outer = vec![0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x73];
+        outer.extend(INNER.0);
+        outer.extend([0x61, 0xff, 0xff, 0xfa, 0x00]);
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(OUTER, contract(&outer));
+        db.insert_account_info(INNER, contract(&inner));
+
+        let mut evm = evm_calling(db, OUTER);
+        let mut tracer = CallTracer::new();
+        let out = evm.inspect(&mut tracer).unwrap();
+        let violation = out.static_call_violation.unwrap();
+        assert_eq!(
+            (violation.opcode, violation.depth, violation.address),
+            (0x55, 2, INNER)
+        );
+
+        let root = tracer.into_trace(&out.result).unwrap();
+        assert_eq!(root.error, None);
+        assert_eq!(root.calls[0].error.as_deref(), Some("write protection"));
+    }
+
+    #[test]
+    fn geth_errors() {
+        let halt = |result: InstructionResult, opcode| result.frame_exit().unwrap().error(opcode);
+        assert_eq!(halt(InstructionResult::Stop, 0x00), None);
+        assert_eq!(
+            halt(InstructionResult::Revert, 0xfd).as_deref(),
+            Some("execution reverted")
+        );
+        assert_eq!(
+            halt(InstructionResult::OutOfGas, 0x01).as_deref(),
+            Some("out of gas")
+        );
+        assert_eq!(
+            halt(InstructionResult::MemoryOOG, 0x52).as_deref(),
+            Some("out of gas")
+        );
+        assert_eq!(
+            halt(InstructionResult::InvalidFEOpcode, 0xfe).as_deref(),
+            Some("invalid opcode: INVALID")
+        );
+        assert_eq!(
+            halt(InstructionResult::OpcodeNotFound, 0xef).as_deref(),
+            Some("invalid opcode: opcode 0xef not defined")
+        );
+        assert_eq!(
+            halt(InstructionResult::NotActivated, 0x5f).as_deref(),
+            Some("invalid opcode: PUSH0")
+        );
+        assert_eq!(
+            halt(InstructionResult::InvalidJump, 0x56).as_deref(),
+            Some("invalid jump destination")
+        );
+        assert_eq!(
+            halt(InstructionResult::CallNotAllowedInsideStatic, 0xf1).as_deref(),
+            Some("write protection")
+        );
+        assert_eq!(
+            halt(InstructionResult::CallTooDeep, 0xf1).as_deref(),
+            Some("max call depth exceeded")
+        );
+    }
+
+    #[test]
+    fn reused_between_transactions() {
+        let mut evm = evm_calling(InMemoryDB::default(), OUTER);
+        evm.env.tx.gas_limit = 100_000;
+
+        let mut tracer = CallTracer::new();
+        assert_eq!(tracer.tx_index(), None);
+        for (tx_index, to) in [OUTER, INNER].into_iter().enumerate() {
+            evm.env.tx.transact_to = crate::primitives::TransactTo::Call(to);
+            let result = evm.inspect_commit(&mut tracer).unwrap();
+            assert_eq!(tracer.tx_index(), Some(tx_index));
+            let trace = tracer.clone().into_trace(&result).unwrap();
+            assert_eq!(trace.to, Some(to));
+        }
+        assert_eq!(evm.tx_index, 2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize() {
+        let frame = CallFrame {
+            kind: CallKind::DelegateCall,
+            from: OUTER,
+            to: Some(INNER),
+            value: None,
+            gas: 0x100,
+            gas_used: 0x10,
+            input: Bytes::from_static(&[0x12]),
+            output: Bytes::new(),
+            error: None,
+            revert_reason: None,
+            calls: Vec::new(),
+        };
+        let json = serde_json::to_value(&frame).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "type": "DELEGATECALL",
+                "from": "0x1010101010101010101010101010101010101010",
+                "to": "0x2020202020202020202020202020202020202020",
+                "gas": "0x100",
+                "gasUsed": "0x10",
+                "input": "0x12",
+            })
+        );
+    }
+}
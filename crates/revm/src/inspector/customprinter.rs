@@ -23,7 +23,10 @@ impl<DB: Database> Inspector<DB> for CustomPrintTracer {
     // all other information can be obtained from interp.
     fn step(&mut self, interp: &mut Interpreter, data: &mut EVMData<'_, DB>) -> InstructionResult {
         let opcode = interp.current_opcode();
-        let opcode_str = opcode::OPCODE_JUMPMAP[opcode as usize];
+        let opcode_str = opcode::OPCODE_JUMPMAP
+            .get(opcode as usize)
+            .copied()
+            .flatten();
 
         let gas_remaining = self.gas_inspector.gas_remaining();
 
@@ -134,7 +134,7 @@ mod tests {
             evm_data: &mut EVMData<'_, DB>,
             address: &B160,
             topics: &[B256],
-            data: &Bytes,
+            data: &[u8],
         ) {
             self.gas_inspector.log(evm_data, address, topics, data);
         }
@@ -235,7 +235,7 @@ mod tests {
         evm.env.tx.gas_limit = 21100;
 
         let mut inspector = StackInspector::default();
-        let ResultAndState { result, state } = evm.inspect(&mut inspector).unwrap();
+        let ResultAndState { result, state, .. } = evm.inspect(&mut inspector).unwrap();
         println!("{result:?} {state:?} {inspector:?}");
 
         for (pc, gas) in inspector.gas_remaining_steps {
@@ -0,0 +1,379 @@
+//! Prestate tracer with the output of geth `prestateTracer`.
+
+use crate::evm_impl::EVMData;
+use crate::interpreter::{opcode, CallInputs, CreateInputs, Gas, InstructionResult, Interpreter};
+use crate::primitives::{db::Database, Account, Bytes, Env, State, B160, B256, KECCAK_EMPTY, U256};
+use crate::Inspector;
+use alloc::collections::{btree_map::Entry, BTreeMap};
+use alloc::vec;
+
+/// State of the account, fields that are not known or not changed are `None`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrestateAccount {
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub balance: Option<U256>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub nonce: Option<u64>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "serde_opt_bytes"
+        )
+    )]
+    pub code: Option<Bytes>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    pub storage: BTreeMap<B256, B256>,
+}
+
+/// Accounts by address, sorted.
+pub type Prestate = BTreeMap<B160, PrestateAccount>;
+
+/// Changes made by the transaction, output of `prestateTracer` with `diffMode` enabled.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrestateDiff {
+    /// State of the changed accounts before the transaction.
+    pub pre: Prestate,
+    /// Changed fields of the accounts after the transaction.
+    /// Selfdestructed accounts are not included.
+    pub post: Prestate,
+}
+
+/// Records the state of the accounts and storage slots before the transaction,
+/// the first time they are accessed by the transaction.
+///
+/// Values are loaded from the database the transaction is executed on, so it needs to be used
+/// with [crate::EVM::inspect] or [crate::EVM::inspect_ref], before the state is committed.
+/// It can be reused between transactions, recorded state is cleared when the next one starts.
+#[derive(Clone, Debug, Default)]
+pub struct PrestateTracer {
+    /// Accessed accounts, `None` if the account did not exist.
+    accounts: BTreeMap<B160, Option<PrestateAccount>>,
+    /// Accessed storage slots.
+    storage: BTreeMap<B160, BTreeMap<B256, B256>>,
+}
+
+impl PrestateTracer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// State before the transaction of all accounts and storage slots that were accessed.
+    pub fn prestate(&self) -> Prestate {
+        self.accounts
+            .iter()
+            .map(|(address, account)| {
+                let mut pre = account.clone().unwrap_or_else(|| PrestateAccount {
+                    balance: Some(U256::ZERO),
+                    nonce: Some(0),
+                    ..Default::default()
+                });
+                pre.storage = self.storage.get(address).cloned().unwrap_or_default();
+                (*address, pre)
+            })
+            .collect()
+    }
+
+    /// State before and after the transaction of the accounts that were changed.
+    ///
+    /// `state` is the state returned by the transaction.
+    pub fn diff(&self, state: &State) -> PrestateDiff {
+        let mut diff = PrestateDiff::default();
+        for (address, account) in state {
+            if !account.is_touched() {
+                continue;
+            }
+            let pre_account = self.accounts.get(address).cloned().flatten();
+            let post_account = post_account(pre_account.as_ref(), account);
+            if post_account.as_ref() == Some(&PrestateAccount::default()) {
+                continue;
+            }
+            if let Some(mut pre) = pre_account {
+                pre.storage = account
+                    .storage
+                    .iter()
+                    .filter(|(_, slot)| slot.is_changed())
+                    .map(|(index, slot)| (to_b256(*index), to_b256(slot.original_value)))
+                    .collect();
+                diff.pre.insert(*address, pre);
+            }
+            if let Some(post) = post_account {
+                diff.post.insert(*address, post);
+            }
+        }
+        diff
+    }
+
+    /// Records the account if it was not accessed before.
+    fn record_account<DB: Database>(
+        &mut self,
+        db: &mut DB,
+        address: B160,
+    ) -> Result<(), DB::Error> {
+        if let Entry::Vacant(entry) = self.accounts.entry(address) {
+            entry.insert(load_account(db, address)?);
+        }
+        Ok(())
+    }
+
+    /// Records the storage slot and its account if they were not accessed before.
+    fn record_slot<DB: Database>(
+        &mut self,
+        db: &mut DB,
+        address: B160,
+        index: U256,
+    ) -> Result<(), DB::Error> {
+        self.record_account(db, address)?;
+        if let Entry::Vacant(entry) = self
+            .storage
+            .entry(address)
+            .or_default()
+            .entry(to_b256(index))
+        {
+            entry.insert(to_b256(db.storage(address, index)?));
+        }
+        Ok(())
+    }
+}
+
+/// Continues the execution if the state was recorded, database error stops it.
+fn check<DB: Database>(
+    data: &mut EVMData<'_, DB>,
+    result: Result<(), DB::Error>,
+) -> InstructionResult {
+    match result {
+        Ok(()) => InstructionResult::Continue,
+        Err(error) => {
+            data.error = Some(error);
+            InstructionResult::FatalExternalError
+        }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for PrestateTracer {
+    fn initialize(&mut self, _env: &Env, _tx_index: usize) {
+        self.accounts.clear();
+        self.storage.clear();
+    }
+
+    fn step(&mut self, interp: &mut Interpreter, data: &mut EVMData<'_, DB>) -> InstructionResult {
+        let Ok(top) = interp.stack.peek(0) else {
+            return InstructionResult::Continue;
+        };
+        let result = match interp.current_opcode() {
+            opcode::SLOAD | opcode::SSTORE => {
+                self.record_slot(data.db, interp.contract.address, top)
+            }
+            opcode::BALANCE
+            | opcode::EXTCODESIZE
+            | opcode::EXTCODECOPY
+            | opcode::EXTCODEHASH
+            | opcode::SELFDESTRUCT => {
+                self.record_account(data.db, B160::from(B256(top.to_be_bytes())))
+            }
+            _ => Ok(()),
+        };
+        check(data, result)
+    }
+
+    fn call(
+        &mut self,
+        data: &mut EVMData<'_, DB>,
+        inputs: &mut CallInputs,
+    ) -> (InstructionResult, Gas, Bytes) {
+        let mut addresses = vec![
+            inputs.context.caller,
+            inputs.context.address,
+            inputs.context.code_address,
+        ];
+        if data.journaled_state.depth() == 0 {
+            addresses.push(data.env.block.coinbase);
+        }
+        let result = addresses
+            .into_iter()
+            .try_for_each(|address| self.record_account(data.db, address));
+        (check(data, result), Gas::new(0), Bytes::new())
+    }
+
+    fn create(
+        &mut self,
+        data: &mut EVMData<'_, DB>,
+        inputs: &mut CreateInputs,
+    ) -> (InstructionResult, Option<B160>, Gas, Bytes) {
+        let mut result = self.record_account(data.db, inputs.caller);
+        if result.is_ok() && data.journaled_state.depth() == 0 {
+            result = self.record_account(data.db, data.env.block.coinbase);
+        }
+        (check(data, result), None, Gas::new(0), Bytes::default())
+    }
+
+    fn create_end(
+        &mut self,
+        data: &mut EVMData<'_, DB>,
+        _inputs: &CreateInputs,
+        ret: InstructionResult,
+        address: Option<B160>,
+        remaining_gas: Gas,
+        out: Bytes,
+    ) -> (InstructionResult, Option<B160>, Gas, Bytes) {
+        if let Some(created) = address {
+            if let Err(error) = self.record_account(data.db, created) {
+                data.error = Some(error);
+                return (
+                    InstructionResult::FatalExternalError,
+                    address,
+                    remaining_gas,
+                    out,
+                );
+            }
+        }
+        (ret, address, remaining_gas, out)
+    }
+}
+
+/// Loads account from the database, `None` if it does not exist.
+fn load_account<DB: Database>(
+    db: &mut DB,
+    address: B160,
+) -> Result<Option<PrestateAccount>, DB::Error> {
+    let Some(info) = db.basic(address)? else {
+        return Ok(None);
+    };
+    if info.is_empty() {
+        return Ok(None);
+    }
+    let code = match info.code {
+        Some(code) => code.original_bytes(),
+        None if info.code_hash != KECCAK_EMPTY => db.code_by_hash(info.code_hash)?.original_bytes(),
+        None => Bytes::new(),
+    };
+    Ok(Some(PrestateAccount {
+        balance: Some(info.balance),
+        nonce: Some(info.nonce),
+        code: (!code.is_empty()).then_some(code),
+        storage: BTreeMap::new(),
+    }))
+}
+
+/// Fields of the account that changed, `None` if account is selfdestructed.
+fn post_account(pre: Option<&PrestateAccount>, account: &Account) -> Option<PrestateAccount> {
+    if account.is_selfdestructed() {
+        return None;
+    }
+    let pre = pre.cloned().unwrap_or_default();
+    let info = &account.info;
+    let code = info
+        .code
+        .as_ref()
+        .map(|code| code.original_bytes())
+        .filter(|code| !code.is_empty());
+    Some(PrestateAccount {
+        balance: (pre.balance != Some(info.balance)).then_some(info.balance),
+        nonce: (pre.nonce != Some(info.nonce)).then_some(info.nonce),
+        code: code.filter(|code| pre.code.as_ref() != Some(code)),
+        storage: account
+            .storage
+            .iter()
+            .filter(|(_, slot)| slot.is_changed() && slot.present_value != U256::ZERO)
+            .map(|(index, slot)| (to_b256(*index), to_b256(slot.present_value)))
+            .collect(),
+    })
+}
+
+fn to_b256(value: U256) -> B256 {
+    B256(value.to_be_bytes())
+}
+
+#[cfg(feature = "serde")]
+mod serde_opt_bytes {
+    use crate::primitives::{utilities::serde_hex_bytes, Bytes};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(x: &Option<Bytes>, s: S) -> Result<S::Ok, S::Error> {
+        match x {
+            Some(x) => serde_hex_bytes::serialize(x, s),
+            None => s.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<Bytes>, D::Error> {
+        #[derive(Deserialize)]
+        struct Wrapper(#[serde(with = "serde_hex_bytes")] Bytes);
+        Ok(Option::<Wrapper>::deserialize(d)?.map(|Wrapper(x)| x))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::AccountInfo;
+    use crate::test_utils::{evm_with_contract, CALLER, OUTER};
+
+    fn run() -> (PrestateTracer, State) {
+        // PUSH1 2 PUSH1 0 SSTORE PUSH1 1 SLOAD POP
+        let mut evm = evm_with_contract(&[0x60, 0x02, 0x60, 0x00, 0x55, 0x60, 0x01, 0x54, 0x50]);
+        let db = evm.db().unwrap();
+        db.insert_account_info(CALLER, AccountInfo::from_balance(U256::from(10)));
+        db.insert_account_storage(OUTER, U256::ZERO, U256::from(1))
+            .unwrap();
+        evm.env.tx.value = U256::from(3);
+        let mut tracer = PrestateTracer::new();
+        let state = evm.inspect(&mut tracer).unwrap().state;
+        (tracer, state)
+    }
+
+    #[test]
+    fn prestate() {
+        let (tracer, _) = run();
+        let prestate = tracer.prestate();
+
+        assert_eq!(prestate[&CALLER].balance, Some(U256::from(10)));
+        let contract = &prestate[&OUTER];
+        assert_eq!(contract.nonce, Some(1));
+        assert!(contract.code.is_some());
+        // read slot is included.
+        assert_eq!(
+            contract.storage,
+            [
+                (to_b256(U256::ZERO), to_b256(U256::from(1))),
+                (to_b256(U256::from(1)), to_b256(U256::ZERO))
+            ]
+            .into_iter()
+            .collect()
+        );
+    }
+
+    #[test]
+    fn diff() {
+        let (tracer, state) = run();
+        let diff = tracer.diff(&state);
+
+        let pre = &diff.pre[&OUTER];
+        assert_eq!(pre.balance, Some(U256::ZERO));
+        assert_eq!(pre.storage.len(), 1);
+
+        let post = &diff.post[&OUTER];
+        assert_eq!(post.balance, Some(U256::from(3)));
+        assert_eq!(post.nonce, None);
+        assert_eq!(post.code, None);
+        assert_eq!(
+            post.storage,
+            [(to_b256(U256::ZERO), to_b256(U256::from(2)))]
+                .into_iter()
+                .collect()
+        );
+        assert_eq!(diff.post[&CALLER].balance, Some(U256::from(7)));
+    }
+}
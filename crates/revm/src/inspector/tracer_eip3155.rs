@@ -10,7 +10,7 @@ use serde_json::json;
 use std::io::Write;
 
 pub struct TracerEip3155 {
-    output: Box<dyn Write>,
+    output: Box<dyn Write + Send>,
     gas_inspector: GasInspector,
 
     #[allow(dead_code)]
@@ -29,7 +29,7 @@ pub struct TracerEip3155 {
 }
 
 impl TracerEip3155 {
-    pub fn new(output: Box<dyn Write>, trace_mem: bool, trace_return_data: bool) -> Self {
+    pub fn new(output: Box<dyn Write + Send>, trace_mem: bool, trace_return_data: bool) -> Self {
         Self {
             output,
             gas_inspector: GasInspector::default(),
@@ -107,16 +107,24 @@ impl<DB: Database> Inspector<DB> for TracerEip3155 {
         // self.log_step(interp, data, is_static, eval);
         self.skip = true;
         if data.journaled_state.depth() == 0 {
-            let log_line = json!({
+            let exit = ret.frame_exit();
+            let mut log_line = json!({
                 //stateroot
                 "output": format!("0x{}", hex::encode(out.as_ref())),
                 "gasUsed": format!("0x{:x}", self.gas_inspector.gas_remaining()),
+                "pass": exit.map_or(false, |exit| exit.is_success()),
                 //time
                 //fork
             });
-
-            writeln!(self.output, "{}", serde_json::to_string(&log_line).unwrap())
-                .expect("If output fails we can ignore the logging");
+            if let (Some(error), Some(log_line)) = (
+                exit.and_then(|exit| exit.error(self.opcode)),
+                log_line.as_object_mut(),
+            ) {
+                log_line.insert("error".into(), error.into());
+            }
+
+            // If output fails we can ignore the logging.
+            let _ = writeln!(self.output, "{log_line}");
         }
         (ret, remaining_gas, out)
     }
@@ -165,14 +173,14 @@ impl TracerEip3155 {
             "depth": depth,
             //returnData
             //refund
-            "opName": opcode::OPCODE_JUMPMAP[self.opcode as usize],
+            "opName": opcode::OPCODE_JUMPMAP.get(self.opcode as usize).copied().flatten(),
             //error
             //storage
             //returnStack
         });
 
-        writeln!(self.output, "{}", serde_json::to_string(&log_line).unwrap())
-            .expect("If output fails we can ignore the logging");
+        // If output fails we can ignore the logging.
+        let _ = writeln!(self.output, "{log_line}");
     }
 }
 
@@ -1,7 +1,7 @@
 use crate::interpreter::{inner_models::SelfDestructResult, InstructionResult};
 use crate::primitives::{
-    db::Database, hash_map::Entry, Account, Bytecode, HashMap, Log, State, StorageSlot,
-    TransientStorage, B160, KECCAK_EMPTY, U256,
+    db::Database, hash_map::Entry, Account, Bytecode, DatabaseReadCounts, HashMap, InternalError,
+    Log, State, StorageSlot, TransientStorage, B160, KECCAK_EMPTY, U256,
 };
 use alloc::{vec, vec::Vec};
 use core::mem::{self};
@@ -25,9 +25,14 @@ pub struct JournaledState {
     /// so we need to take care of that difference. Set this to false if you are handling
     /// legacy transactions
     pub is_before_spurious_dragon: bool,
+    /// Balance of an account that selfdestructs with itself as the beneficiary is burned.
+    /// Set this to false to keep the balance on the account until it is removed.
+    pub burn_selfdestruct_to_self: bool,
     /// It is assumed that precompiles start from 0x1 address and spand next N addresses.
     /// we are using that assumption here
     pub num_of_precompiles: usize,
+    /// Number of database calls that were needed to load the state.
+    pub db_reads: DatabaseReadCounts,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -84,6 +89,32 @@ pub enum JournalEntry {
     CodeChange { address: B160, had_code: Bytecode },
 }
 
+/// Existence of an account as seen by the EVM, see [`JournaledState::load_account_existence`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AccountExistence {
+    /// Account is not in the database and was not touched in this transaction.
+    NotExisting,
+    /// Account exists, but has zero nonce, zero balance and no code (EIP-161 empty).
+    Empty,
+    /// Account has nonce, balance or code.
+    Exists,
+}
+
+impl AccountExistence {
+    /// Returns true if the account exists by the rules of the fork.
+    ///
+    /// Before Spurious Dragon empty accounts exist. After it (EIP-161) empty accounts
+    /// are treated as not existing, this is what e.g. CALL gas cost and EXTCODEHASH use.
+    pub fn exists(&self, is_before_spurious_dragon: bool) -> bool {
+        match self {
+            Self::NotExisting => false,
+            Self::Empty => is_before_spurious_dragon,
+            Self::Exists => true,
+        }
+    }
+}
+
 /// SubRoutine checkpoint that will help us to go back from this
 pub struct JournalCheckpoint {
     log_i: usize,
@@ -106,7 +137,9 @@ impl JournaledState {
             journal: vec![vec![]],
             depth: 0,
             is_before_spurious_dragon: false,
+            burn_selfdestruct_to_self: true,
             num_of_precompiles,
+            db_reads: DatabaseReadCounts::default(),
         }
     }
 
@@ -129,13 +162,13 @@ impl JournaledState {
     /// be removed from state.
     pub fn touch(&mut self, address: &B160) {
         if let Some(account) = self.state.get_mut(address) {
-            Self::touch_account(self.journal.last_mut().unwrap(), address, account);
+            Self::touch_account(&mut self.journal, address, account);
         }
     }
 
-    fn touch_account(journal: &mut Vec<JournalEntry>, address: &B160, account: &mut Account) {
+    fn touch_account(journal: &mut Vec<Vec<JournalEntry>>, address: &B160, account: &mut Account) {
         if !account.is_touched() {
-            journal.push(JournalEntry::AccountTouched { address: *address });
+            push_journal(journal, JournalEntry::AccountTouched { address: *address });
             account.mark_touch();
         }
     }
@@ -150,9 +183,9 @@ impl JournaledState {
         (state, logs)
     }
 
-    /// Use it with load_account function.
-    pub fn account(&self, address: B160) -> &Account {
-        self.state.get(&address).unwrap() // Always assume that acc is already loaded
+    /// Use it with load_account function, returns `None` if account is not loaded.
+    pub fn account(&self, address: B160) -> Option<&Account> {
+        self.state.get(&address)
     }
 
     pub fn depth(&self) -> u64 {
@@ -160,38 +193,43 @@ impl JournaledState {
     }
 
     /// use it only if you know that acc is hot
-    /// Assume account is hot
-    pub fn set_code(&mut self, address: B160, code: Bytecode) {
-        let account = self.state.get_mut(&address).unwrap();
-        Self::touch_account(self.journal.last_mut().unwrap(), &address, account);
-
-        self.journal
-            .last_mut()
-            .unwrap()
-            .push(JournalEntry::CodeChange {
+    /// Assume account is hot, error is returned if it is not loaded.
+    pub fn set_code(&mut self, address: B160, code: Bytecode) -> Result<(), InternalError> {
+        let account = self
+            .state
+            .get_mut(&address)
+            .ok_or(InternalError::AccountNotLoaded(address))?;
+        Self::touch_account(&mut self.journal, &address, account);
+
+        push_journal(
+            &mut self.journal,
+            JournalEntry::CodeChange {
                 address,
                 had_code: code.clone(),
-            });
+            },
+        );
 
         account.info.code_hash = code.hash();
         account.info.code = Some(code);
+        Ok(())
     }
 
-    pub fn inc_nonce(&mut self, address: B160) -> Option<u64> {
-        let account = self.state.get_mut(&address).unwrap();
+    /// Increments nonce of loaded account, returns `None` if nonce would overflow.
+    pub fn inc_nonce(&mut self, address: B160) -> Result<Option<u64>, InternalError> {
+        let account = self
+            .state
+            .get_mut(&address)
+            .ok_or(InternalError::AccountNotLoaded(address))?;
         // Check if nonce is going to overflow.
         if account.info.nonce == u64::MAX {
-            return None;
+            return Ok(None);
         }
-        Self::touch_account(self.journal.last_mut().unwrap(), &address, account);
-        self.journal
-            .last_mut()
-            .unwrap()
-            .push(JournalEntry::NonceChange { address });
+        Self::touch_account(&mut self.journal, &address, account);
+        push_journal(&mut self.journal, JournalEntry::NonceChange { address });
 
         account.info.nonce += 1;
 
-        Some(account.info.nonce)
+        Ok(Some(account.info.nonce))
     }
 
     pub fn transfer<DB: Database>(
@@ -209,30 +247,36 @@ impl JournaledState {
             .map_err(|_| InstructionResult::FatalExternalError)?;
 
         // sub balance from
-        let from_account = &mut self.state.get_mut(from).unwrap();
-        Self::touch_account(self.journal.last_mut().unwrap(), from, from_account);
+        let from_account = self
+            .state
+            .get_mut(from)
+            .ok_or(InstructionResult::FatalExternalError)?;
+        Self::touch_account(&mut self.journal, from, from_account);
         let from_balance = &mut from_account.info.balance;
         *from_balance = from_balance
             .checked_sub(balance)
             .ok_or(InstructionResult::OutOfFund)?;
 
         // add balance to
-        let to_account = &mut self.state.get_mut(to).unwrap();
-        Self::touch_account(self.journal.last_mut().unwrap(), to, to_account);
+        let to_account = self
+            .state
+            .get_mut(to)
+            .ok_or(InstructionResult::FatalExternalError)?;
+        Self::touch_account(&mut self.journal, to, to_account);
         let to_balance = &mut to_account.info.balance;
         *to_balance = to_balance
             .checked_add(balance)
             .ok_or(InstructionResult::OverflowPayment)?;
         // Overflow of U256 balance is not possible to happen on mainnet. We dont bother to return funds from from_acc.
 
-        self.journal
-            .last_mut()
-            .unwrap()
-            .push(JournalEntry::BalanceTransfer {
+        push_journal(
+            &mut self.journal,
+            JournalEntry::BalanceTransfer {
                 from: *from,
                 to: *to,
                 balance,
-            });
+            },
+        );
 
         Ok(())
     }
@@ -260,8 +304,10 @@ impl JournaledState {
         let checkpoint = self.checkpoint();
 
         // Newly created account is present, as we just loaded it.
-        let account = self.state.get_mut(&address).unwrap();
-        let last_journal = self.journal.last_mut().unwrap();
+        let Some(account) = self.state.get_mut(&address) else {
+            self.checkpoint_revert(checkpoint);
+            return Err(InstructionResult::FatalExternalError);
+        };
 
         // check if it is possible to create this account.
         if Self::check_account_collision(address, account, self.num_of_precompiles) {
@@ -284,7 +330,7 @@ impl JournaledState {
 
         // touch account. This is important as for pre SpuriousDragon account could be
         // saved even empty.
-        Self::touch_account(last_journal, &address, account);
+        Self::touch_account(&mut self.journal, &address, account);
 
         // Add balance to created account, as we already have target here.
         let Some(new_balance) = account.info.balance.checked_add(balance) else {
@@ -296,20 +342,26 @@ impl JournaledState {
         // EIP-161: State trie clearing (invariant-preserving alternative)
         if SPEC::enabled(SPURIOUS_DRAGON) {
             account.info.nonce = 1;
-            last_journal.push(JournalEntry::NonceChange { address });
+            push_journal(&mut self.journal, JournalEntry::NonceChange { address });
         }
 
         // Sub balance from caller
-        let caller_account = self.state.get_mut(&caller).unwrap();
+        let Some(caller_account) = self.state.get_mut(&caller) else {
+            self.checkpoint_revert(checkpoint);
+            return Err(InstructionResult::FatalExternalError);
+        };
         // Balance is already checked in `create_inner`, so it is safe to just substract.
         caller_account.info.balance -= balance;
 
         // add journal entry of transfered balance
-        last_journal.push(JournalEntry::BalanceTransfer {
-            from: caller,
-            to: address,
-            balance,
-        });
+        push_journal(
+            &mut self.journal,
+            JournalEntry::BalanceTransfer {
+                from: caller,
+                to: address,
+                balance,
+            },
+        );
 
         Ok(checkpoint)
     }
@@ -345,6 +397,7 @@ impl JournaledState {
     ) {
         const PRECOMPILE3: B160 =
             B160([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3]);
+        // accounts that are not in the state have nothing to revert.
         for entry in journal_entries.into_iter().rev() {
             match entry {
                 JournalEntry::AccountLoaded { address } => {
@@ -358,7 +411,9 @@ impl JournaledState {
                         continue;
                     }
                     // remove touched status
-                    state.get_mut(&address).unwrap().unmark_touch();
+                    if let Some(account) = state.get_mut(&address) {
+                        account.unmark_touch();
+                    }
                 }
                 JournalEntry::AccountDestroyed {
                     address,
@@ -366,43 +421,53 @@ impl JournaledState {
                     was_destroyed,
                     had_balance,
                 } => {
-                    let account = state.get_mut(&address).unwrap();
-                    // set previous ste of selfdestructed flag. as there could be multiple
-                    // selfdestructs in one transaction.
-                    if was_destroyed {
-                        // flag is still selfdestructed
-                        account.mark_selfdestruct();
-                    } else {
-                        // flag that is not selfdestructed
-                        account.unmark_selfdestruct();
+                    if let Some(account) = state.get_mut(&address) {
+                        // set previous ste of selfdestructed flag. as there could be multiple
+                        // selfdestructs in one transaction.
+                        if was_destroyed {
+                            // flag is still selfdestructed
+                            account.mark_selfdestruct();
+                        } else {
+                            // flag that is not selfdestructed
+                            account.unmark_selfdestruct();
+                        }
+                        account.info.balance += had_balance;
                     }
-                    account.info.balance += had_balance;
 
                     if address != target {
-                        let target = state.get_mut(&target).unwrap();
-                        target.info.balance -= had_balance;
+                        if let Some(target) = state.get_mut(&target) {
+                            target.info.balance -= had_balance;
+                        }
                     }
                 }
                 JournalEntry::BalanceTransfer { from, to, balance } => {
                     // we dont need to check overflow and underflow when adding sub subtracting the balance.
-                    let from = state.get_mut(&from).unwrap();
-                    from.info.balance += balance;
-                    let to = state.get_mut(&to).unwrap();
-                    to.info.balance -= balance;
+                    if let Some(from) = state.get_mut(&from) {
+                        from.info.balance += balance;
+                    }
+                    if let Some(to) = state.get_mut(&to) {
+                        to.info.balance -= balance;
+                    }
                 }
                 JournalEntry::NonceChange { address } => {
-                    state.get_mut(&address).unwrap().info.nonce -= 1;
+                    if let Some(account) = state.get_mut(&address) {
+                        account.info.nonce -= 1;
+                    }
                 }
                 JournalEntry::StorageChange {
                     address,
                     key,
                     had_value,
                 } => {
-                    let storage = &mut state.get_mut(&address).unwrap().storage;
+                    let Some(account) = state.get_mut(&address) else {
+                        continue;
+                    };
                     if let Some(had_value) = had_value {
-                        storage.get_mut(&key).unwrap().present_value = had_value;
+                        if let Some(slot) = account.storage.get_mut(&key) {
+                            slot.present_value = had_value;
+                        }
                     } else {
-                        storage.remove(&key);
+                        account.storage.remove(&key);
                     }
                 }
                 JournalEntry::TransientStorageChange {
@@ -420,9 +485,10 @@ impl JournaledState {
                     }
                 }
                 JournalEntry::CodeChange { address, had_code } => {
-                    let acc = state.get_mut(&address).unwrap();
-                    acc.info.code_hash = had_code.hash();
-                    acc.info.code = Some(had_code);
+                    if let Some(acc) = state.get_mut(&address) {
+                        acc.info.code_hash = had_code.hash();
+                        acc.info.code = Some(had_code);
+                    }
                 }
             }
         }
@@ -452,7 +518,7 @@ impl JournaledState {
         self.journal
             .iter_mut()
             .rev()
-            .take(leng - checkpoint.journal_i)
+            .take(leng.saturating_sub(checkpoint.journal_i))
             .for_each(|cs| {
                 Self::journal_revert(
                     state,
@@ -474,31 +540,39 @@ impl JournaledState {
         db: &mut DB,
     ) -> Result<SelfDestructResult, DB::Error> {
         let (is_cold, target_exists) = self.load_account_exist(target, db)?;
+        let burn_selfdestruct_to_self = self.burn_selfdestruct_to_self;
         // transfer all the balance
-        let acc = self.state.get_mut(&address).unwrap();
-        let balance = mem::take(&mut acc.info.balance);
+        let (acc, _) = self.load_account(address, db)?;
+        let balance = acc.info.balance;
         let previously_destroyed = acc.is_selfdestructed();
-        acc.mark_selfdestruct();
 
         // NOTE: In case that target and destroyed addresses are same, balance will be lost.
         // ref: https://github.com/ethereum/go-ethereum/blob/141cd425310b503c5678e674a8c3872cf46b7086/core/vm/instructions.go#L832-L833
         // https://github.com/ethereum/go-ethereum/blob/141cd425310b503c5678e674a8c3872cf46b7086/core/state/statedb.go#L449
+        // Unless `burn_selfdestruct_to_self` is disabled, then the account is not destroyed
+        // and the balance stays on it.
+        let removed_balance = if address != target || burn_selfdestruct_to_self {
+            acc.mark_selfdestruct();
+            mem::take(&mut acc.info.balance)
+        } else {
+            U256::ZERO
+        };
         if address != target {
-            let target_account = self.state.get_mut(&target).unwrap();
-            // touch target account
-            Self::touch_account(self.journal.last_mut().unwrap(), &target, target_account);
+            let (target_account, _) = self.load_account(target, db)?;
             target_account.info.balance += balance;
+            // touch target account
+            self.touch(&target);
         }
 
-        self.journal
-            .last_mut()
-            .unwrap()
-            .push(JournalEntry::AccountDestroyed {
+        push_journal(
+            &mut self.journal,
+            JournalEntry::AccountDestroyed {
                 address,
                 target,
                 was_destroyed: previously_destroyed,
-                had_balance: balance,
-            });
+                had_balance: removed_balance,
+            },
+        );
 
         Ok(SelfDestructResult {
             had_value: balance != U256::ZERO,
@@ -513,16 +587,8 @@ impl JournaledState {
         address: B160,
         db: &mut DB,
     ) -> Result<&mut Account, DB::Error> {
-        let account = self.initial_account_load(address, &[], db)?;
-        if account.info.code.is_none() {
-            if account.info.code_hash == KECCAK_EMPTY {
-                account.info.code = Some(Bytecode::new());
-            } else {
-                // load code if requested
-                account.info.code = Some(db.code_by_hash(account.info.code_hash)?);
-            }
-        }
-
+        let account = Self::initial_load(&mut self.state, &mut self.db_reads, address, &[], db)?;
+        Self::load_account_code(account, &mut self.db_reads, db)?;
         Ok(account)
     }
 
@@ -533,18 +599,32 @@ impl JournaledState {
         slots: &[U256],
         db: &mut DB,
     ) -> Result<&mut Account, DB::Error> {
+        Self::initial_load(&mut self.state, &mut self.db_reads, address, slots, db)
+    }
+
+    fn initial_load<'a, DB: Database>(
+        state: &'a mut State,
+        db_reads: &mut DatabaseReadCounts,
+        address: B160,
+        slots: &[U256],
+        db: &mut DB,
+    ) -> Result<&'a mut Account, DB::Error> {
         // load or get account.
-        let account = match self.state.entry(address) {
+        let account = match state.entry(address) {
             Entry::Occupied(entry) => entry.into_mut(),
-            Entry::Vacant(vac) => vac.insert(
-                db.basic(address)?
-                    .map(|i| i.into())
-                    .unwrap_or(Account::new_not_existing()),
-            ),
+            Entry::Vacant(vac) => {
+                db_reads.basic += 1;
+                vac.insert(
+                    db.basic(address)?
+                        .map(|i| i.into())
+                        .unwrap_or(Account::new_not_existing()),
+                )
+            }
         };
         // preload storages.
         for slot in slots {
             if let Entry::Vacant(entry) = account.storage.entry(*slot) {
+                db_reads.storage += 1;
                 let storage = db.storage(address, *slot)?;
                 entry.insert(StorageSlot::new(storage));
             }
@@ -558,27 +638,14 @@ impl JournaledState {
         address: B160,
         db: &mut DB,
     ) -> Result<(&mut Account, bool), DB::Error> {
-        Ok(match self.state.entry(address) {
-            Entry::Occupied(entry) => (entry.into_mut(), false),
-            Entry::Vacant(vac) => {
-                let account = if let Some(account) = db.basic(address)? {
-                    account.into()
-                } else {
-                    Account::new_not_existing()
-                };
-
-                // journal loading of account. AccessList touch.
-                self.journal
-                    .last_mut()
-                    .unwrap()
-                    .push(JournalEntry::AccountLoaded { address });
-
-                // precompiles are hot loaded so we need to take that into account
-                let is_cold = !is_precompile(address, self.num_of_precompiles);
-
-                (vac.insert(account), is_cold)
-            }
-        })
+        load_account_into(
+            &mut self.state,
+            &mut self.journal,
+            &mut self.db_reads,
+            self.num_of_precompiles,
+            address,
+            db,
+        )
     }
 
     // first is is_cold second bool is exists.
@@ -588,16 +655,29 @@ impl JournaledState {
         db: &mut DB,
     ) -> Result<(bool, bool), DB::Error> {
         let is_before_spurious_dragon = self.is_before_spurious_dragon;
+        let (is_cold, existence) = self.load_account_existence(address, db)?;
+        Ok((is_cold, existence.exists(is_before_spurious_dragon)))
+    }
+
+    /// Loads the account and returns if it is cold and if it is not existing, empty or existing.
+    ///
+    /// Unlike [`Self::load_account_exist`] this does not depend on the fork, so tooling can
+    /// tell empty accounts apart from missing ones.
+    pub fn load_account_existence<DB: Database>(
+        &mut self,
+        address: B160,
+        db: &mut DB,
+    ) -> Result<(bool, AccountExistence), DB::Error> {
         let (acc, is_cold) = self.load_account(address, db)?;
 
-        let exist = if is_before_spurious_dragon {
-            let is_existing = !acc.is_loaded_as_not_existing();
-            let is_touched = acc.is_touched();
-            is_existing || is_touched
+        let existence = if acc.is_loaded_as_not_existing() && !acc.is_touched() {
+            AccountExistence::NotExisting
+        } else if acc.is_empty() {
+            AccountExistence::Empty
         } else {
-            !acc.is_empty()
+            AccountExistence::Exists
         };
-        Ok((is_cold, exist))
+        Ok((is_cold, existence))
     }
 
     pub fn load_code<DB: Database>(
@@ -605,19 +685,35 @@ impl JournaledState {
         address: B160,
         db: &mut DB,
     ) -> Result<(&mut Account, bool), DB::Error> {
-        let (acc, is_cold) = self.load_account(address, db)?;
+        let (acc, is_cold) = load_account_into(
+            &mut self.state,
+            &mut self.journal,
+            &mut self.db_reads,
+            self.num_of_precompiles,
+            address,
+            db,
+        )?;
+        Self::load_account_code(acc, &mut self.db_reads, db)?;
+        Ok((acc, is_cold))
+    }
+
+    fn load_account_code<DB: Database>(
+        acc: &mut Account,
+        db_reads: &mut DatabaseReadCounts,
+        db: &mut DB,
+    ) -> Result<(), DB::Error> {
         if acc.info.code.is_none() {
             if acc.info.code_hash == KECCAK_EMPTY {
                 let empty = Bytecode::new();
                 acc.info.code = Some(empty);
             } else {
+                db_reads.code_by_hash += 1;
                 let code = db.code_by_hash(acc.info.code_hash)?;
                 acc.info.code = Some(code);
             }
         }
-        Ok((acc, is_cold))
+        Ok(())
     }
-
     // account is already present and loaded.
     pub fn sload<DB: Database>(
         &mut self,
@@ -625,33 +721,8 @@ impl JournaledState {
         key: U256,
         db: &mut DB,
     ) -> Result<(U256, bool), DB::Error> {
-        let account = self.state.get_mut(&address).unwrap(); // asume acc is hot
-        let is_newly_created = account.is_newly_created();
-        let load = match account.storage.entry(key) {
-            Entry::Occupied(occ) => (occ.get().present_value, false),
-            Entry::Vacant(vac) => {
-                // if storage was cleared, we dont need to ping db.
-                let value = if is_newly_created {
-                    U256::ZERO
-                } else {
-                    db.storage(address, key)?
-                };
-                // add it to journal as cold loaded.
-                self.journal
-                    .last_mut()
-                    .unwrap()
-                    .push(JournalEntry::StorageChange {
-                        address,
-                        key,
-                        had_value: None,
-                    });
-
-                vac.insert(StorageSlot::new(value));
-
-                (value, true)
-            }
-        };
-        Ok(load)
+        let (slot, is_cold) = self.load_slot(address, key, db)?;
+        Ok((slot.present_value, is_cold))
     }
 
     /// account should already be present in our state.
@@ -664,28 +735,68 @@ impl JournaledState {
         db: &mut DB,
     ) -> Result<(U256, U256, U256, bool), DB::Error> {
         // assume that acc exists and load the slot.
-        let (present, is_cold) = self.sload(address, key, db)?;
-        let acc = self.state.get_mut(&address).unwrap();
-
-        // if there is no original value in dirty return present value, that is our original.
-        let slot = acc.storage.get_mut(&key).unwrap();
+        let (slot, is_cold) = self.load_slot(address, key, db)?;
+        let present = slot.present_value;
 
         // new value is same as present, we dont need to do anything
         if present == new {
             return Ok((slot.original_value, present, new, is_cold));
         }
 
-        self.journal
-            .last_mut()
-            .unwrap()
-            .push(JournalEntry::StorageChange {
+        // insert value into present state.
+        slot.present_value = new;
+        let original = slot.original_value;
+        push_journal(
+            &mut self.journal,
+            JournalEntry::StorageChange {
                 address,
                 key,
                 had_value: Some(present),
-            });
-        // insert value into present state.
-        slot.present_value = new;
-        Ok((slot.original_value, present, new, is_cold))
+            },
+        );
+        Ok((original, present, new, is_cold))
+    }
+
+    /// Loads the storage slot of the account, account is loaded too if it is not present.
+    /// Returns the slot and if it was cold.
+    fn load_slot<DB: Database>(
+        &mut self,
+        address: B160,
+        key: U256,
+        db: &mut DB,
+    ) -> Result<(&mut StorageSlot, bool), DB::Error> {
+        let (account, _) = load_account_into(
+            &mut self.state,
+            &mut self.journal,
+            &mut self.db_reads,
+            self.num_of_precompiles,
+            address,
+            db,
+        )?;
+        let is_newly_created = account.is_newly_created();
+        Ok(match account.storage.entry(key) {
+            Entry::Occupied(occ) => (occ.into_mut(), false),
+            Entry::Vacant(vac) => {
+                // if storage was cleared, we dont need to ping db.
+                let value = if is_newly_created {
+                    U256::ZERO
+                } else {
+                    self.db_reads.storage += 1;
+                    db.storage(address, key)?
+                };
+                // add it to journal as cold loaded.
+                push_journal(
+                    &mut self.journal,
+                    JournalEntry::StorageChange {
+                        address,
+                        key,
+                        had_value: None,
+                    },
+                );
+
+                (vac.insert(StorageSlot::new(value)), true)
+            }
+        })
     }
 
     /// Read transient storage tied to the account.
@@ -728,14 +839,14 @@ impl JournaledState {
 
         if let Some(had_value) = had_value {
             // insert in journal only if value was changed.
-            self.journal
-                .last_mut()
-                .unwrap()
-                .push(JournalEntry::TransientStorageChange {
+            push_journal(
+                &mut self.journal,
+                JournalEntry::TransientStorageChange {
                     address,
                     key,
                     had_value,
-                });
+                },
+            );
         }
     }
 
@@ -745,14 +856,56 @@ impl JournaledState {
     }
 }
 
+/// Loads account into the state and journals the load. Returns the account and if it is cold.
+fn load_account_into<'a, DB: Database>(
+    state: &'a mut State,
+    journal: &mut Vec<Vec<JournalEntry>>,
+    db_reads: &mut DatabaseReadCounts,
+    num_of_precompiles: usize,
+    address: B160,
+    db: &mut DB,
+) -> Result<(&'a mut Account, bool), DB::Error> {
+    Ok(match state.entry(address) {
+        Entry::Occupied(entry) => (entry.into_mut(), false),
+        Entry::Vacant(vac) => {
+            db_reads.basic += 1;
+            let account = if let Some(account) = db.basic(address)? {
+                account.into()
+            } else {
+                Account::new_not_existing()
+            };
+
+            // journal loading of account. AccessList touch.
+            push_journal(journal, JournalEntry::AccountLoaded { address });
+
+            // precompiles are hot loaded so we need to take that into account
+            let is_cold = !is_precompile(address, num_of_precompiles);
+
+            (vac.insert(account), is_cold)
+        }
+    })
+}
+
+/// Pushes entry to the journal of the current call.
+///
+/// There is always at least one journal, new ones are pushed by checkpoints and only
+/// removed up to the checkpoint that created them.
+fn push_journal(journal: &mut Vec<Vec<JournalEntry>>, entry: JournalEntry) {
+    match journal.last_mut() {
+        Some(last) => last.push(entry),
+        None => journal.push(vec![entry]),
+    }
+}
+
 /// Check if address is precompile by having assumption
 /// that precompiles are in range of 1 to N.
 #[inline(always)]
 pub fn is_precompile(address: B160, num_of_precompiles: usize) -> bool {
-    if !address[..18].iter().all(|i| *i == 0) {
+    let [prefix @ .., high, low] = address.0;
+    if !prefix.iter().all(|i| *i == 0) {
         return false;
     }
-    let num = u16::from_be_bytes([address[18], address[19]]);
+    let num = u16::from_be_bytes([high, low]);
     num.wrapping_sub(1) < num_of_precompiles as u16
 }
 
@@ -802,4 +955,95 @@ mod test {
             "0x000..3 is precompile"
         );
     }
+    #[test]
+    fn selfdestruct_to_self() {
+        use crate::primitives::AccountInfo;
+        use crate::InMemoryDB;
+
+        let address = B160([0x10; 20]);
+        let balance = U256::from(100);
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(address, AccountInfo::from_balance(balance));
+
+        for burn in [true, false] {
+            let mut journal = JournaledState::new(0);
+            journal.burn_selfdestruct_to_self = burn;
+            journal.load_account(address, &mut db).unwrap();
+            let checkpoint = journal.checkpoint();
+
+            let result = journal.selfdestruct(address, address, &mut db).unwrap();
+            assert!(result.had_value);
+            let account = &journal.state[&address];
+            assert_eq!(account.is_selfdestructed(), burn);
+            let expected = if burn { U256::ZERO } else { balance };
+            assert_eq!(account.info.balance, expected);
+
+            journal.checkpoint_revert(checkpoint);
+            let account = &journal.state[&address];
+            assert!(!account.is_selfdestructed());
+            assert_eq!(account.info.balance, balance);
+        }
+    }
+    #[test]
+    fn account_existence() {
+        use crate::primitives::AccountInfo;
+        use crate::InMemoryDB;
+
+        let missing = B160([0x01; 20]);
+        let empty = B160([0x02; 20]);
+        let funded = B160([0x03; 20]);
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(empty, AccountInfo::default());
+        db.insert_account_info(funded, AccountInfo::from_balance(U256::from(1)));
+
+        let mut journal = JournaledState::new_legacy(0);
+        let cases = [
+            (missing, AccountExistence::NotExisting, false),
+            (empty, AccountExistence::Empty, true),
+            (funded, AccountExistence::Exists, true),
+        ];
+        for (address, existence, legacy_exists) in cases {
+            let (is_cold, loaded) = journal.load_account_existence(address, &mut db).unwrap();
+            assert!(is_cold);
+            assert_eq!(loaded, existence);
+            assert_eq!(loaded.exists(true), legacy_exists);
+            assert_eq!(
+                journal.load_account_exist(address, &mut db).unwrap(),
+                (false, legacy_exists)
+            );
+        }
+        assert!(!AccountExistence::Empty.exists(false));
+
+        // touching the missing account makes it an empty account.
+        journal.touch(&missing);
+        let (_, loaded) = journal.load_account_existence(missing, &mut db).unwrap();
+        assert_eq!(loaded, AccountExistence::Empty);
+    }
+
+    #[test]
+    fn account_not_loaded() {
+        use crate::InMemoryDB;
+
+        let address = B160([0x10; 20]);
+        let mut db = InMemoryDB::default();
+        let mut journal = JournaledState::new(0);
+
+        assert!(journal.account(address).is_none());
+        assert_eq!(
+            journal.set_code(address, Bytecode::new()),
+            Err(InternalError::AccountNotLoaded(address))
+        );
+        assert_eq!(
+            journal.inc_nonce(address),
+            Err(InternalError::AccountNotLoaded(address))
+        );
+
+        // storage access loads the account.
+        assert_eq!(
+            journal.sstore(address, U256::ZERO, U256::from(1), &mut db),
+            Ok((U256::ZERO, U256::ZERO, U256::from(1), true))
+        );
+        assert!(journal.account(address).is_some());
+        assert_eq!(journal.inc_nonce(address), Ok(Some(1)));
+    }
 }
@@ -1,10 +1,27 @@
 #![cfg_attr(not(feature = "std"), no_std)]
-
+#![cfg_attr(
+    all(feature = "no_panic", not(test)),
+    deny(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::indexing_slicing,
+        clippy::panic,
+        clippy::unreachable
+    )
+)]
+
+mod artifacts;
+mod backtest;
 pub mod db;
+pub mod erc20;
 mod evm;
 mod evm_impl;
+#[cfg(feature = "std")]
+mod evm_pool;
 mod inspector;
 mod journaled_state;
+#[cfg(test)]
+mod test_utils;
 
 #[cfg(all(feature = "with-serde", not(feature = "serde")))]
 compile_error!("`with-serde` feature has been renamed to `serde`.");
@@ -12,10 +29,14 @@ compile_error!("`with-serde` feature has been renamed to `serde`.");
 pub(crate) const USE_GAS: bool = !cfg!(feature = "no_gas_measuring");
 pub type DummyStateDB = InMemoryDB;
 
+pub use artifacts::{AccessSet, ExecutionArtifacts, ExecutionMetrics};
+pub use backtest::{backtest, BacktestResult, BlockStateProvider};
 pub use db::{Database, DatabaseCommit, InMemoryDB};
-pub use evm::{evm_inner, new, EVM};
-pub use evm_impl::EVMData;
-pub use journaled_state::{JournalEntry, JournaledState};
+pub use evm::{evm_inner, evm_inner_with_memory_pool, new, validate_env, LogsHook, EVM};
+pub use evm_impl::{validate_env_with_spec, EVMData};
+#[cfg(feature = "std")]
+pub use evm_pool::{PooledEvm, SyncEvmPool};
+pub use journaled_state::{AccountExistence, JournalEntry, JournaledState};
 
 extern crate alloc;
 
@@ -0,0 +1,36 @@
+//! Fixtures shared by the tests of the crate.
+
+use crate::primitives::{AccountInfo, Bytecode, Bytes, TransactTo, B160, U256};
+use crate::{Database, InMemoryDB, EVM};
+
+/// Sender of the test transactions.
+pub const CALLER: B160 = B160([0x01; 20]);
+/// Contract called by the test transactions.
+pub const OUTER: B160 = B160([0x10; 20]);
+/// Contract called by [OUTER].
+pub const INNER: B160 = B160([0x20; 20]);
+
+/// Account of a deployed contract with `code`.
+pub fn contract(code: &[u8]) -> AccountInfo {
+    AccountInfo::new(
+        U256::ZERO,
+        1,
+        Bytecode::new_raw(Bytes::copy_from_slice(code)),
+    )
+}
+
+/// EVM over `db` with a transaction from [CALLER] to `to`.
+pub fn evm_calling<DB: Database>(db: DB, to: B160) -> EVM<DB> {
+    let mut evm = crate::new();
+    evm.database(db);
+    evm.env.tx.caller = CALLER;
+    evm.env.tx.transact_to = TransactTo::Call(to);
+    evm
+}
+
+/// EVM with `code` deployed at [OUTER] and a transaction that calls it.
+pub fn evm_with_contract(code: &[u8]) -> EVM<InMemoryDB> {
+    let mut db = InMemoryDB::default();
+    db.insert_account_info(OUTER, contract(code));
+    evm_calling(db, OUTER)
+}